@@ -2,10 +2,9 @@ use std::net::UdpSocket;
 use std::str::FromStr;
 
 use domain::base::{
-    message::Message, opt::AllOptData, Dname, MessageBuilder, Rtype,
-    StaticCompressor, StreamTarget,
+    message::Message, Dname, MessageBuilder, Rtype, StaticCompressor,
+    StreamTarget,
 };
-use domain::rdata::AllRecordData;
 
 fn create_message() -> StreamTarget<Vec<u8>> {
     // Create a message builder wrapping a compressor wrapping a stream
@@ -70,33 +69,6 @@ fn main() {
     }
     .unwrap();
 
-    for question in response.question() {
-        println!("{}", question.unwrap());
-    }
-
-    // Unpack and parse with all known record types
-    for record in response.answer().unwrap().limit_to::<AllRecordData<_, _>>()
-    {
-        println!("{}", record.unwrap());
-    }
-
-    for record in response
-        .authority()
-        .unwrap()
-        .limit_to::<AllRecordData<_, _>>()
-    {
-        println!("{}", record.unwrap());
-    }
-
-    // Display is only implemented for some OPTs at the time of writing
-    for option in response.opt().unwrap().iter::<AllOptData<_, _>>() {
-        let opt = option.unwrap();
-        match opt {
-            AllOptData::Nsid(nsid) => println!("{}", nsid),
-            AllOptData::ExtendedError(extendederror) => {
-                println!("{}", extendederror)
-            }
-            _ => println!("NO OPT!"),
-        }
-    }
+    // Print the complete response in the style of dig.
+    print!("{}", response.display_dig());
 }
@@ -0,0 +1,495 @@
+//! Presenting DNS messages in the style of `dig`.
+//!
+//! When debugging, it is often helpful to look at a complete DNS message in
+//! a human readable form. The widely used `dig` tool has established a
+//! format for this: a comment block describing the header, followed by an
+//! optional block for the EDNS OPT record, followed by the question section
+//! and the three record sections with their records given in zone file
+//! presentation format.
+//!
+//! This module provides a type, [`DigDisplay`], that wraps a reference to a
+//! [`Message`] and implements `Display` producing this format. Values are
+//! created via [`Message::display_dig`] or, if you want to change what
+//! exactly is included in the output, via [`Message::display_dig_with`]
+//! and a [`DigOptions`] value.
+//!
+//! The reverse direction, turning text in this format back into a message,
+//! is provided by the `zonefile::dig` module if the `zonefile` feature is
+//! enabled.
+
+use super::iana::{Opcode, Rtype};
+use super::message::{Message, RecordSection};
+use super::name::{Dname, ParsedDname};
+use super::opt::{AllOptData, OptData};
+use super::record::ParsedRecord;
+use crate::rdata::AllRecordData;
+use core::fmt;
+use octseq::octets::Octets;
+
+//------------ DigOptions ----------------------------------------------------
+
+/// Options for presenting a message in `dig` style.
+///
+/// The fields determine which parts of the message will be included in
+/// the output. Via the `Default` implementation, you get options that
+/// print everything. This is what [`Message::display_dig`] uses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DigOptions {
+    /// Only print the record data of the answer section.
+    ///
+    /// This is similar to `dig +short`. If this is `true`, all the other
+    /// fields are ignored.
+    pub short: bool,
+
+    /// Print the header block.
+    pub header: bool,
+
+    /// Print the OPT pseudo-section if the message has an OPT record.
+    pub opt: bool,
+
+    /// Print the question section.
+    pub question: bool,
+
+    /// Print the answer section.
+    pub answer: bool,
+
+    /// Print the authority section.
+    pub authority: bool,
+
+    /// Print the additional section.
+    ///
+    /// The OPT record is never included in the additional section.
+    pub additional: bool,
+
+    /// Include the TTL of the records.
+    pub ttl: bool,
+}
+
+impl DigOptions {
+    /// Creates options for the short format.
+    ///
+    /// Only the record data of the records in the answer section will be
+    /// printed, one record per line.
+    pub fn short() -> Self {
+        DigOptions {
+            short: true,
+            ..Default::default()
+        }
+    }
+}
+
+//--- Default
+
+impl Default for DigOptions {
+    fn default() -> Self {
+        DigOptions {
+            short: false,
+            header: true,
+            opt: true,
+            question: true,
+            answer: true,
+            authority: true,
+            additional: true,
+            ttl: true,
+        }
+    }
+}
+
+//------------ Message -------------------------------------------------------
+
+/// # Presenting the Message
+///
+impl<Octs: Octets> Message<Octs> {
+    /// Returns a value that displays the message in `dig` style.
+    pub fn display_dig(&self) -> DigDisplay<'_, Octs> {
+        self.display_dig_with(DigOptions::default())
+    }
+
+    /// Returns a value that displays the message with the given options.
+    pub fn display_dig_with(
+        &self,
+        options: DigOptions,
+    ) -> DigDisplay<'_, Octs> {
+        DigDisplay {
+            message: self,
+            options,
+        }
+    }
+}
+
+//------------ DigDisplay ----------------------------------------------------
+
+/// A helper type displaying a message in `dig` style.
+///
+/// A value of this type can be acquired via [`Message::display_dig`] and
+/// [`Message::display_dig_with`]. It only implements `Display`.
+///
+/// Because a message is parsed lazily, parsing errors are only encountered
+/// while presenting the message. In this case, a comment line describing
+/// the error is included in the output and the remainder of the message is
+/// skipped.
+pub struct DigDisplay<'a, Octs> {
+    /// The message to display.
+    message: &'a Message<Octs>,
+
+    /// The options for displaying.
+    options: DigOptions,
+}
+
+impl<'a, Octs: Octets> DigDisplay<'a, Octs> {
+    /// Returns the message.
+    pub fn message(&self) -> &'a Message<Octs> {
+        self.message
+    }
+
+    /// Returns the options.
+    pub fn options(&self) -> DigOptions {
+        self.options
+    }
+
+    /// Formats the message in short format.
+    fn fmt_short(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let section = match self.message.answer() {
+            Ok(section) => section,
+            Err(err) => return writeln!(f, ";; ERROR: {}", err),
+        };
+        for record in section {
+            let record = match record {
+                Ok(record) => record,
+                Err(err) => return writeln!(f, ";; ERROR: {}", err),
+            };
+            match record.into_record::<RecordData<'a, Octs>>() {
+                Ok(Some(record)) => writeln!(f, "{}", record.data())?,
+                Ok(None) => {}
+                Err(err) => return writeln!(f, ";; ERROR: {}", err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats the header block.
+    fn fmt_header(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header = self.message.header();
+        let counts = self.message.header_counts();
+        write!(f, ";; ->>HEADER<<- opcode: {}, status: ", header.opcode())?;
+        match self.message.opt() {
+            Some(opt) => write!(f, "{}", opt.rcode(header))?,
+            None => write!(f, "{}", header.rcode())?,
+        }
+        writeln!(f, ", id: {}", header.id())?;
+
+        f.write_str(";; flags:")?;
+        for (set, name) in [
+            (header.qr(), "qr"),
+            (header.aa(), "aa"),
+            (header.tc(), "tc"),
+            (header.rd(), "rd"),
+            (header.ra(), "ra"),
+            (header.z(), "z"),
+            (header.ad(), "ad"),
+            (header.cd(), "cd"),
+        ] {
+            if set {
+                write!(f, " {}", name)?;
+            }
+        }
+        let names = SectionNames::new(header.opcode());
+        writeln!(
+            f,
+            "; {}: {}, {}: {}, {}: {}, {}: {}",
+            names.question,
+            counts.qdcount(),
+            names.answer,
+            counts.ancount(),
+            names.authority,
+            counts.nscount(),
+            names.additional,
+            counts.arcount()
+        )
+    }
+
+    /// Formats the OPT pseudo-section if there is an OPT record.
+    fn fmt_opt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opt = match self.message.opt() {
+            Some(opt) => opt,
+            None => return Ok(()),
+        };
+        f.write_str("\n;; OPT PSEUDOSECTION:\n")?;
+        write!(f, "; EDNS: version: {}, flags:", opt.version())?;
+        if opt.dnssec_ok() {
+            f.write_str(" do")?;
+        }
+        writeln!(f, "; udp: {}", opt.udp_payload_size())?;
+        for option in opt.iter::<AllOptData<_, Dname<_>>>() {
+            match option {
+                Ok(option) => writeln!(f, "; {}: {}", option.code(), option)?,
+                Err(err) => return writeln!(f, "; ERROR: {}", err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats the question section.
+    fn fmt_question(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = SectionNames::new(self.message.header().opcode());
+        let mut heading = Some(names.question);
+        for question in self.message.question() {
+            if let Some(name) = heading.take() {
+                writeln!(f, "\n;; {} SECTION:", name)?;
+            }
+            match question {
+                Ok(question) => writeln!(
+                    f,
+                    ";{}.\t\t{}\t{}",
+                    question.qname(),
+                    question.qclass(),
+                    question.qtype()
+                )?,
+                Err(err) => return writeln!(f, ";; ERROR: {}", err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats a record section.
+    ///
+    /// Like `dig`, only prints the section if there are records to print.
+    /// Returns `Ok(false)` if parsing failed and no more sections should be
+    /// attempted.
+    fn fmt_section(
+        &self,
+        f: &mut fmt::Formatter,
+        name: &str,
+        section: RecordSection<'a, Octs>,
+    ) -> Result<bool, fmt::Error> {
+        let mut heading = Some(name);
+        for record in section {
+            match record {
+                Ok(record) => {
+                    if record.rtype() == Rtype::Opt {
+                        continue;
+                    }
+                    if let Some(name) = heading.take() {
+                        writeln!(f, "\n;; {} SECTION:", name)?;
+                    }
+                    if !self.fmt_record(f, record)? {
+                        return Ok(false);
+                    }
+                }
+                Err(err) => {
+                    writeln!(f, ";; ERROR: {}", err)?;
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Formats a single record.
+    ///
+    /// OPT records are skipped by the caller since they are shown in their
+    /// own pseudo-section.
+    fn fmt_record(
+        &self,
+        f: &mut fmt::Formatter,
+        record: ParsedRecord<'a, Octs>,
+    ) -> Result<bool, fmt::Error> {
+        let record = match record.into_record::<RecordData<'a, Octs>>() {
+            Ok(Some(record)) => record,
+            Ok(None) => return Ok(true),
+            Err(err) => {
+                writeln!(f, ";; ERROR: {}", err)?;
+                return Ok(false);
+            }
+        };
+        write!(f, "{}.\t", record.owner())?;
+        if self.options.ttl {
+            write!(f, "{}\t", record.ttl())?;
+        }
+        writeln!(
+            f,
+            "{}\t{}\t{}",
+            record.class(),
+            record.rtype(),
+            record.data()
+        )?;
+        Ok(true)
+    }
+}
+
+//--- Display
+
+impl<'a, Octs: Octets> fmt::Display for DigDisplay<'a, Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.options.short {
+            return self.fmt_short(f);
+        }
+        if self.options.header {
+            self.fmt_header(f)?;
+        }
+        if self.options.opt {
+            self.fmt_opt(f)?;
+        }
+        if self.options.question {
+            self.fmt_question(f)?;
+        }
+
+        let names = SectionNames::new(self.message.header().opcode());
+        let mut section = match self.message.answer() {
+            Ok(section) => section,
+            Err(err) => return writeln!(f, ";; ERROR: {}", err),
+        };
+        for (show, name) in [
+            (self.options.answer, names.answer),
+            (self.options.authority, names.authority),
+            (self.options.additional, names.additional),
+        ] {
+            if show && !self.fmt_section(f, name, section)? {
+                return Ok(());
+            }
+            section = match section.next_section() {
+                Ok(Some(section)) => section,
+                Ok(None) => break,
+                Err(err) => return writeln!(f, ";; ERROR: {}", err),
+            }
+        }
+        Ok(())
+    }
+}
+
+//------------ RecordData ----------------------------------------------------
+
+/// The record data type used for presenting records.
+type RecordData<'a, Octs> = AllRecordData<
+    <Octs as Octets>::Range<'a>,
+    ParsedDname<<Octs as Octets>::Range<'a>>,
+>;
+
+//------------ SectionNames --------------------------------------------------
+
+/// The names of the four sections of a message.
+///
+/// For UPDATE messages, the sections have different names than for all
+/// other messages.
+struct SectionNames {
+    question: &'static str,
+    answer: &'static str,
+    authority: &'static str,
+    additional: &'static str,
+}
+
+impl SectionNames {
+    fn new(opcode: Opcode) -> Self {
+        if opcode == Opcode::Update {
+            SectionNames {
+                question: "ZONE",
+                answer: "PREREQUISITE",
+                authority: "UPDATE",
+                additional: "ADDITIONAL",
+            }
+        } else {
+            SectionNames {
+                question: "QUESTION",
+                answer: "ANSWER",
+                authority: "AUTHORITY",
+                additional: "ADDITIONAL",
+            }
+        }
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::iana::{Rcode, Rtype};
+    use crate::base::message_builder::MessageBuilder;
+    use crate::base::name::Dname;
+    use crate::base::opt::rfc7873::ClientCookie;
+    use crate::base::opt::Cookie;
+    use crate::rdata::{Ns, A};
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    fn test_message() -> Message<Vec<u8>> {
+        let mut msg = MessageBuilder::new_vec();
+        msg.header_mut().set_id(4711);
+        msg.header_mut().set_qr(true);
+        msg.header_mut().set_rd(true);
+        msg.header_mut().set_rcode(Rcode::NoError);
+        let mut msg = msg.question();
+        msg.push((Dname::vec_from_str("example.com.").unwrap(), Rtype::A))
+            .unwrap();
+        let mut msg = msg.answer();
+        msg.push((
+            Dname::vec_from_str("example.com.").unwrap(),
+            3600,
+            A::from_octets(192, 0, 2, 1),
+        ))
+        .unwrap();
+        let mut msg = msg.authority();
+        msg.push((
+            Dname::vec_from_str("example.com.").unwrap(),
+            86400,
+            Ns::new(Dname::vec_from_str("ns.example.com.").unwrap()),
+        ))
+        .unwrap();
+        let mut msg = msg.additional();
+        msg.opt(|opt| {
+            opt.set_udp_payload_size(1232);
+            opt.set_dnssec_ok(true);
+            opt.push(&Cookie::from(ClientCookie::new([
+                1, 2, 3, 4, 5, 6, 7, 8,
+            ])))
+        })
+        .unwrap();
+        msg.into_message()
+    }
+
+    #[test]
+    fn display_full() {
+        assert_eq!(
+            test_message().display_dig().to_string(),
+            ";; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 4711\n\
+             ;; flags: qr rd; QUERY: 1, ANSWER: 1, AUTHORITY: 1, \
+             ADDITIONAL: 1\n\
+             \n\
+             ;; OPT PSEUDOSECTION:\n\
+             ; EDNS: version: 0, flags: do; udp: 1232\n\
+             ; COOKIE: 0102030405060708\n\
+             \n\
+             ;; QUESTION SECTION:\n\
+             ;example.com.\t\tIN\tA\n\
+             \n\
+             ;; ANSWER SECTION:\n\
+             example.com.\t3600\tIN\tA\t192.0.2.1\n\
+             \n\
+             ;; AUTHORITY SECTION:\n\
+             example.com.\t86400\tIN\tNS\tns.example.com.\n"
+        );
+    }
+
+    #[test]
+    fn display_options() {
+        let msg = test_message();
+        assert_eq!(
+            msg.display_dig_with(DigOptions::short()).to_string(),
+            "192.0.2.1\n"
+        );
+        assert_eq!(
+            msg.display_dig_with(DigOptions {
+                header: false,
+                opt: false,
+                question: false,
+                authority: false,
+                additional: false,
+                ttl: false,
+                ..Default::default()
+            })
+            .to_string(),
+            "\n;; ANSWER SECTION:\nexample.com.\tIN\tA\t192.0.2.1\n"
+        );
+    }
+}
@@ -300,6 +300,105 @@ impl Header {
     }
 }
 
+//--- Serialize and Deserialize
+
+// The header serializes into its individual fields for human readable
+// formats and into its four octets of wire format for compact formats.
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Header {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serde::Serialize::serialize(
+                &HeaderFields::from(*self),
+                serializer,
+            )
+        } else {
+            serializer.serialize_newtype_struct("Header", &self.inner)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Header {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            <HeaderFields as serde::Deserialize>::deserialize(deserializer)
+                .map(Into::into)
+        } else {
+            #[derive(serde::Deserialize)]
+            #[serde(rename = "Header")]
+            struct Wire([u8; 4]);
+
+            <Wire as serde::Deserialize>::deserialize(deserializer)
+                .map(|wire| Header { inner: wire.0 })
+        }
+    }
+}
+
+//------------ HeaderFields --------------------------------------------------
+
+/// The fields of the header for human readable serialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Header")]
+struct HeaderFields {
+    id: u16,
+    qr: bool,
+    opcode: Opcode,
+    aa: bool,
+    tc: bool,
+    rd: bool,
+    ra: bool,
+    z: bool,
+    ad: bool,
+    cd: bool,
+    rcode: Rcode,
+}
+
+#[cfg(feature = "serde")]
+impl From<Header> for HeaderFields {
+    fn from(header: Header) -> Self {
+        HeaderFields {
+            id: header.id(),
+            qr: header.qr(),
+            opcode: header.opcode(),
+            aa: header.aa(),
+            tc: header.tc(),
+            rd: header.rd(),
+            ra: header.ra(),
+            z: header.z(),
+            ad: header.ad(),
+            cd: header.cd(),
+            rcode: header.rcode(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<HeaderFields> for Header {
+    fn from(fields: HeaderFields) -> Self {
+        let mut res = Header::new();
+        res.set_id(fields.id);
+        res.set_qr(fields.qr);
+        res.set_opcode(fields.opcode);
+        res.set_aa(fields.aa);
+        res.set_tc(fields.tc);
+        res.set_rd(fields.rd);
+        res.set_ra(fields.ra);
+        res.set_z(fields.z);
+        res.set_ad(fields.ad);
+        res.set_cd(fields.cd);
+        res.set_rcode(fields.rcode);
+        res
+    }
+}
+
 //------------ Flags ---------------------------------------------------
 
 /// The flags contained in the DNS message header.
@@ -1121,4 +1220,61 @@ mod test {
         let f1 = Flags::from_str("XXXX");
         assert!(f1.is_err());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn header_ser_de() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let mut header = Header::new();
+        header.set_id(0x1234);
+        header.set_qr(true);
+        header.set_rd(true);
+        header.set_rcode(Rcode::NXDomain);
+
+        assert_tokens(
+            &header.compact(),
+            &[
+                Token::NewtypeStruct { name: "Header" },
+                Token::Tuple { len: 4 },
+                Token::U8(0x12),
+                Token::U8(0x34),
+                Token::U8(0x81),
+                Token::U8(0x03),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(
+            &header.readable(),
+            &[
+                Token::Struct {
+                    name: "Header",
+                    len: 11,
+                },
+                Token::Str("id"),
+                Token::U16(0x1234),
+                Token::Str("qr"),
+                Token::Bool(true),
+                Token::Str("opcode"),
+                Token::Str("QUERY"),
+                Token::Str("aa"),
+                Token::Bool(false),
+                Token::Str("tc"),
+                Token::Bool(false),
+                Token::Str("rd"),
+                Token::Bool(true),
+                Token::Str("ra"),
+                Token::Bool(false),
+                Token::Str("z"),
+                Token::Bool(false),
+                Token::Str("ad"),
+                Token::Bool(false),
+                Token::Str("cd"),
+                Token::Bool(false),
+                Token::Str("rcode"),
+                Token::U8(3),
+                Token::StructEnd,
+            ],
+        );
+    }
 }
@@ -0,0 +1,31 @@
+//! Address family numbers.
+//!
+//! This registry defines the numbers used to identify the family of a
+//! network address. In DNS, it is used by the APL record defined in
+//! [RFC 3123].
+//!
+//! [RFC 3123]: https://tools.ietf.org/html/rfc3123
+
+//------------ AddressFamily -------------------------------------------------
+
+int_enum! {
+    /// Address family numbers.
+    ///
+    /// The address family determines how an address is to be interpreted.
+    /// Only the two families for IP addresses are currently used in DNS.
+    ///
+    /// For the complete list of registered values see the
+    /// [IANA registration].
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/address-family-numbers/address-family-numbers.xhtml
+    =>
+    AddressFamily, u16;
+
+    /// IP version 4.
+    (Ipv4 => 1, b"IPv4")
+
+    /// IP version 6.
+    (Ipv6 => 2, b"IPv6")
+}
+
+int_enum_str_decimal!(AddressFamily, u16);
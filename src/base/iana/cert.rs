@@ -0,0 +1,53 @@
+//! Certificate types.
+//!
+//! This registry defines the values of the type field of the CERT record
+//! defined in [RFC 4398].
+//!
+//! [RFC 4398]: https://tools.ietf.org/html/rfc4398
+
+//------------ CertType ------------------------------------------------------
+
+int_enum! {
+    /// CERT certificate types.
+    ///
+    /// The certificate type determines the format of the certificate or
+    /// CRL contained in a CERT record.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/cert-rr-types/cert-rr-types.xhtml
+    =>
+    CertType, u16;
+
+    /// An X.509 certificate as per PKIX.
+    (Pkix => 1, b"PKIX")
+
+    /// An SPKI certificate.
+    (Spki => 2, b"SPKI")
+
+    /// An OpenPGP packet.
+    (Pgp => 3, b"PGP")
+
+    /// The URL of an X.509 data object.
+    (Ipkix => 4, b"IPKIX")
+
+    /// The URL of an SPKI certificate.
+    (Ispki => 5, b"ISPKI")
+
+    /// The fingerprint and URL of an OpenPGP packet.
+    (Ipgp => 6, b"IPGP")
+
+    /// An attribute certificate.
+    (Acpkix => 7, b"ACPKIX")
+
+    /// The URL of an attribute certificate.
+    (Iacpkix => 8, b"IACPKIX")
+
+    /// A URI private certificate type.
+    (Uri => 253, b"URI")
+
+    /// An OID private certificate type.
+    (Oid => 254, b"OID")
+}
+
+int_enum_str_with_decimal!(CertType, u16, "unknown certificate type");
@@ -0,0 +1,116 @@
+//! DANE parameters.
+//!
+//! These three registries define the values of the first three fields of
+//! the TLSA record defined in [RFC 6698]. The SMIMEA record defined in
+//! [RFC 8162] uses the same fields and registries. The mnemonics are those
+//! suggested by [RFC 7218].
+//!
+//! [RFC 6698]: https://tools.ietf.org/html/rfc6698
+//! [RFC 7218]: https://tools.ietf.org/html/rfc7218
+//! [RFC 8162]: https://tools.ietf.org/html/rfc8162
+
+//------------ TlsaCertUsage -------------------------------------------------
+
+int_enum! {
+    /// TLSA certificate usages.
+    ///
+    /// The certificate usage specifies how the certificate association
+    /// presented by a TLSA record is to be used when verifying the
+    /// certificate of a peer.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2014-04-04.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dane-parameters/dane-parameters.xhtml#certificate-usages
+    =>
+    TlsaCertUsage, u8;
+
+    /// The association is a CA certificate checked via PKIX.
+    (PkixTa => 0, b"PKIX-TA")
+
+    /// The association is the end entity certificate checked via PKIX.
+    (PkixEe => 1, b"PKIX-EE")
+
+    /// The association is a trust anchor for the certificate chain.
+    (DaneTa => 2, b"DANE-TA")
+
+    /// The association is the end entity certificate.
+    (DaneEe => 3, b"DANE-EE")
+
+    /// Reserved for private use.
+    (PrivCert => 255, b"PrivCert")
+}
+
+int_enum_str_decimal!(TlsaCertUsage, u8);
+
+//------------ TlsaSelector --------------------------------------------------
+
+int_enum! {
+    /// TLSA selectors.
+    ///
+    /// The selector specifies which part of a certificate is matched
+    /// against the certificate association data.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2014-04-04.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dane-parameters/dane-parameters.xhtml#selectors
+    =>
+    TlsaSelector, u8;
+
+    /// The full certificate is matched.
+    (Cert => 0, b"Cert")
+
+    /// The DER-encoded SubjectPublicKeyInfo of the certificate is matched.
+    (Spki => 1, b"SPKI")
+
+    /// Reserved for private use.
+    (PrivSel => 255, b"PrivSel")
+}
+
+int_enum_str_decimal!(TlsaSelector, u8);
+
+//------------ TlsaMatchingType ----------------------------------------------
+
+int_enum! {
+    /// TLSA matching types.
+    ///
+    /// The matching type specifies how the selected part of a certificate
+    /// is presented in the certificate association data.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2014-04-04.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dane-parameters/dane-parameters.xhtml#matching-types
+    =>
+    TlsaMatchingType, u8;
+
+    /// The data is the selected content itself.
+    (Full => 0, b"Full")
+
+    /// The data is the SHA-256 hash of the selected content.
+    (Sha256 => 1, b"SHA2-256")
+
+    /// The data is the SHA-512 hash of the selected content.
+    (Sha512 => 2, b"SHA2-512")
+
+    /// Reserved for private use.
+    (PrivMatch => 255, b"PrivMatch")
+}
+
+int_enum_str_decimal!(TlsaMatchingType, u8);
+
+//============ Tests =========================================================
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ser_de() {
+        use super::TlsaCertUsage;
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(&TlsaCertUsage::DaneEe, &[Token::U8(3)]);
+        assert_tokens(&TlsaCertUsage::Int(100), &[Token::U8(100)]);
+    }
+}
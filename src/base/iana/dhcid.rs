@@ -0,0 +1,52 @@
+//! DHCID parameters.
+//!
+//! These two registries define the values of the identifier type and
+//! digest type fields of the DHCID record defined in [RFC 4701].
+//!
+//! [RFC 4701]: https://tools.ietf.org/html/rfc4701
+
+//------------ DhcidIdType ---------------------------------------------------
+
+int_enum! {
+    /// DHCID identifier types.
+    ///
+    /// The identifier type specifies which DHCP client identifier was used
+    /// to calculate the digest of a DHCID record.
+    ///
+    /// The values are defined in [RFC 4701].
+    ///
+    /// [RFC 4701]: https://tools.ietf.org/html/rfc4701
+    =>
+    DhcidIdType, u16;
+
+    /// The DHCPv4 hardware type followed by the `chaddr` field.
+    (Chaddr => 0, b"CHADDR")
+
+    /// The data of the DHCPv4 client identifier option.
+    (ClientId => 1, b"CLIENTID")
+
+    /// The DHCPv6 DUID.
+    (Duid => 2, b"DUID")
+}
+
+int_enum_str_decimal!(DhcidIdType, u16);
+
+//------------ DhcidDigestType -----------------------------------------------
+
+int_enum! {
+    /// DHCID digest types.
+    ///
+    /// The digest type specifies the hash function used to calculate the
+    /// digest of a DHCID record.
+    ///
+    /// The values are defined in [RFC 4701].
+    ///
+    /// [RFC 4701]: https://tools.ietf.org/html/rfc4701
+    =>
+    DhcidDigestType, u8;
+
+    /// The digest is calculated using SHA-256.
+    (Sha256 => 1, b"SHA-256")
+}
+
+int_enum_str_decimal!(DhcidDigestType, u8);
@@ -25,7 +25,11 @@
 //! re-exported here. This is mostly so we can have associated types like
 //! `FromStrError` without having to resort to devilishly long names.
 
+pub use self::addrfam::AddressFamily;
+pub use self::cert::CertType;
 pub use self::class::Class;
+pub use self::dane::{TlsaCertUsage, TlsaMatchingType, TlsaSelector};
+pub use self::dhcid::{DhcidDigestType, DhcidIdType};
 pub use self::digestalg::DigestAlg;
 pub use self::exterr::ExtendedErrorCode;
 pub use self::nsec3::Nsec3HashAlg;
@@ -34,12 +38,18 @@ pub use self::opt::OptionCode;
 pub use self::rcode::{OptRcode, Rcode, TsigRcode};
 pub use self::rtype::Rtype;
 pub use self::secalg::SecAlg;
+pub use self::sshfp::{SshfpAlg, SshfpType};
 pub use self::svcb::SvcbParamKey;
+pub use self::zonemd::{ZonemdAlg, ZonemdScheme};
 
 #[macro_use]
 mod macros;
 
+pub mod addrfam;
+pub mod cert;
 pub mod class;
+pub mod dane;
+pub mod dhcid;
 pub mod digestalg;
 pub mod exterr;
 pub mod nsec3;
@@ -48,4 +58,6 @@ pub mod opt;
 pub mod rcode;
 pub mod rtype;
 pub mod secalg;
+pub mod sshfp;
 pub mod svcb;
+pub mod zonemd;
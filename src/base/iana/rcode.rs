@@ -18,7 +18,7 @@
 //!
 #![allow(clippy::upper_case_acronyms)]
 
-use core::{cmp, fmt, hash};
+use core::{cmp, fmt, hash, str};
 
 //------------ Rcode --------------------------------------------------------
 
@@ -250,6 +250,42 @@ impl fmt::Display for Rcode {
     }
 }
 
+//--- FromStr
+
+impl str::FromStr for Rcode {
+    type Err = RcodeFromStrError;
+
+    /// Converts a string into an rcode.
+    ///
+    /// Accepts the mnemonics produced by the `Display` impl, ignoring case,
+    /// as well as the decimal value of the rcode.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Rcode::*;
+
+        for (mnemonic, rcode) in [
+            ("NOERROR", NoError),
+            ("FORMERR", FormErr),
+            ("SERVFAIL", ServFail),
+            ("NXDOMAIN", NXDomain),
+            ("NOTIMP", NotImp),
+            ("REFUSED", Refused),
+            ("YXDOMAIN", YXDomain),
+            ("YXRRSET", YXRRSet),
+            ("NXRRSET", NXRRSet),
+            ("NOAUTH", NotAuth),
+            ("NOTZONE", NotZone),
+        ] {
+            if s.eq_ignore_ascii_case(mnemonic) {
+                return Ok(rcode);
+            }
+        }
+        match s.parse::<u8>() {
+            Ok(value) if value < 0x10 => Ok(Rcode::from_int(value)),
+            _ => Err(RcodeFromStrError),
+        }
+    }
+}
+
 //--- PartialEq and Eq
 
 impl cmp::PartialEq for Rcode {
@@ -542,7 +578,7 @@ impl OptRcode {
             NotZone => 10,
             BadVers => 16,
             BadCookie => 23,
-            Int(value) => value & 0x0F,
+            Int(value) => value & 0x0FFF,
         }
     }
 
@@ -554,7 +590,7 @@ impl OptRcode {
     /// Returns the two parts of an extended rcode value.
     pub fn to_parts(self) -> (Rcode, u8) {
         let res = self.to_int();
-        (Rcode::from_int(res as u8), (res >> 8) as u8)
+        (Rcode::from_int(res as u8), (res >> 4) as u8)
     }
 
     /// Returns the rcode part of the extended rcode.
@@ -616,6 +652,100 @@ impl fmt::Display for OptRcode {
     }
 }
 
+//--- PartialEq and Eq
+
+impl cmp::PartialEq for OptRcode {
+    fn eq(&self, other: &OptRcode) -> bool {
+        self.to_int() == other.to_int()
+    }
+}
+
+impl cmp::PartialEq<u16> for OptRcode {
+    fn eq(&self, other: &u16) -> bool {
+        self.to_int() == *other
+    }
+}
+
+impl cmp::PartialEq<OptRcode> for u16 {
+    fn eq(&self, other: &OptRcode) -> bool {
+        *self == other.to_int()
+    }
+}
+
+impl cmp::Eq for OptRcode {}
+
+//--- PartialOrd and Ord
+
+impl cmp::PartialOrd for OptRcode {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.to_int().partial_cmp(&other.to_int())
+    }
+}
+
+impl cmp::PartialOrd<u16> for OptRcode {
+    fn partial_cmp(&self, other: &u16) -> Option<cmp::Ordering> {
+        self.to_int().partial_cmp(other)
+    }
+}
+
+impl cmp::PartialOrd<OptRcode> for u16 {
+    fn partial_cmp(&self, other: &OptRcode) -> Option<cmp::Ordering> {
+        self.partial_cmp(&other.to_int())
+    }
+}
+
+impl cmp::Ord for OptRcode {
+    fn cmp(&self, other: &OptRcode) -> cmp::Ordering {
+        self.to_int().cmp(&other.to_int())
+    }
+}
+
+//--- Hash
+
+impl hash::Hash for OptRcode {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.to_int().hash(state)
+    }
+}
+
+//--- FromStr
+
+impl str::FromStr for OptRcode {
+    type Err = RcodeFromStrError;
+
+    /// Converts a string into an extended rcode.
+    ///
+    /// Accepts the mnemonics produced by the `Display` impl, ignoring case,
+    /// as well as the decimal value of the rcode.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::OptRcode::*;
+
+        for (mnemonic, rcode) in [
+            ("NOERROR", NoError),
+            ("FORMERR", FormErr),
+            ("SERVFAIL", ServFail),
+            ("NXDOMAIN", NXDomain),
+            ("NOTIMP", NotImp),
+            ("REFUSED", Refused),
+            ("YXDOMAIN", YXDomain),
+            ("YXRRSET", YXRRSet),
+            ("NXRRSET", NXRRSet),
+            ("NOAUTH", NotAuth),
+            ("NOTZONE", NotZone),
+            ("BADVER", BadVers),
+            ("BADCOOKIE", BadCookie),
+        ] {
+            if s.eq_ignore_ascii_case(mnemonic) {
+                return Ok(rcode);
+            }
+        }
+        match s.parse::<u16>() {
+            Ok(value) if value < 0x1000 => Ok(OptRcode::from_int(value)),
+            _ => Err(RcodeFromStrError),
+        }
+    }
+}
+
 //------------ TsigRcode ----------------------------------------------------
 
 int_enum! {
@@ -846,3 +976,53 @@ impl From<OptRcode> for TsigRcode {
 }
 
 int_enum_str_with_decimal!(TsigRcode, u16, "unknown TSIG error");
+
+//============ Error Types ===================================================
+
+//------------ RcodeFromStrError ---------------------------------------------
+
+/// A string could not be converted into an rcode or extended rcode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RcodeFromStrError;
+
+impl fmt::Display for RcodeFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown response code")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RcodeFromStrError {}
+
+//============ Tests =========================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn optrcode_parts() {
+        assert_eq!(OptRcode::BadCookie.to_parts(), (Rcode::YXRRSet, 1));
+        assert_eq!(
+            OptRcode::from_parts(Rcode::YXRRSet, 1),
+            OptRcode::BadCookie
+        );
+        assert_eq!(OptRcode::Int(0x0FFF).to_int(), 0x0FFF);
+        assert_eq!(OptRcode::Int(0x0FFF).ext(), 0xFF);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Rcode::from_str("nxdomain").unwrap(), Rcode::NXDomain);
+        assert_eq!(Rcode::from_str("12").unwrap(), Rcode::Int(12));
+        assert!(Rcode::from_str("BADCOOKIE").is_err());
+        assert!(Rcode::from_str("16").is_err());
+        assert_eq!(
+            OptRcode::from_str("BADCOOKIE").unwrap(),
+            OptRcode::BadCookie
+        );
+        assert_eq!(OptRcode::from_str("4000").unwrap(), OptRcode::Int(4000));
+        assert!(OptRcode::from_str("BOGUS").is_err());
+    }
+}
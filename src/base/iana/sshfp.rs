@@ -0,0 +1,79 @@
+//! SSHFP parameters.
+//!
+//! These two registries define the values of the algorithm and fingerprint
+//! type fields of the SSHFP record defined in [RFC 4255].
+//!
+//! [RFC 4255]: https://tools.ietf.org/html/rfc4255
+
+//------------ SshfpAlg ------------------------------------------------------
+
+int_enum! {
+    /// SSHFP public key algorithms.
+    ///
+    /// The algorithm specifies the type of the SSH public key a fingerprint
+    /// has been created for.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2020-02-26.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dns-sshfp-rr-parameters/dns-sshfp-rr-parameters.xhtml#dns-sshfp-rr-parameters-1
+    =>
+    SshfpAlg, u8;
+
+    /// An RSA key.
+    (Rsa => 1, b"RSA")
+
+    /// A DSA key.
+    (Dsa => 2, b"DSA")
+
+    /// An ECDSA key.
+    ///
+    /// This value has been defined in [RFC 6594].
+    ///
+    /// [RFC 6594]: https://tools.ietf.org/html/rfc6594
+    (Ecdsa => 3, b"ECDSA")
+
+    /// An Ed25519 key.
+    ///
+    /// This value has been defined in [RFC 7479].
+    ///
+    /// [RFC 7479]: https://tools.ietf.org/html/rfc7479
+    (Ed25519 => 4, b"Ed25519")
+
+    /// An Ed448 key.
+    ///
+    /// This value has been defined in [RFC 8709].
+    ///
+    /// [RFC 8709]: https://tools.ietf.org/html/rfc8709
+    (Ed448 => 6, b"Ed448")
+}
+
+int_enum_str_decimal!(SshfpAlg, u8);
+
+//------------ SshfpType -----------------------------------------------------
+
+int_enum! {
+    /// SSHFP fingerprint types.
+    ///
+    /// The fingerprint type specifies the hash function used to create the
+    /// fingerprint of an SSH public key.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2020-02-26.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dns-sshfp-rr-parameters/dns-sshfp-rr-parameters.xhtml#dns-sshfp-rr-parameters-2
+    =>
+    SshfpType, u8;
+
+    /// The fingerprint is the SHA-1 hash of the key.
+    (Sha1 => 1, b"SHA-1")
+
+    /// The fingerprint is the SHA-256 hash of the key.
+    ///
+    /// This value has been defined in [RFC 6594].
+    ///
+    /// [RFC 6594]: https://tools.ietf.org/html/rfc6594
+    (Sha256 => 2, b"SHA-256")
+}
+
+int_enum_str_decimal!(SshfpType, u8);
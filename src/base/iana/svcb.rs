@@ -41,3 +41,23 @@ impl fmt::Display for SvcbParamKey {
         f.write_str(s)
     }
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SvcbParamKey {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.to_int())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SvcbParamKey {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        <u16 as serde::Deserialize>::deserialize(deserializer)
+            .map(SvcbParamKey::from_int)
+    }
+}
@@ -0,0 +1,51 @@
+//! ZONEMD parameters.
+//!
+//! These two registries define the values of the scheme and hash algorithm
+//! fields of the ZONEMD record defined in [RFC 8976].
+//!
+//! [RFC 8976]: https://tools.ietf.org/html/rfc8976
+
+//------------ ZonemdScheme --------------------------------------------------
+
+int_enum! {
+    /// ZONEMD schemes.
+    ///
+    /// The scheme specifies the methods by which the zone data is collated
+    /// and presented to the hash function.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2021-02-09.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#zonemd-schemes
+    =>
+    ZonemdScheme, u8;
+
+    /// The digest is calculated over the zone as a single sequence.
+    (Simple => 1, b"SIMPLE")
+}
+
+int_enum_str_decimal!(ZonemdScheme, u8);
+
+//------------ ZonemdAlg -----------------------------------------------------
+
+int_enum! {
+    /// ZONEMD hash algorithms.
+    ///
+    /// The hash algorithm specifies the cryptographic hash function used
+    /// to create the zone digest.
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// This type is complete as of the registry update of 2021-02-09.
+    ///
+    /// [IANA registration]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#zonemd-hash-algorithms
+    =>
+    ZonemdAlg, u8;
+
+    /// The digest is created using SHA-384.
+    (Sha384 => 1, b"SHA384")
+
+    /// The digest is created using SHA-512.
+    (Sha512 => 2, b"SHA512")
+}
+
+int_enum_str_decimal!(ZonemdAlg, u8);
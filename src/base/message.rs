@@ -13,7 +13,7 @@
 use super::header::{Header, HeaderCounts, HeaderSection};
 use super::iana::{Class, Rcode, Rtype};
 use super::message_builder::{AdditionalBuilder, AnswerBuilder, PushError};
-use super::name::ParsedDname;
+use super::name::{ParsedDname, ToDname};
 use super::opt::{Opt, OptRecord};
 use super::question::Question;
 use super::rdata::ParseRecordData;
@@ -21,7 +21,7 @@ use super::record::{ComposeRecord, ParsedRecord, Record};
 use super::wire::{Composer, ParseError};
 use crate::rdata::rfc1035::Cname;
 use core::marker::PhantomData;
-use core::{fmt, mem};
+use core::{cmp, fmt, mem};
 use octseq::{Octets, OctetsFrom, Parser};
 
 //------------ Message -------------------------------------------------------
@@ -414,6 +414,38 @@ impl<Octs: Octets + ?Sized> Message<Octs> {
         }
     }
 
+    /// Returns whether this is the answer to some other message preserving
+    /// the case of the question names.
+    ///
+    /// This is like [`is_answer`][Self::is_answer] except that the names
+    /// in the question sections are compared case-sensitively. This is
+    /// needed when the case of the query name has been randomized to make
+    /// spoofing harder as described in draft-vixie-dnsext-dns0x20.
+    pub fn is_answer_exact<Other: Octets>(
+        &self,
+        query: &Message<Other>,
+    ) -> bool {
+        if !self.is_answer(query) {
+            return false;
+        }
+        let mut query_iter = query.question();
+        for question in self.question() {
+            let (question, query_question) =
+                match (question, query_iter.next()) {
+                    (Ok(question), Some(Ok(query_question))) => {
+                        (question, query_question)
+                    }
+                    _ => return false,
+                };
+            if question.qname().composed_cmp(query_question.qname())
+                != cmp::Ordering::Equal
+            {
+                return false;
+            }
+        }
+        true
+    }
+
     /// Returns the first question, if there is any.
     ///
     /// The method will return `None` both if there are no questions or if
@@ -657,6 +689,40 @@ where
     }
 }
 
+//--- Serialize and Deserialize
+
+#[cfg(feature = "serde")]
+impl<Octs> serde::Serialize for Message<Octs>
+where
+    Octs: AsRef<[u8]> + octseq::serde::SerializeOctets,
+{
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::utils::base16::serde::serialize(&self.octets, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Octs> serde::Deserialize<'de> for Message<Octs>
+where
+    Octs: AsRef<[u8]>
+        + octseq::builder::FromBuilder
+        + octseq::serde::DeserializeOctets<'de>,
+    <Octs as octseq::builder::FromBuilder>::Builder:
+        octseq::builder::EmptyBuilder,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Message::from_octets(crate::utils::base16::serde::deserialize(
+            deserializer,
+        )?)
+        .map_err(serde::de::Error::custom)
+    }
+}
+
 //--- IntoIterator
 
 impl<'a, Octs: Octets + ?Sized> IntoIterator for &'a Message<Octs> {
@@ -930,6 +996,25 @@ impl<'a, Octs: Octets + ?Sized> RecordSection<'a, Octs> {
         RecordIter::new(self, true)
     }
 
+    /// Trades `self` in for an iterator over the record sets of the section.
+    ///
+    /// The returned iterator groups the records of the section by owner
+    /// name, class, and record type and returns each such record set once.
+    /// The records of a set do not need to follow each other in the
+    /// section. Only record sets of a record type that `Data` is willing to
+    /// parse are returned.
+    ///
+    /// The record data of the sets is only parsed when iterating over a set.
+    /// As with [`limit_to`], the iterator starts at the current position of
+    /// `self`.
+    ///
+    /// [`limit_to`]: #method.limit_to
+    pub fn rrsets<Data: ParseRecordData<'a, Octs>>(
+        self,
+    ) -> RrsetIter<'a, Octs, Data> {
+        RrsetIter::new(self)
+    }
+
     /// Proceeds to the next section if there is one.
     ///
     /// Returns an error if parsing has failed and the message is unusable
@@ -1134,6 +1219,340 @@ where
     }
 }
 
+//------------ RrsetIter -----------------------------------------------------
+
+/// An iterator over the record sets of a record section of a DNS message.
+///
+/// The iterator’s item type is the result of trying to find the next record
+/// set. Each record set is returned as a [`ParsedRrset`] that allows
+/// iterating over the record data of its members.
+///
+/// The records of a record set do not need to follow each other in the
+/// section. Whether a record starts a new set is determined by looking at
+/// all the records of the section before it, so iterating over a section
+/// this way is quadratic in the number of records. Given the limited size
+/// of DNS messages, this seems acceptable in exchange for not needing to
+/// allocate.
+///
+/// Record sets of types that `Data` doesn’t want to parse are skipped. To
+/// determine this, the record data of the first record of each set is
+/// parsed. If that fails, an error is returned but iteration can continue.
+/// If parsing an entire record fails, the item will be an error and all
+/// subsequent attempts to continue will produce `None`.
+///
+/// You can create a value of this type through the
+/// [`RecordSection::rrsets`] method.
+///
+/// [`RecordSection::rrsets`]: struct.RecordSection.html#method.rrsets
+#[derive(Debug)]
+pub struct RrsetIter<'a, Octs: ?Sized, Data> {
+    /// The section at the position the iterator was created at.
+    start: RecordSection<'a, Octs>,
+
+    /// The section at the position of the next record to look at.
+    section: RecordSection<'a, Octs>,
+
+    marker: PhantomData<Data>,
+}
+
+impl<'a, Octs, Data> RrsetIter<'a, Octs, Data>
+where
+    Octs: Octets + ?Sized,
+    Data: ParseRecordData<'a, Octs>,
+{
+    /// Creates a new record set iterator.
+    fn new(section: RecordSection<'a, Octs>) -> Self {
+        RrsetIter {
+            start: section,
+            section,
+            marker: PhantomData,
+        }
+    }
+
+    /// Trades the record set iterator for the full iterator.
+    ///
+    /// The returned iterator will continue right after the first record of
+    /// the last record set previously returned.
+    pub fn unwrap(self) -> RecordSection<'a, Octs> {
+        self.section
+    }
+
+    /// Proceeds to the next section if there is one.
+    ///
+    /// Returns an error if parsing the message has failed. Returns
+    /// `Ok(None)` if this iterator was already on the additional section.
+    pub fn next_section(
+        self,
+    ) -> Result<Option<RecordSection<'a, Octs>>, ParseError> {
+        self.section.next_section()
+    }
+
+    /// Returns whether a record before `pos` belongs to the same set.
+    fn is_seen(&self, record: &ParsedRecord<'a, Octs>, pos: usize) -> bool {
+        let mut earlier = self.start;
+        while earlier.pos() < pos {
+            match earlier.next() {
+                Some(Ok(other)) => {
+                    if is_same_rrset(record, &other) {
+                        return true;
+                    }
+                }
+                _ => return false,
+            }
+        }
+        false
+    }
+}
+
+//--- Clone
+
+impl<'a, Octs: ?Sized, Data> Clone for RrsetIter<'a, Octs, Data> {
+    fn clone(&self) -> Self {
+        RrsetIter {
+            start: self.start,
+            section: self.section,
+            marker: PhantomData,
+        }
+    }
+}
+
+//--- Iterator
+
+impl<'a, Octs, Data> Iterator for RrsetIter<'a, Octs, Data>
+where
+    Octs: Octets + ?Sized,
+    Data: ParseRecordData<'a, Octs>,
+{
+    type Item = Result<ParsedRrset<'a, Octs, Data>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.section;
+            let record = match self.section.next() {
+                Some(Ok(record)) => record,
+                Some(Err(err)) => return Some(Err(err)),
+                None => return None,
+            };
+            if self.is_seen(&record, start.pos()) {
+                continue;
+            }
+            match record.clone().into_record::<Data>() {
+                Ok(Some(_)) => {}
+                Ok(None) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+
+            // Scan the rest of the section for the lowest TTL.
+            let mut ttl = record.ttl();
+            let mut ttl_mismatch = false;
+            let mut rest = self.section;
+            while let Some(Ok(other)) = rest.next() {
+                if is_same_rrset(&record, &other) {
+                    ttl_mismatch |= other.ttl() != record.ttl();
+                    ttl = cmp::min(ttl, other.ttl());
+                }
+            }
+            return Some(Ok(ParsedRrset {
+                section: start,
+                owner: record.owner(),
+                class: record.class(),
+                rtype: record.rtype(),
+                ttl,
+                ttl_mismatch,
+                marker: PhantomData,
+            }));
+        }
+    }
+}
+
+//------------ ParsedRrset ---------------------------------------------------
+
+/// A record set within a record section of a DNS message.
+///
+/// A value of this type is returned by [`RrsetIter`]. It provides the owner
+/// name, class, record type, and TTL of the set. The record data of its
+/// members is parsed on the fly when iterating via [`iter`].
+///
+/// Section 5.2 of [RFC 2181] requires all records of a set to have the same
+/// TTL. If they don’t, the set should be treated as if all records had the
+/// lowest TTL. Consequently, [`ttl`] returns the lowest TTL while
+/// [`has_ttl_mismatch`] reports whether the TTLs of the set differed.
+///
+/// [`iter`]: #method.iter
+/// [`ttl`]: #method.ttl
+/// [`has_ttl_mismatch`]: #method.has_ttl_mismatch
+/// [RFC 2181]: https://tools.ietf.org/html/rfc2181
+pub struct ParsedRrset<'a, Octs: ?Sized, Data> {
+    /// The section positioned at the first record of the set.
+    section: RecordSection<'a, Octs>,
+
+    /// The owner name of the set.
+    owner: ParsedDname<&'a Octs>,
+
+    /// The class of the set.
+    class: Class,
+
+    /// The record type of the set.
+    rtype: Rtype,
+
+    /// The lowest TTL of the set’s records.
+    ttl: u32,
+
+    /// Whether the records of the set had different TTLs.
+    ttl_mismatch: bool,
+
+    marker: PhantomData<Data>,
+}
+
+impl<'a, Octs, Data> ParsedRrset<'a, Octs, Data>
+where
+    Octs: Octets + ?Sized,
+    Data: ParseRecordData<'a, Octs>,
+{
+    /// Returns the owner name of the set.
+    pub fn owner(&self) -> ParsedDname<&'a Octs> {
+        self.owner
+    }
+
+    /// Returns the class of the set.
+    pub fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Returns the record type of the set.
+    pub fn rtype(&self) -> Rtype {
+        self.rtype
+    }
+
+    /// Returns the TTL of the set.
+    ///
+    /// This is the lowest TTL of all the records of the set.
+    pub fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    /// Returns whether the records of the set have different TTLs.
+    pub fn has_ttl_mismatch(&self) -> bool {
+        self.ttl_mismatch
+    }
+
+    /// Returns an iterator over the record data of the set.
+    pub fn iter(&self) -> ParsedRrsetIter<'a, Octs, Data> {
+        ParsedRrsetIter {
+            rrset: self.clone(),
+            section: self.section,
+        }
+    }
+}
+
+//--- Clone
+
+impl<'a, Octs: ?Sized, Data> Clone for ParsedRrset<'a, Octs, Data> {
+    fn clone(&self) -> Self {
+        ParsedRrset {
+            section: self.section,
+            owner: self.owner,
+            class: self.class,
+            rtype: self.rtype,
+            ttl: self.ttl,
+            ttl_mismatch: self.ttl_mismatch,
+            marker: PhantomData,
+        }
+    }
+}
+
+//--- Debug
+
+impl<'a, Octs: Octets + ?Sized, Data> fmt::Debug
+    for ParsedRrset<'a, Octs, Data>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParsedRrset")
+            .field("owner", &self.owner)
+            .field("class", &self.class)
+            .field("rtype", &self.rtype)
+            .field("ttl", &self.ttl)
+            .field("ttl_mismatch", &self.ttl_mismatch)
+            .finish()
+    }
+}
+
+//------------ ParsedRrsetIter -----------------------------------------------
+
+/// An iterator over the record data of a [`ParsedRrset`].
+///
+/// The iterator parses the record data of each member of the set as it
+/// goes along. If that fails, an error is returned but iteration can
+/// continue.
+pub struct ParsedRrsetIter<'a, Octs: ?Sized, Data> {
+    /// The set we are iterating over.
+    rrset: ParsedRrset<'a, Octs, Data>,
+
+    /// The section positioned at the next record to look at.
+    section: RecordSection<'a, Octs>,
+}
+
+impl<'a, Octs: ?Sized, Data> Clone for ParsedRrsetIter<'a, Octs, Data> {
+    fn clone(&self) -> Self {
+        ParsedRrsetIter {
+            rrset: self.rrset.clone(),
+            section: self.section,
+        }
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized, Data> fmt::Debug
+    for ParsedRrsetIter<'a, Octs, Data>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParsedRrsetIter")
+            .field("rrset", &self.rrset)
+            .field("pos", &self.section.pos())
+            .finish()
+    }
+}
+
+impl<'a, Octs, Data> Iterator for ParsedRrsetIter<'a, Octs, Data>
+where
+    Octs: Octets + ?Sized,
+    Data: ParseRecordData<'a, Octs>,
+{
+    type Item = Result<Data, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record = match self.section.next() {
+                Some(Ok(record)) => record,
+                Some(Err(err)) => return Some(Err(err)),
+                None => return None,
+            };
+            if record.rtype() != self.rrset.rtype
+                || record.class() != self.rrset.class
+                || !record.owner().name_eq(&self.rrset.owner)
+            {
+                continue;
+            }
+            match record.into_record::<Data>() {
+                Ok(Some(record)) => return Some(Ok(record.into_data())),
+                Err(err) => return Some(Err(err)),
+                Ok(None) => {}
+            }
+        }
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns whether two records belong to the same record set.
+fn is_same_rrset<Octs: Octets + ?Sized>(
+    left: &ParsedRecord<Octs>,
+    right: &ParsedRecord<Octs>,
+) -> bool {
+    left.rtype() == right.rtype()
+        && left.class() == right.class()
+        && left.owner().name_eq(&right.owner())
+}
+
 //============ Error Types ===================================================
 
 //------------ ShortMessage --------------------------------------------------
@@ -1226,6 +1645,40 @@ mod test {
         assert!(Message::from_octets(&[0u8; 12]).is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn is_answer_exact() {
+        let mut query = MessageBuilder::new_vec().question();
+        query
+            .push((
+                Dname::vec_from_str("wWw.ExAmple.com.").unwrap(),
+                Rtype::A,
+            ))
+            .unwrap();
+        let query = query.into_message();
+
+        let answer = MessageBuilder::new_vec()
+            .start_answer(&query, Rcode::NoError)
+            .unwrap()
+            .into_message();
+        assert!(answer.is_answer(&query));
+        assert!(answer.is_answer_exact(&query));
+
+        let mut lower = MessageBuilder::new_vec();
+        lower.header_mut().set_id(query.header().id());
+        lower.header_mut().set_qr(true);
+        let mut lower = lower.question();
+        lower
+            .push((
+                Dname::vec_from_str("www.example.com.").unwrap(),
+                Rtype::A,
+            ))
+            .unwrap();
+        let lower = lower.into_message();
+        assert!(lower.is_answer(&query));
+        assert!(!lower.is_answer_exact(&query));
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn canonical_name() {
@@ -1322,4 +1775,51 @@ mod test {
             assert_eq!(0, msg.header_counts().arcount());
         }
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rrsets() {
+        use crate::rdata::{Aaaa, A};
+
+        let name = Dname::vec_from_str("example.com.").unwrap();
+        let www = Dname::vec_from_str("www.example.com.").unwrap();
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((&name, 3600, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push((&name, 3600, Aaaa::new("2001:db8::1".parse().unwrap())))
+            .unwrap();
+        msg.push((&www, 3600, A::from_octets(192, 0, 2, 3)))
+            .unwrap();
+        msg.push((&name, 600, A::from_octets(192, 0, 2, 2)))
+            .unwrap();
+        let msg = msg.into_message();
+
+        let mut iter = msg
+            .answer()
+            .unwrap()
+            .rrsets::<AllRecordData<_, ParsedDname<_>>>();
+        let rrset = iter.next().unwrap().unwrap();
+        assert_eq!(rrset.owner(), name);
+        assert_eq!(rrset.rtype(), Rtype::A);
+        assert_eq!(rrset.ttl(), 600);
+        assert!(rrset.has_ttl_mismatch());
+        assert_eq!(rrset.iter().count(), 2);
+        let rrset = iter.next().unwrap().unwrap();
+        assert_eq!(rrset.rtype(), Rtype::Aaaa);
+        assert!(!rrset.has_ttl_mismatch());
+        let rrset = iter.next().unwrap().unwrap();
+        assert_eq!(rrset.owner(), www);
+        assert_eq!(rrset.ttl(), 3600);
+        assert!(iter.next().is_none());
+
+        let mut iter = msg.answer().unwrap().rrsets::<A>();
+        let rrset = iter.next().unwrap().unwrap();
+        assert_eq!(
+            rrset.iter().collect::<Result<Vec<_>, _>>().unwrap(),
+            [A::from_octets(192, 0, 2, 1), A::from_octets(192, 0, 2, 2)]
+        );
+        let rrset = iter.next().unwrap().unwrap();
+        assert_eq!(rrset.owner(), www);
+        assert!(iter.next().is_none());
+    }
 }
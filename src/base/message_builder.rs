@@ -29,9 +29,18 @@
 //! section builders is also available via the [`RecordSectionBuilder`]
 //! trait so you can build code that works with all three record sections.
 //!
+//! If a message must not exceed a certain size, such as a UDP response
+//! that has to fit the payload size announced by the client, a limit can be
+//! set via [`MessageBuilder::set_push_limit`]. The `push_rrset` method of
+//! the record section builders then adds complete record sets or nothing
+//! at all and sets the TC bit where required. Space for a final OPT or TSIG
+//! record can be kept free via [`MessageBuilder::set_reserved_len`].
+//!
 //! The [`AdditionalBuilder`] has a special feature that helps building the
 //! OPT record for EDNS. Its [`opt`][AdditionalBuilder::opt] method allows a
 //! closure to build this record on the fly via the [`OptBuilder`] type.
+//! Its sibling [`opt_padded`][AdditionalBuilder::opt_padded] additionally
+//! pads the message according to a [`PaddingPolicy`].
 //!
 //! Building happens atop any [octets builder], so the type of buffer to use
 //! for building can be chosen. The module also provides a few helper types
@@ -121,6 +130,7 @@
 //! [`AdditionalBuilder`]: struct.AdditionalBuilder.html
 //! [`AdditionalBuilder::opt`]: struct.AdditionalBuilder.html#method.opt
 //! [`OptBuilder`]: struct.OptBuilder.html
+//! [`PaddingPolicy`]: super::opt::rfc7830::PaddingPolicy
 //! [`RecordSectionBuilder`]: trait.RecordSectionBuilder.html
 //! [`StaticCompressor`]: struct.StaticCompressor.html
 //! [`StreamTarget`]: struct.StreamTarget.html
@@ -135,6 +145,7 @@ use super::iana::Rtype;
 use super::iana::{OptRcode, OptionCode, Rcode};
 use super::message::Message;
 use super::name::{Label, ToDname};
+use super::opt::rfc7830::{Padding, PaddingPolicy};
 use super::opt::{ComposeOptData, OptHeader};
 use super::question::ComposeQuestion;
 use super::record::ComposeRecord;
@@ -170,7 +181,18 @@ use std::vec::Vec;
 /// [`OctetsBuilder`]: ../../octets/trait.OctetsBuilder.html
 #[derive(Clone, Debug)]
 pub struct MessageBuilder<Target> {
+    /// The octets builder the message is built in.
     target: Target,
+
+    /// The maximum size of the message.
+    ///
+    /// Pushes that would make the message larger fail.
+    limit: usize,
+
+    /// The number of octets at the end of the message kept for OPT and TSIG.
+    ///
+    /// Regular pushes have to leave this much space below `limit`.
+    reserved: usize,
 }
 
 /// # Creating Message Builders
@@ -189,7 +211,11 @@ impl<Target: OctetsBuilder + Truncate> MessageBuilder<Target> {
     ) -> Result<Self, Target::AppendError> {
         target.truncate(0);
         target.append_slice(HeaderSection::new().as_slice())?;
-        Ok(MessageBuilder { target })
+        Ok(MessageBuilder {
+            target,
+            limit: usize::MAX,
+            reserved: 0,
+        })
     }
 }
 
@@ -296,6 +322,54 @@ impl<Target: OctetsBuilder + AsMut<[u8]>> MessageBuilder<Target> {
     }
 }
 
+/// # Limiting the Message Size
+///
+/// By default, a message can grow for as long as the underlying octets
+/// builder has space. If a message has to fit into a certain size, for
+/// instance the UDP payload size announced by a client, a limit can be set
+/// via [`set_push_limit`][Self::set_push_limit]. Any push that would grow
+/// the message beyond this limit fails with [`PushError::ShortBuf`] and
+/// leaves the message unchanged.
+///
+/// In addition, some space at the end of the message can be reserved via
+/// [`set_reserved_len`][Self::set_reserved_len]. Regular pushes will not
+/// use this space. Instead, it is available to the OPT record added via
+/// [`AdditionalBuilder::opt`] and to records added via
+/// [`AdditionalBuilder::push_reserved`], which is what TSIG signing uses.
+///
+/// The `push_rrset` methods of the three record section builders can be
+/// used to add complete resource record sets which are dropped entirely if
+/// they don’t fit. The answer section’s method also sets the TC bit in this
+/// case. Required authority data, such as the NS records of a referral, can
+/// be added via [`AuthorityBuilder::push_required_rrset`] to do the same.
+impl<Target> MessageBuilder<Target> {
+    /// Returns the maximum size of the message.
+    pub fn push_limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Sets the maximum size of the message.
+    ///
+    /// The limit only affects future pushes. If the message already is
+    /// larger than the limit, it is left as is.
+    pub fn set_push_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    /// Returns the number of octets reserved at the end of the message.
+    pub fn reserved_len(&self) -> usize {
+        self.reserved
+    }
+
+    /// Sets the number of octets to reserve at the end of the message.
+    ///
+    /// This should be the combined length of the OPT and TSIG records that
+    /// will be added to the message once all other records are in place.
+    pub fn set_reserved_len(&mut self, len: usize) {
+        self.reserved = len;
+    }
+}
+
 /// # Conversions
 ///
 impl<Target: Composer> MessageBuilder<Target> {
@@ -386,11 +460,43 @@ impl<Target> MessageBuilder<Target> {
 }
 
 impl<Target: Composer> MessageBuilder<Target> {
+    /// Pushes something to the end of the message.
+    ///
+    /// The push has to leave the reserved space at the end of the message
+    /// untouched.
     fn push<Push, Inc>(
         &mut self,
         push: Push,
         inc: Inc,
     ) -> Result<(), PushError>
+    where
+        Push: FnOnce(&mut Target) -> Result<(), ShortBuf>,
+        Inc: FnOnce(&mut HeaderCounts) -> Result<(), CountOverflow>,
+    {
+        let limit = self.limit.saturating_sub(self.reserved);
+        self.push_limited(push, inc, limit)
+    }
+
+    /// Pushes something to the end of the message using reserved space.
+    fn push_reserved<Push, Inc>(
+        &mut self,
+        push: Push,
+        inc: Inc,
+    ) -> Result<(), PushError>
+    where
+        Push: FnOnce(&mut Target) -> Result<(), ShortBuf>,
+        Inc: FnOnce(&mut HeaderCounts) -> Result<(), CountOverflow>,
+    {
+        self.push_limited(push, inc, self.limit)
+    }
+
+    /// Pushes something keeping the message at most `limit` octets long.
+    fn push_limited<Push, Inc>(
+        &mut self,
+        push: Push,
+        inc: Inc,
+        limit: usize,
+    ) -> Result<(), PushError>
     where
         Push: FnOnce(&mut Target) -> Result<(), ShortBuf>,
         Inc: FnOnce(&mut HeaderCounts) -> Result<(), CountOverflow>,
@@ -400,12 +506,46 @@ impl<Target: Composer> MessageBuilder<Target> {
             self.target.truncate(pos);
             return Err(From::from(err));
         }
+        if self.target.as_ref().len() > limit {
+            self.target.truncate(pos);
+            return Err(PushError::ShortBuf);
+        }
         if inc(self.counts_mut()).is_err() {
             self.target.truncate(pos);
             return Err(PushError::CountOverflow);
         }
         Ok(())
     }
+
+    /// Pushes all records of a record set or none at all.
+    ///
+    /// If any of the records fails to be pushed, the message and its
+    /// header counts are restored to what they were before.
+    fn push_rrset<Iter, Inc>(
+        &mut self,
+        rrset: Iter,
+        inc: Inc,
+    ) -> Result<(), PushError>
+    where
+        Iter: IntoIterator,
+        Iter::Item: ComposeRecord,
+        Inc: Fn(&mut HeaderCounts) -> Result<(), CountOverflow>,
+    {
+        let pos = self.target.as_ref().len();
+        let counts = self.counts();
+        for record in rrset {
+            let res = self.push(
+                |target| record.compose_record(target).map_err(Into::into),
+                &inc,
+            );
+            if let Err(err) = res {
+                self.target.truncate(pos);
+                *self.counts_mut() = counts;
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
 }
 
 //--- From
@@ -771,6 +911,29 @@ impl<Target: Composer> AnswerBuilder<Target> {
             |counts| counts.inc_ancount(),
         )
     }
+
+    /// Appends a complete record set to the answer section.
+    ///
+    /// The method pushes all records produced by `rrset` to the answer
+    /// section. If any of them cannot be added, none of them will be. If
+    /// this happens because the message has become too large, the TC bit
+    /// of the message header is set, since the record set was required to
+    /// be part of the answer as described in section 9 of [RFC 2181].
+    ///
+    /// [RFC 2181]: https://tools.ietf.org/html/rfc2181
+    pub fn push_rrset<Iter>(&mut self, rrset: Iter) -> Result<(), PushError>
+    where
+        Iter: IntoIterator,
+        Iter::Item: ComposeRecord,
+    {
+        let res = self
+            .builder
+            .push_rrset(rrset, |counts| counts.inc_ancount());
+        if let Err(PushError::ShortBuf) = res {
+            self.header_mut().set_tc(true);
+        }
+        res
+    }
 }
 
 /// # Conversions
@@ -1014,6 +1177,53 @@ impl<Target: Composer> AuthorityBuilder<Target> {
             |counts| counts.inc_nscount(),
         )
     }
+
+    /// Appends a complete record set to the authority section.
+    ///
+    /// The method pushes all records produced by `rrset` to the authority
+    /// section. If any of them cannot be added, none of them will be.
+    ///
+    /// The TC bit is not set if the record set doesn’t fit. Section 9 of
+    /// [RFC 2181] only requires this if the record set is needed for the
+    /// response to be useful, such as the NS records of a referral or the
+    /// SOA record of a negative answer. Use
+    /// [`push_required_rrset`][Self::push_required_rrset] for those.
+    ///
+    /// [RFC 2181]: https://tools.ietf.org/html/rfc2181
+    pub fn push_rrset<Iter>(&mut self, rrset: Iter) -> Result<(), PushError>
+    where
+        Iter: IntoIterator,
+        Iter::Item: ComposeRecord,
+    {
+        self.answer
+            .builder
+            .push_rrset(rrset, |counts| counts.inc_nscount())
+    }
+
+    /// Appends a required record set to the authority section.
+    ///
+    /// This is the same as [`push_rrset`][Self::push_rrset] except that the
+    /// TC bit of the message header is set if the record set cannot be
+    /// added because the message has become too large. This is what
+    /// section 9 of [RFC 2181] requires for record sets that need to be
+    /// part of the response, such as the NS records of a referral or the
+    /// SOA record of a negative answer.
+    ///
+    /// [RFC 2181]: https://tools.ietf.org/html/rfc2181
+    pub fn push_required_rrset<Iter>(
+        &mut self,
+        rrset: Iter,
+    ) -> Result<(), PushError>
+    where
+        Iter: IntoIterator,
+        Iter::Item: ComposeRecord,
+    {
+        let res = self.push_rrset(rrset);
+        if let Err(PushError::ShortBuf) = res {
+            self.header_mut().set_tc(true);
+        }
+        res
+    }
 }
 
 /// # Conversions
@@ -1265,6 +1475,45 @@ impl<Target: Composer> AdditionalBuilder<Target> {
             |counts| counts.inc_arcount(),
         )
     }
+
+    /// Appends a complete record set to the additional section.
+    ///
+    /// The method pushes all records produced by `rrset` to the additional
+    /// section. If any of them cannot be added, none of them will be.
+    ///
+    /// Unlike with the answer section, the TC bit is not set if the record
+    /// set doesn’t fit, since section 9 of [RFC 2181] states that
+    /// additional information can be left out without doing so. If
+    /// the record set is required, e.g., because it contains glue needed for
+    /// a referral, you need to set the TC bit yourself.
+    ///
+    /// [RFC 2181]: https://tools.ietf.org/html/rfc2181
+    pub fn push_rrset<Iter>(&mut self, rrset: Iter) -> Result<(), PushError>
+    where
+        Iter: IntoIterator,
+        Iter::Item: ComposeRecord,
+    {
+        self.authority
+            .answer
+            .builder
+            .push_rrset(rrset, |counts| counts.inc_arcount())
+    }
+
+    /// Appends a record to the additional section using reserved space.
+    ///
+    /// This is the same as [`push`][Self::push] except that the record
+    /// may use the space reserved at the end of the message via
+    /// [`MessageBuilder::set_reserved_len`]. It is intended for records
+    /// that need to be at the very end of the message, such as TSIG.
+    pub fn push_reserved(
+        &mut self,
+        record: impl ComposeRecord,
+    ) -> Result<(), PushError> {
+        self.authority.answer.builder.push_reserved(
+            |target| record.compose_record(target).map_err(Into::into),
+            |counts| counts.inc_arcount(),
+        )
+    }
 }
 
 impl<Target: Composer> AdditionalBuilder<Target> {
@@ -1277,16 +1526,64 @@ impl<Target: Composer> AdditionalBuilder<Target> {
     /// The method will return whatever the closure returns. In addition, it
     /// will return an error if it failed to add the header of the OPT record.
     ///
+    /// The OPT record may use the space reserved at the end of the message
+    /// via [`MessageBuilder::set_reserved_len`].
+    ///
     /// [`OptBuilder`]: struct.OptBuilder.html
     pub fn opt<F>(&mut self, op: F) -> Result<(), PushError>
     where
         F: FnOnce(&mut OptBuilder<Target>) -> Result<(), Target::AppendError>,
     {
-        self.authority.answer.builder.push(
+        self.authority.answer.builder.push_reserved(
             |target| OptBuilder::new(target)?.build(op).map_err(Into::into),
             |counts| counts.inc_arcount(),
         )
     }
+
+    /// Appends and builds an OPT record with padding.
+    ///
+    /// This is similar to [`opt`][Self::opt] but adds a padding option as
+    /// defined in [RFC 7830] after the closure has added all other options.
+    /// The amount of padding is determined by `policy` based on the final
+    /// size of the message.
+    ///
+    /// Since the padding option needs to be the last option of the OPT
+    /// record and the OPT record needs to come after all other records
+    /// except for a TSIG record, this method should be called after all
+    /// other records have been added. If the message is going to be signed
+    /// via TSIG, `trailing_len` should be the length of the TSIG record as
+    /// returned by `tsig::Key::compose_len` so the padding can take it
+    /// into account. Since that is an upper bound, the padded message may
+    /// end up a few octets short of a block boundary if the key name is
+    /// compressed. Otherwise, `trailing_len` should be zero.
+    ///
+    /// Padding never makes the message exceed its push limit.
+    ///
+    /// [RFC 7830]: https://tools.ietf.org/html/rfc7830
+    pub fn opt_padded<F>(
+        &mut self,
+        policy: PaddingPolicy,
+        trailing_len: usize,
+        op: F,
+    ) -> Result<(), PushError>
+    where
+        F: FnOnce(&mut OptBuilder<Target>) -> Result<(), Target::AppendError>,
+    {
+        let limit = self.push_limit();
+        self.authority.answer.builder.push_reserved(
+            |target| {
+                OptBuilder::new(target)?
+                    .build(|opt| {
+                        op(opt)?;
+                        let len =
+                            opt.target.as_ref().len() + 4 + trailing_len;
+                        opt.push(&Padding::new(policy.pad_len(len, limit)))
+                    })
+                    .map_err(Into::into)
+            },
+            |counts| counts.inc_arcount(),
+        )
+    }
 }
 
 /// # Conversions
@@ -2323,6 +2620,120 @@ mod test {
         assert_eq!(opts.next(), Some(Ok(nsid)));
     }
 
+    #[test]
+    fn push_limit() {
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let rr = |addr| (&name, 86400u32, A::from_octets(192, 0, 2, addr));
+
+        // The question takes 17 octets, each record 27 octets, an empty
+        // OPT record 11 octets.
+        let mut msg = MessageBuilder::new_vec();
+        msg.set_push_limit(100);
+        msg.set_reserved_len(11);
+        let mut msg = msg.question();
+        msg.push((&name, Rtype::A)).unwrap();
+        let mut msg = msg.answer();
+        msg.push_rrset([rr(1), rr(2)]).unwrap();
+        assert_eq!(msg.as_slice().len(), 83);
+        assert!(!msg.header().tc());
+
+        // The next set doesn’t fit: it is dropped and TC is set.
+        assert!(matches!(msg.push_rrset([rr(3)]), Err(PushError::ShortBuf)));
+        assert_eq!(msg.as_slice().len(), 83);
+        assert_eq!(msg.counts().ancount(), 2);
+        assert!(msg.header().tc());
+
+        // The OPT record can use the reserved space but nothing else can.
+        let mut msg = msg.additional();
+        msg.opt(|_| Ok(())).unwrap();
+        assert_eq!(msg.as_slice().len(), 94);
+        assert!(msg.push(rr(4)).is_err());
+        assert_eq!(msg.counts().arcount(), 1);
+
+        let msg = Message::from_octets(msg.finish()).unwrap();
+        assert_eq!(msg.answer().unwrap().count(), 2);
+        assert!(msg.opt().is_some());
+    }
+
+    #[test]
+    fn push_rrset_additional() {
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let rr = |addr| (&name, 86400u32, A::from_octets(192, 0, 2, addr));
+
+        let mut msg = MessageBuilder::new_vec();
+        msg.set_push_limit(70);
+        let mut msg = msg.additional();
+        msg.push_rrset([rr(1)]).unwrap();
+
+        // A partially fitting set is dropped entirely without setting TC.
+        assert!(msg.push_rrset([rr(2), rr(3)]).is_err());
+        assert_eq!(msg.as_slice().len(), 39);
+        assert_eq!(msg.counts().arcount(), 1);
+        assert!(!msg.header().tc());
+    }
+
+    #[test]
+    fn push_rrset_authority() {
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let rr = |addr| (&name, 86400u32, A::from_octets(192, 0, 2, addr));
+
+        let mut msg = MessageBuilder::new_vec();
+        msg.set_push_limit(70);
+        let mut msg = msg.authority();
+        msg.push_rrset([rr(1)]).unwrap();
+
+        // Optional authority data is dropped without setting TC.
+        assert!(msg.push_rrset([rr(2), rr(3)]).is_err());
+        assert_eq!(msg.as_slice().len(), 39);
+        assert_eq!(msg.counts().nscount(), 1);
+        assert!(!msg.header().tc());
+
+        // A required set that doesn’t fit sets TC.
+        assert!(matches!(
+            msg.push_required_rrset([rr(2), rr(3)]),
+            Err(PushError::ShortBuf)
+        ));
+        assert_eq!(msg.counts().nscount(), 1);
+        assert!(msg.header().tc());
+    }
+
+    #[test]
+    fn opt_padded() {
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let query = || {
+            let mut msg = MessageBuilder::new_vec().question();
+            msg.push((&name, Rtype::A)).unwrap();
+            msg.additional()
+        };
+
+        // Block-length padding for a query.
+        let mut msg = query();
+        msg.opt_padded(PaddingPolicy::query(), 0, |opt| {
+            opt.set_udp_payload_size(1232);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(msg.as_slice().len(), 128);
+        let msg = Message::from_octets(msg.finish()).unwrap();
+        let opt = msg.opt().unwrap();
+        assert_eq!(opt.udp_payload_size(), 1232);
+        let mut opts = opt.as_opt().iter::<opt::Padding>();
+        assert_eq!(opts.next().unwrap().unwrap().len(), 84);
+
+        // Room for a trailing record is left.
+        let mut msg = query();
+        msg.opt_padded(PaddingPolicy::query(), 20, |_| Ok(()))
+            .unwrap();
+        assert_eq!(msg.as_slice().len(), 108);
+
+        // Maximal padding fills the message up to the limit.
+        let mut msg = query();
+        msg.set_push_limit(200);
+        msg.opt_padded(PaddingPolicy::Maximal, 0, |_| Ok(()))
+            .unwrap();
+        assert_eq!(msg.as_slice().len(), 200);
+    }
+
     fn create_compressed<T: Composer>(target: T) -> T
     where
         T::AppendError: fmt::Debug,
@@ -55,6 +55,7 @@
 //! all of them in their module. These are:
 //!
 //! * [charstr](charstr/index.html) for DNS character strings,
+//! * [dig](dig/index.html) for presenting messages in the style of `dig`,
 //! * [header](header/index.html) for the header of DNS messages,
 //! * [name](name/index.html) for domain names,
 //! * [opt](opt/index.html) for the record data of OPT records used in EDNS,
@@ -62,7 +63,9 @@
 //! * [serial](serial/index.html) for serial numbers of zones, and
 //! * [record](record/index.html) for DNS resource records including record
 //!   data,
-//! * [rdata](rdata/index.html) for all the individual record types.
+//! * [rdata](rdata/index.html) for all the individual record types,
+//! * [rrset](rrset/index.html) for sets of records with the same owner,
+//!   class, and type.
 //!
 //!
 //! # Zone File Processing
@@ -109,12 +112,15 @@ pub use self::name::{
 pub use self::question::Question;
 pub use self::rdata::{ParseRecordData, RecordData, UnknownRecordData};
 pub use self::record::{ParsedRecord, Record, RecordHeader};
+#[cfg(feature = "std")]
+pub use self::rrset::Rrset;
 pub use self::serial::Serial;
 
 //--- Modules
 
 pub mod charstr;
 pub mod cmp;
+pub mod dig;
 pub mod header;
 pub mod iana;
 pub mod message;
@@ -125,6 +131,8 @@ pub mod opt;
 pub mod question;
 pub mod rdata;
 pub mod record;
+#[cfg(feature = "std")]
+pub mod rrset;
 pub mod scan;
 pub mod serial;
 //pub mod str;
@@ -0,0 +1,367 @@
+//! Syntax checks for domain names.
+//!
+//! This is a private module. Its public types are re-exported by the parent.
+//!
+//! The DNS itself allows any octet in a label, but names used for specific
+//! purposes are conventionally limited further. Host names follow the ‘LDH
+//! rule’ of [RFC 952] as relaxed by [RFC 1123]: labels consist of ASCII
+//! letters, digits, and hyphens and neither start nor end with a hyphen.
+//! Service names as used by SRV records and the like start with a label
+//! starting with an underscore and may contain more such labels further
+//! down. Mailbox names, such as the
+//! responsible person in a SOA record, have an arbitrary printable first
+//! label followed by a host name.
+//!
+//! The checks are implemented as provided methods of [`ToLabelIter`] and
+//! are thus available for both absolute and relative names.
+//!
+//! [`ToLabelIter`]: super::ToLabelIter
+//! [RFC 952]: https://tools.ietf.org/html/rfc952
+//! [RFC 1123]: https://tools.ietf.org/html/rfc1123
+
+use super::label::Label;
+use core::fmt;
+
+//------------ Checks --------------------------------------------------------
+
+/// Checks that a sequence of labels forms a host name.
+pub(super) fn check_hostname<'a>(
+    labels: impl Iterator<Item = &'a Label>,
+) -> Result<(), NameCheckError> {
+    for (idx, label) in labels.enumerate() {
+        check_ldh(label).map_err(|kind| NameCheckError::new(idx, kind))?;
+    }
+    Ok(())
+}
+
+/// Checks that a sequence of labels forms a possibly wildcard host name.
+pub(super) fn check_wildcard_hostname<'a>(
+    labels: impl Iterator<Item = &'a Label>,
+) -> Result<(), NameCheckError> {
+    for (idx, label) in labels.enumerate() {
+        if label.is_wildcard() {
+            if idx != 0 {
+                return Err(NameCheckError::new(
+                    idx,
+                    NameCheckErrorKind::MisplacedWildcard,
+                ));
+            }
+            continue;
+        }
+        check_ldh(label).map_err(|kind| NameCheckError::new(idx, kind))?;
+    }
+    Ok(())
+}
+
+/// Checks that a sequence of labels forms a service name.
+pub(super) fn check_service_name<'a>(
+    labels: impl Iterator<Item = &'a Label>,
+) -> Result<(), NameCheckError> {
+    for (idx, label) in labels.enumerate() {
+        let res = match label.as_slice().split_first() {
+            Some((b'_', tail)) => {
+                if tail.is_empty() {
+                    Err(NameCheckErrorKind::EmptyLabel)
+                } else {
+                    check_ldh_slice(tail)
+                }
+            }
+            _ if idx == 0 => Err(NameCheckErrorKind::MissingServiceLabel),
+            _ => check_ldh(label),
+        };
+        res.map_err(|kind| NameCheckError::new(idx, kind))?;
+    }
+    Ok(())
+}
+
+/// Checks that a sequence of labels forms a mailbox name.
+pub(super) fn check_mailbox<'a>(
+    mut labels: impl Iterator<Item = &'a Label>,
+) -> Result<(), NameCheckError> {
+    match labels.next() {
+        Some(label) if !label.is_root() => {
+            if let Some(&ch) = label.iter().find(|ch| !ch.is_ascii_graphic())
+            {
+                return Err(NameCheckError::new(
+                    0,
+                    NameCheckErrorKind::IllegalCharacter(ch),
+                ));
+            }
+        }
+        _ => {
+            return Err(NameCheckError::new(
+                0,
+                NameCheckErrorKind::MissingLocalPart,
+            ))
+        }
+    }
+    check_hostname(labels).map_err(|err| err.shift(1))
+}
+
+/// Checks that a label follows the LDH rule.
+///
+/// The root label is fine.
+fn check_ldh(label: &Label) -> Result<(), NameCheckErrorKind> {
+    if label.is_root() {
+        Ok(())
+    } else {
+        check_ldh_slice(label.as_slice())
+    }
+}
+
+/// Checks that a non-empty octets slice follows the LDH rule.
+fn check_ldh_slice(slice: &[u8]) -> Result<(), NameCheckErrorKind> {
+    if let Some(&ch) = slice
+        .iter()
+        .find(|&&ch| !ch.is_ascii_alphanumeric() && ch != b'-')
+    {
+        return Err(NameCheckErrorKind::IllegalCharacter(ch));
+    }
+    if slice.first() == Some(&b'-') {
+        return Err(NameCheckErrorKind::LeadingHyphen);
+    }
+    if slice.last() == Some(&b'-') {
+        return Err(NameCheckErrorKind::TrailingHyphen);
+    }
+    Ok(())
+}
+
+//============ Error Types ===================================================
+
+//------------ NameCheckError ------------------------------------------------
+
+/// A domain name failed a syntax check.
+///
+/// The error describes the first offending label by its index, starting
+/// from zero for the leftmost label, and why it was rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NameCheckError {
+    /// The index of the offending label.
+    label: usize,
+
+    /// The reason for rejecting the label.
+    kind: NameCheckErrorKind,
+}
+
+impl NameCheckError {
+    /// Creates a new error for the given label index and kind.
+    fn new(label: usize, kind: NameCheckErrorKind) -> Self {
+        NameCheckError { label, kind }
+    }
+
+    /// Returns an error with the label index moved to the right.
+    fn shift(self, by: usize) -> Self {
+        NameCheckError {
+            label: self.label + by,
+            kind: self.kind,
+        }
+    }
+
+    /// Returns the index of the offending label.
+    ///
+    /// The leftmost label has index zero.
+    pub fn label(&self) -> usize {
+        self.label
+    }
+
+    /// Returns the reason why the label was rejected.
+    pub fn kind(&self) -> NameCheckErrorKind {
+        self.kind
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for NameCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "label {}: {}", self.label, self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NameCheckError {}
+
+//------------ NameCheckErrorKind --------------------------------------------
+
+/// The reason a domain name failed a syntax check.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NameCheckErrorKind {
+    /// The label contains an octet not allowed here.
+    IllegalCharacter(u8),
+
+    /// The label starts with a hyphen.
+    LeadingHyphen,
+
+    /// The label ends with a hyphen.
+    TrailingHyphen,
+
+    /// A service label consists of only the underscore.
+    EmptyLabel,
+
+    /// A wildcard label appears somewhere other than leftmost.
+    MisplacedWildcard,
+
+    /// A service name does not start with an underscore label.
+    MissingServiceLabel,
+
+    /// A mailbox name consists of the root label only.
+    MissingLocalPart,
+}
+
+//--- Display
+
+impl fmt::Display for NameCheckErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NameCheckErrorKind::IllegalCharacter(ch) => {
+                write!(f, "illegal character '\\{:03}'", ch)
+            }
+            NameCheckErrorKind::LeadingHyphen => {
+                f.write_str("label starts with a hyphen")
+            }
+            NameCheckErrorKind::TrailingHyphen => {
+                f.write_str("label ends with a hyphen")
+            }
+            NameCheckErrorKind::EmptyLabel => {
+                f.write_str("empty service label")
+            }
+            NameCheckErrorKind::MisplacedWildcard => {
+                f.write_str("wildcard label not leftmost")
+            }
+            NameCheckErrorKind::MissingServiceLabel => {
+                f.write_str("missing service label")
+            }
+            NameCheckErrorKind::MissingLocalPart => {
+                f.write_str("missing local part in mailbox")
+            }
+        }
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::name::{Dname, RelativeDname};
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    fn name(s: &str) -> Dname<Vec<u8>> {
+        Dname::from_str(s).unwrap()
+    }
+
+    fn err(label: usize, kind: NameCheckErrorKind) -> NameCheckError {
+        NameCheckError::new(label, kind)
+    }
+
+    #[test]
+    fn hostname() {
+        use NameCheckErrorKind::*;
+
+        assert!(name("www.example.com").check_hostname().is_ok());
+        assert!(name("3com.COM").check_hostname().is_ok());
+        assert!(name("a-b.c0.x").check_hostname().is_ok());
+        assert!(name(".").check_hostname().is_ok());
+        assert!(RelativeDname::from_octets(b"\x03www".as_ref())
+            .unwrap()
+            .check_hostname()
+            .is_ok());
+        assert_eq!(
+            name("foo_bar.example.com").check_hostname(),
+            Err(err(0, IllegalCharacter(b'_')))
+        );
+        assert_eq!(
+            name("www.-example.com").check_hostname(),
+            Err(err(1, LeadingHyphen))
+        );
+        assert_eq!(
+            name("www.example-.com").check_hostname(),
+            Err(err(1, TrailingHyphen))
+        );
+        assert_eq!(
+            name("*.example.com").check_hostname(),
+            Err(err(0, IllegalCharacter(b'*')))
+        );
+        assert_eq!(
+            name("a\\032b.example.com").check_hostname(),
+            Err(err(0, IllegalCharacter(b' ')))
+        );
+    }
+
+    #[test]
+    fn wildcard_hostname() {
+        use NameCheckErrorKind::*;
+
+        assert!(name("*.example.com").check_wildcard_hostname().is_ok());
+        assert!(name("www.example.com").check_wildcard_hostname().is_ok());
+        assert_eq!(
+            name("www.*.example.com").check_wildcard_hostname(),
+            Err(err(1, MisplacedWildcard))
+        );
+        assert_eq!(
+            name("*a.example.com").check_wildcard_hostname(),
+            Err(err(0, IllegalCharacter(b'*')))
+        );
+    }
+
+    #[test]
+    fn service_name() {
+        use NameCheckErrorKind::*;
+
+        assert!(name("_sip._tcp.example.com").check_service_name().is_ok());
+        assert!(name("_dmarc.example.com").check_service_name().is_ok());
+        assert!(name("_ldap._tcp.dc._msdcs.example.com")
+            .check_service_name()
+            .is_ok());
+        assert!(name("_25._tcp.mail.example.com")
+            .check_service_name()
+            .is_ok());
+        assert!(name("_sip.www._tcp.example.com")
+            .check_service_name()
+            .is_ok());
+        assert_eq!(
+            name("www.example.com").check_service_name(),
+            Err(err(0, MissingServiceLabel))
+        );
+        assert_eq!(
+            name("_sip.www_1.example.com").check_service_name(),
+            Err(err(1, IllegalCharacter(b'_')))
+        );
+        assert_eq!(
+            name("_ldap._tcp.dc._.example.com").check_service_name(),
+            Err(err(3, EmptyLabel))
+        );
+        assert_eq!(
+            name("_._tcp.example.com").check_service_name(),
+            Err(err(0, EmptyLabel))
+        );
+        assert_eq!(
+            name("_sip-._tcp.example.com").check_service_name(),
+            Err(err(0, TrailingHyphen))
+        );
+        assert_eq!(
+            name(".").check_service_name(),
+            Err(err(0, MissingServiceLabel))
+        );
+    }
+
+    #[test]
+    fn mailbox() {
+        use NameCheckErrorKind::*;
+
+        assert!(name("hostmaster.example.com").check_mailbox().is_ok());
+        assert!(name("john\\.doe.example.com").check_mailbox().is_ok());
+        assert!(name("foo+bar_baz.example.com").check_mailbox().is_ok());
+        assert_eq!(name(".").check_mailbox(), Err(err(0, MissingLocalPart)));
+        assert_eq!(
+            name("a\\032b.example.com").check_mailbox(),
+            Err(err(0, IllegalCharacter(b' ')))
+        );
+        assert_eq!(
+            name("hostmaster.exa_mple.com").check_mailbox(),
+            Err(err(1, IllegalCharacter(b'_')))
+        );
+    }
+}
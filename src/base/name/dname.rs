@@ -5,7 +5,10 @@
 use super::super::cmp::CanonicalOrd;
 use super::super::scan::{Scanner, Symbol};
 use super::super::wire::{FormError, ParseError};
+#[cfg(feature = "random")]
+use super::builder::PushError;
 use super::builder::{DnameBuilder, FromStrError};
+use super::check::NameCheckError;
 use super::label::{Label, LabelTypeError, SplitLabelError};
 use super::relative::{DnameIter, RelativeDname};
 use super::traits::{ToDname, ToLabelIter};
@@ -14,6 +17,8 @@ use bytes::Bytes;
 use core::ops::{Bound, RangeBounds};
 use core::str::FromStr;
 use core::{cmp, fmt, hash, str};
+#[cfg(feature = "random")]
+use octseq::builder::OctetsBuilder;
 use octseq::builder::{EmptyBuilder, FreezeBuilder, FromBuilder, Truncate};
 use octseq::octets::{Octets, OctetsFrom};
 use octseq::parse::Parser;
@@ -267,6 +272,40 @@ impl<Octs: ?Sized> Dname<Octs> {
     {
         unsafe { Dname::from_slice_unchecked(self.0.as_ref()) }
     }
+
+    /// Returns a copy of the name with the case of its letters randomized.
+    ///
+    /// Each ASCII letter of the name is independently converted to either
+    /// upper or lower case at random. Because domain names are compared
+    /// ignoring case, the result is still equal to the original name.
+    ///
+    /// Sending the query name in random case and checking that the response
+    /// echoes it exactly makes spoofing responses harder. This technique is
+    /// described in draft-vixie-dnsext-dns0x20.
+    #[cfg(feature = "random")]
+    pub fn to_random_case<Target>(&self) -> Result<Dname<Target>, PushError>
+    where
+        Octs: AsRef<[u8]>,
+        Target: FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let slice = self.0.as_ref();
+        let mut builder = Target::Builder::with_capacity(slice.len());
+        for &ch in slice {
+            // Length octets are at most 63 and thus never ASCII letters.
+            let ch = if !ch.is_ascii_alphabetic() {
+                ch
+            } else if ::rand::random() {
+                ch.to_ascii_uppercase()
+            } else {
+                ch.to_ascii_lowercase()
+            };
+            builder
+                .append_slice(&[ch])
+                .map_err(|_| PushError::ShortBuf)?;
+        }
+        Ok(unsafe { Dname::from_octets_unchecked(builder.freeze()) })
+    }
 }
 
 /// # Properties
@@ -339,6 +378,34 @@ impl<Octs: AsRef<[u8]> + ?Sized> Dname<Octs> {
         <Self as ToLabelIter>::ends_with(self, base)
     }
 
+    /// Checks that the name is a valid host name.
+    ///
+    /// See [`ToLabelIter::check_hostname`] for details.
+    pub fn check_hostname(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_hostname(self)
+    }
+
+    /// Checks that the name is a host name with an optional wildcard.
+    ///
+    /// See [`ToLabelIter::check_wildcard_hostname`] for details.
+    pub fn check_wildcard_hostname(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_wildcard_hostname(self)
+    }
+
+    /// Checks that the name is a valid service name.
+    ///
+    /// See [`ToLabelIter::check_service_name`] for details.
+    pub fn check_service_name(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_service_name(self)
+    }
+
+    /// Checks that the name is a valid mailbox name.
+    ///
+    /// See [`ToLabelIter::check_mailbox`] for details.
+    pub fn check_mailbox(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_mailbox(self)
+    }
+
     /// Returns whether an index points to the first byte of a non-root label.
     pub fn is_label_start(&self, mut index: usize) -> bool {
         if index == 0 {
@@ -1188,6 +1255,28 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    #[cfg(all(feature = "std", feature = "random"))]
+    fn to_random_case() {
+        let name =
+            Dname::from_octets(b"\x03www\x07example\x03com\0".as_ref())
+                .unwrap();
+        let random = name.to_random_case::<Vec<u8>>().unwrap();
+        assert_eq!(name, random);
+        assert!(name.as_slice().eq_ignore_ascii_case(random.as_slice()));
+
+        // With 13 letters, at least one of ten attempts should differ
+        // from all lower case unless something is seriously broken.
+        assert!((0..10).any(|_| {
+            name.to_random_case::<Vec<u8>>().unwrap().as_slice()
+                != name.as_slice()
+        }));
+        assert_eq!(
+            Dname::root_slice().to_random_case::<Vec<u8>>().unwrap(),
+            Dname::root_slice()
+        );
+    }
+
     #[test]
     fn is_root() {
         assert!(Dname::from_slice(b"\0").unwrap().is_root());
@@ -0,0 +1,770 @@
+//! Internationalized domain names.
+//!
+//! This is a private module. Its public types are re-exported by the parent.
+//!
+//! Internationalized domain names (IDNs) use Unicode characters beyond the
+//! ASCII letters, digits, and hyphen traditionally allowed in host names.
+//! Since the DNS itself only deals with octets, such labels are converted
+//! into an ASCII form – the *A-label* – starting with `xn--` followed by
+//! the [Punycode] encoding of the Unicode form – the *U-label*. Before
+//! encoding, the Unicode string is mapped and checked according to
+//! [UTS #46].
+//!
+//! The module adds two methods to [`Dname`]: [`Dname::from_unicode`]
+//! creates a name from a Unicode string and [`Dname::to_unicode`] renders a
+//! name back as Unicode. Labels are converted individually via
+//! [`Label::to_unicode`]. The Punycode encoding itself is available via
+//! [`punycode_encode`] and [`punycode_decode`].
+//!
+//! The processing follows the non-transitional rules of UTS #46 with the
+//! `UseSTD3ASCIIRules`, `CheckHyphens`, `CheckBidi`, and `VerifyDnsLength`
+//! flags set. The mapping step removes the code points ignored by UTS #46,
+//! such as the soft hyphen and variation selectors, and is otherwise
+//! approximated by Unicode case folding and NFKC normalization which is
+//! what the UTS #46 mapping table is derived from.
+//!
+//! Labels that consist of ASCII characters only are processed using the
+//! same rules as [`Dname::from_chars`], including escape sequences. Only
+//! labels containing non-ASCII characters are subject to IDNA processing.
+//!
+//! [Punycode]: https://tools.ietf.org/html/rfc3492
+//! [UTS #46]: https://www.unicode.org/reports/tr46/
+
+use super::builder::{DnameBuilder, FromStrError, PushError};
+use super::dname::Dname;
+use super::label::Label;
+use core::fmt;
+use octseq::builder::{
+    EmptyBuilder, FreezeBuilder, FromBuilder, OctetsBuilder, ShortBuf,
+};
+use std::string::String;
+use std::vec::Vec;
+use unicode_bidi::{bidi_class, BidiClass};
+use unicode_normalization::char::{is_combining_mark, is_public_assigned};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+//------------ Dname ---------------------------------------------------------
+
+impl<Octs> Dname<Octs> {
+    /// Creates a domain name from a Unicode string.
+    ///
+    /// The labels of the name can be separated by a dot or any of the
+    /// ideographic full stops U+3002, U+FF0E, and U+FF61. Labels containing
+    /// non-ASCII characters are mapped, checked, and converted into their
+    /// A-label form. All other labels are taken as is and may contain
+    /// escape sequences like the names accepted by
+    /// [`from_chars`][Self::from_chars].
+    ///
+    /// As with `from_chars`, the name will always be absolute.
+    ///
+    /// If the name is not valid, the returned error will contain the index
+    /// of the label that failed and the reason.
+    pub fn from_unicode(s: &str) -> Result<Self, IdnaError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+            + FreezeBuilder<Octets = Octs>
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+    {
+        let mut labels = split_labels(s);
+        let mut builder = DnameBuilder::<Octs::Builder>::new();
+        let mut ulabels = Vec::with_capacity(labels.len());
+
+        // A trailing separator only marks the name as absolute. A single
+        // separator is the root name.
+        if labels.len() > 1
+            && labels.last().map(String::is_empty) == Some(true)
+        {
+            labels.pop();
+            if labels.len() == 1 && labels[0].is_empty() {
+                labels.pop();
+            }
+        }
+        for (index, label) in labels.iter().enumerate() {
+            if label.is_empty() {
+                return Err(IdnaError::new(index, IdnaErrorKind::EmptyLabel));
+            }
+            let ulabel = push_label(&mut builder, label)
+                .map_err(|kind| IdnaError::new(index, kind))?;
+            ulabels.push(ulabel);
+        }
+        check_bidi_domain(&ulabels)?;
+        builder.into_dname().map_err(|err| {
+            IdnaError::new(labels.len().saturating_sub(1), err.into())
+        })
+    }
+}
+
+impl<Octs: AsRef<[u8]> + ?Sized> Dname<Octs> {
+    /// Returns the Unicode representation of the domain name.
+    ///
+    /// All A-labels of the name are converted into their U-label form. All
+    /// other labels are formatted the same way as the `Display`
+    /// implementation does, i.e., with escape sequences where necessary.
+    /// Like the `Display` implementation, the representation omits the
+    /// trailing dot.
+    ///
+    /// Returns an error if any of the A-labels isn’t valid. The error
+    /// contains the index of the offending label.
+    pub fn to_unicode(&self) -> Result<String, IdnaError> {
+        let mut ulabels = Vec::new();
+        for (index, label) in self.iter().enumerate() {
+            if label.is_root() {
+                break;
+            }
+            ulabels.push(
+                label
+                    .to_unicode()
+                    .map_err(|kind| IdnaError::new(index, kind))?,
+            );
+        }
+        check_bidi_domain(&ulabels)?;
+        Ok(ulabels.join("."))
+    }
+}
+
+//------------ Label ---------------------------------------------------------
+
+impl Label {
+    /// Returns the Unicode representation of the label.
+    ///
+    /// If the label is an A-label, i.e., it starts with `xn--`, it is
+    /// decoded and checked. Otherwise the label is formatted like its
+    /// `Display` implementation does.
+    ///
+    /// Note that the bidi rule can only be checked for a complete domain
+    /// name, so this method doesn’t do that.
+    pub fn to_unicode(&self) -> Result<String, IdnaErrorKind> {
+        let slice = self.as_slice();
+        if slice.len() < 4 || !slice[..4].eq_ignore_ascii_case(b"xn--") {
+            return Ok(format!("{}", self));
+        }
+        let encoded = core::str::from_utf8(&slice[4..])
+            .map_err(|_| IdnaErrorKind::Punycode)?;
+        if !encoded.is_ascii() {
+            return Err(IdnaErrorKind::Punycode);
+        }
+        let res = punycode_decode(&encoded.to_ascii_lowercase())
+            .map_err(|_| IdnaErrorKind::Punycode)?;
+        if res.is_ascii() {
+            // An A-label must encode at least one non-ASCII character.
+            return Err(IdnaErrorKind::Punycode);
+        }
+        check_ulabel(&res)?;
+        Ok(res)
+    }
+}
+
+//------------ Punycode ------------------------------------------------------
+
+const BASE: u32 = 36;
+const TMIN: u32 = 1;
+const TMAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+/// Encodes a Unicode string using Punycode.
+///
+/// This implements the encoding defined in [RFC 3492]. It does not add the
+/// `xn--` prefix used by A-labels. An error is returned if the encoding
+/// would overflow.
+///
+/// [RFC 3492]: https://tools.ietf.org/html/rfc3492
+pub fn punycode_encode(input: &str) -> Result<String, PunycodeError> {
+    let chars: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut res: String = input.chars().filter(char::is_ascii).collect();
+    let basic = res.len() as u32;
+    let total = chars.len() as u32;
+    let mut handled = basic;
+    if basic > 0 {
+        res.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    while handled < total {
+        // There is at least one code point left that is not smaller than n.
+        let m = chars
+            .iter()
+            .copied()
+            .filter(|&ch| ch >= n)
+            .min()
+            .ok_or(PunycodeError)?;
+        delta = (m - n)
+            .checked_mul(handled + 1)
+            .and_then(|value| delta.checked_add(value))
+            .ok_or(PunycodeError)?;
+        n = m;
+        for &ch in &chars {
+            if ch < n {
+                delta = delta.checked_add(1).ok_or(PunycodeError)?;
+            }
+            if ch == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    res.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                res.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.checked_add(1).ok_or(PunycodeError)?;
+        n += 1;
+    }
+    Ok(res)
+}
+
+/// Decodes a Punycode-encoded string.
+///
+/// This implements the decoding defined in [RFC 3492]. The input must not
+/// contain the `xn--` prefix used by A-labels. An error is returned if the
+/// input is not valid Punycode.
+///
+/// [RFC 3492]: https://tools.ietf.org/html/rfc3492
+pub fn punycode_decode(input: &str) -> Result<String, PunycodeError> {
+    let (basic, extended) = match input.rfind('-') {
+        Some(pos) => (&input[..pos], &input[pos + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return Err(PunycodeError);
+    }
+    let mut res: Vec<char> = basic.chars().collect();
+
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut extended = extended.bytes().peekable();
+    while extended.peek().is_some() {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = extended
+                .next()
+                .and_then(decode_digit)
+                .ok_or(PunycodeError)?;
+            i = digit
+                .checked_mul(w)
+                .and_then(|value| i.checked_add(value))
+                .ok_or(PunycodeError)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t).ok_or(PunycodeError)?;
+            k += BASE;
+        }
+        let len = res.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len).ok_or(PunycodeError)?;
+        i %= len;
+        res.insert(i as usize, char::from_u32(n).ok_or(PunycodeError)?);
+        i += 1;
+    }
+    Ok(res.into_iter().collect())
+}
+
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        TMIN
+    } else if k >= bias + TMAX {
+        TMAX
+    } else {
+        k - bias
+    }
+}
+
+fn adapt(delta: u32, num_points: u32, first: bool) -> u32 {
+    let mut delta = if first { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - TMIN) * TMAX) / 2 {
+        delta /= BASE - TMIN;
+        k += BASE;
+    }
+    k + (((BASE - TMIN + 1) * delta) / (delta + SKEW))
+}
+
+fn encode_digit(digit: u32) -> char {
+    if digit < 26 {
+        (b'a' + digit as u8) as char
+    } else {
+        (b'0' + (digit - 26) as u8) as char
+    }
+}
+
+fn decode_digit(ch: u8) -> Option<u32> {
+    match ch {
+        b'a'..=b'z' => Some(u32::from(ch - b'a')),
+        b'A'..=b'Z' => Some(u32::from(ch - b'A')),
+        b'0'..=b'9' => Some(u32::from(ch - b'0') + 26),
+        _ => None,
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Splits a string into labels.
+///
+/// Separators inside escape sequences are not considered.
+fn split_labels(s: &str) -> Vec<String> {
+    let mut res = Vec::new();
+    let mut label = String::new();
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '.' | '\u{3002}' | '\u{FF0E}' | '\u{FF61}' => {
+                res.push(core::mem::take(&mut label));
+            }
+            '\\' => {
+                label.push(ch);
+                if let Some(ch) = chars.next() {
+                    label.push(ch);
+                }
+            }
+            _ => label.push(ch),
+        }
+    }
+    res.push(label);
+    res
+}
+
+/// Appends a label to the builder.
+///
+/// Returns the Unicode form of the label for checking the bidi rule.
+fn push_label<Builder>(
+    builder: &mut DnameBuilder<Builder>,
+    label: &str,
+) -> Result<String, IdnaErrorKind>
+where
+    Builder: OctetsBuilder + AsRef<[u8]> + AsMut<[u8]>,
+{
+    if label.is_ascii() {
+        builder.append_chars(label.chars())?;
+        builder.end_label();
+        if label.len() >= 4 && label[..4].eq_ignore_ascii_case("xn--") {
+            return Label::from_slice(label.as_bytes())
+                .map_err(|_| IdnaErrorKind::LongLabel)?
+                .to_unicode();
+        }
+        return Ok(label.into());
+    }
+
+    // Map: remove ignored code points, case fold, and normalize.
+    // Lower-casing a second time catches upper case characters that result
+    // from compatibility mappings.
+    let mapped: String = label
+        .chars()
+        .filter(|&ch| !is_ignored_char(ch))
+        .nfkc()
+        .flat_map(char::to_lowercase)
+        .flat_map(char::to_lowercase)
+        .nfc()
+        .collect();
+    if mapped.is_empty() {
+        return Err(IdnaErrorKind::EmptyLabel);
+    }
+    check_ulabel(&mapped)?;
+    if mapped.is_ascii() {
+        builder.append_label(mapped.as_bytes())?;
+        return Ok(mapped);
+    }
+    let mut alabel = String::from("xn--");
+    alabel.push_str(
+        &punycode_encode(&mapped).map_err(|_| IdnaErrorKind::Punycode)?,
+    );
+    builder.append_label(alabel.as_bytes())?;
+    Ok(mapped)
+}
+
+/// Returns whether a code point is mapped to nothing by UTS #46.
+///
+/// These are the default ignorable code points with the status ‘ignored’
+/// in the IDNA mapping table.
+fn is_ignored_char(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{180B}'..='\u{180D}'
+            | '\u{180F}'
+            | '\u{200B}'
+            | '\u{2060}'
+            | '\u{2064}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+/// Checks that a U-label fulfills the validity criteria of UTS #46.
+///
+/// This doesn’t check the bidi rule which depends on the whole domain
+/// name.
+fn check_ulabel(label: &str) -> Result<(), IdnaErrorKind> {
+    if !is_nfc(label) {
+        return Err(IdnaErrorKind::NotNormalized);
+    }
+    if label.starts_with('-')
+        || label.ends_with('-')
+        || label.get(2..4) == Some("--")
+    {
+        return Err(IdnaErrorKind::Hyphen);
+    }
+    if let Some(ch) = label.chars().next() {
+        if is_combining_mark(ch) {
+            return Err(IdnaErrorKind::LeadingCombiningMark);
+        }
+    }
+    for ch in label.chars() {
+        if !is_valid_char(ch) {
+            return Err(IdnaErrorKind::DisallowedCodePoint(ch));
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether a code point may appear in a U-label.
+fn is_valid_char(ch: char) -> bool {
+    if ch.is_ascii() {
+        return ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-';
+    }
+    if ch.is_control()
+        || ch.is_whitespace()
+        || ch == '\u{FFFD}'
+        || !is_public_assigned(ch)
+    {
+        return false;
+    }
+
+    // Characters changed by the mapping are not valid.
+    let mut lower = ch.to_lowercase();
+    if lower.next() != Some(ch) || lower.next().is_some() {
+        return false;
+    }
+    let mut normalized = core::iter::once(ch).nfkc();
+    normalized.next() == Some(ch) && normalized.next().is_none()
+}
+
+/// Checks the bidi rule for all labels if the domain is a bidi domain.
+///
+/// A bidi domain is a domain name that contains at least one right-to-left
+/// character in any of its labels. For such names, all labels need to
+/// fulfil the bidi rule of [RFC 5893].
+///
+/// [RFC 5893]: https://tools.ietf.org/html/rfc5893
+fn check_bidi_domain(labels: &[String]) -> Result<(), IdnaError> {
+    let is_bidi = labels.iter().flat_map(|label| label.chars()).any(|ch| {
+        matches!(bidi_class(ch), BidiClass::R | BidiClass::AL | BidiClass::AN)
+    });
+    if !is_bidi {
+        return Ok(());
+    }
+    for (index, label) in labels.iter().enumerate() {
+        if !is_bidi_label(label) {
+            return Err(IdnaError::new(index, IdnaErrorKind::BidiRule));
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether a label fulfils the bidi rule of RFC 5893, section 2.
+fn is_bidi_label(label: &str) -> bool {
+    let rtl = match label.chars().next().map(bidi_class) {
+        Some(BidiClass::L) => false,
+        Some(BidiClass::R) | Some(BidiClass::AL) => true,
+        _ => return false,
+    };
+    let mut last = None;
+    let mut has_en = false;
+    let mut has_an = false;
+    for class in label.chars().map(bidi_class) {
+        let allowed = match class {
+            BidiClass::EN
+            | BidiClass::ES
+            | BidiClass::CS
+            | BidiClass::ET
+            | BidiClass::ON
+            | BidiClass::BN
+            | BidiClass::NSM => true,
+            BidiClass::R | BidiClass::AL | BidiClass::AN => rtl,
+            BidiClass::L => !rtl,
+            _ => false,
+        };
+        if !allowed {
+            return false;
+        }
+        has_en |= matches!(class, BidiClass::EN);
+        has_an |= matches!(class, BidiClass::AN);
+        if !matches!(class, BidiClass::NSM) {
+            last = Some(class);
+        }
+    }
+    if rtl {
+        matches!(
+            last,
+            Some(BidiClass::R)
+                | Some(BidiClass::AL)
+                | Some(BidiClass::EN)
+                | Some(BidiClass::AN)
+        ) && !(has_en && has_an)
+    } else {
+        matches!(last, Some(BidiClass::L) | Some(BidiClass::EN))
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ IdnaError -----------------------------------------------------
+
+/// An internationalized domain name could not be converted.
+///
+/// The error contains the index of the label that failed, starting at zero
+/// for the left-most label, and the reason for the failure.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdnaError {
+    /// The index of the label that failed.
+    label: usize,
+
+    /// What was wrong with the label.
+    kind: IdnaErrorKind,
+}
+
+impl IdnaError {
+    fn new(label: usize, kind: IdnaErrorKind) -> Self {
+        IdnaError { label, kind }
+    }
+
+    /// Returns the index of the label that failed.
+    pub fn label(&self) -> usize {
+        self.label
+    }
+
+    /// Returns the reason for the failure.
+    pub fn kind(&self) -> IdnaErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for IdnaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "label {}: {}", self.label, self.kind)
+    }
+}
+
+impl std::error::Error for IdnaError {}
+
+//------------ IdnaErrorKind -------------------------------------------------
+
+/// The reason a label of an internationalized domain name was rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum IdnaErrorKind {
+    /// An empty label was encountered.
+    EmptyLabel,
+
+    /// The label contains a code point that is not allowed.
+    DisallowedCodePoint(char),
+
+    /// The label is not in Unicode normalization form C.
+    NotNormalized,
+
+    /// The label starts or ends with a hyphen or has two hyphens in the
+    /// third and fourth position.
+    Hyphen,
+
+    /// The label starts with a combining mark.
+    LeadingCombiningMark,
+
+    /// The label violates the bidi rule of RFC 5893.
+    BidiRule,
+
+    /// The label is not a valid A-label.
+    Punycode,
+
+    /// The label is longer than 63 octets after encoding.
+    LongLabel,
+
+    /// The name is longer than 255 octets after encoding.
+    LongName,
+
+    /// An ASCII label is not in valid presentation format.
+    Presentation(FromStrError),
+
+    /// The buffer is too short to contain the name.
+    ShortBuf,
+}
+
+//--- From
+
+impl From<PushError> for IdnaErrorKind {
+    fn from(err: PushError) -> Self {
+        match err {
+            PushError::LongLabel => IdnaErrorKind::LongLabel,
+            PushError::LongName => IdnaErrorKind::LongName,
+            PushError::ShortBuf => IdnaErrorKind::ShortBuf,
+        }
+    }
+}
+
+impl From<FromStrError> for IdnaErrorKind {
+    fn from(err: FromStrError) -> Self {
+        match err {
+            FromStrError::LongLabel => IdnaErrorKind::LongLabel,
+            FromStrError::LongName => IdnaErrorKind::LongName,
+            FromStrError::ShortBuf => IdnaErrorKind::ShortBuf,
+            err => IdnaErrorKind::Presentation(err),
+        }
+    }
+}
+
+//--- Display
+
+impl fmt::Display for IdnaErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdnaErrorKind::EmptyLabel => f.write_str("empty label"),
+            IdnaErrorKind::DisallowedCodePoint(ch) => {
+                write!(f, "disallowed code point U+{:04X}", u32::from(ch))
+            }
+            IdnaErrorKind::NotNormalized => {
+                f.write_str("label not in normalization form C")
+            }
+            IdnaErrorKind::Hyphen => f.write_str("misplaced hyphen"),
+            IdnaErrorKind::LeadingCombiningMark => {
+                f.write_str("label starts with a combining mark")
+            }
+            IdnaErrorKind::BidiRule => f.write_str("bidi rule violated"),
+            IdnaErrorKind::Punycode => f.write_str("invalid A-label"),
+            IdnaErrorKind::LongLabel => {
+                f.write_str("label length limit exceeded")
+            }
+            IdnaErrorKind::LongName => f.write_str("long domain name"),
+            IdnaErrorKind::Presentation(err) => err.fmt(f),
+            IdnaErrorKind::ShortBuf => ShortBuf.fmt(f),
+        }
+    }
+}
+
+//------------ PunycodeError -------------------------------------------------
+
+/// A string could not be encoded or decoded using Punycode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PunycodeError;
+
+impl fmt::Display for PunycodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid Punycode")
+    }
+}
+
+impl std::error::Error for PunycodeError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn punycode() {
+        // Samples from RFC 3492, section 7.1, and some simple cases.
+        for (decoded, encoded) in [
+            ("bücher", "bcher-kva"),
+            ("münchen", "mnchen-3ya"),
+            ("他们为什么不说中文", "ihqwcrb4cv8a8dqg056pqjye"),
+            (
+                "\u{0644}\u{064A}\u{0647}\u{0645}\u{0627}\u{0628}\u{062A}\
+                 \u{0643}\u{0644}\u{0645}\u{0648}\u{0634}\u{0639}\u{0631}\
+                 \u{0628}\u{064A}\u{061F}",
+                "egbpdaj6bu4bxfgehfvwxn",
+            ),
+            ("3年B組金八先生", "3B-ww4c5e180e575a65lsy2b"),
+        ] {
+            assert_eq!(punycode_encode(decoded).unwrap(), encoded);
+            assert_eq!(punycode_decode(encoded).unwrap(), decoded);
+        }
+        assert!(punycode_decode("bcher-kv!").is_err());
+        assert!(punycode_decode("99999999999").is_err());
+    }
+
+    #[test]
+    fn from_and_to_unicode() {
+        let name = Dname::<Vec<u8>>::from_unicode("Bücher.example").unwrap();
+        assert_eq!(name.to_string(), "xn--bcher-kva.example");
+        assert_eq!(name.to_unicode().unwrap(), "bücher.example");
+
+        let name =
+            Dname::<Vec<u8>>::from_unicode("ＭÜＮＣＨＥＮ。de.").unwrap();
+        assert_eq!(name.to_string(), "xn--mnchen-3ya.de");
+
+        let name = Dname::<Vec<u8>>::from_unicode("_dmarc.bücher.exa\\.mple")
+            .unwrap();
+        assert_eq!(name.to_unicode().unwrap(), "_dmarc.bücher.exa\\.mple");
+
+        let name = Dname::<Vec<u8>>::from_unicode(".").unwrap();
+        assert!(name.is_root());
+    }
+
+    #[test]
+    fn ignored_code_points() {
+        for (unicode, ascii) in [
+            ("ex\u{AD}ample.com", "example.com"),
+            ("bü\u{AD}cher.example", "xn--bcher-kva.example"),
+            ("bü\u{200B}cher.example", "xn--bcher-kva.example"),
+            ("bü\u{2060}cher.example", "xn--bcher-kva.example"),
+            ("bü\u{FE00}cher.example", "xn--bcher-kva.example"),
+            ("bücher\u{FE0F}.example", "xn--bcher-kva.example"),
+            ("\u{FEFF}bücher.example", "xn--bcher-kva.example"),
+        ] {
+            assert_eq!(
+                Dname::<Vec<u8>>::from_unicode(unicode).unwrap().to_string(),
+                ascii
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        fn err(s: &str) -> (usize, IdnaErrorKind) {
+            let err = Dname::<Vec<u8>>::from_unicode(s).unwrap_err();
+            (err.label(), err.kind())
+        }
+
+        assert_eq!(
+            err("example.bü\u{2028}cher"),
+            (1, IdnaErrorKind::DisallowedCodePoint('\u{2028}'))
+        );
+        assert_eq!(
+            err("example.\u{05D0}a.com"),
+            (1, IdnaErrorKind::BidiRule)
+        );
+        assert_eq!(err("1\u{05D0}.example"), (0, IdnaErrorKind::BidiRule));
+        assert_eq!(err(&"ü".repeat(60)), (0, IdnaErrorKind::LongLabel));
+        assert_eq!(err("-bü.example"), (0, IdnaErrorKind::Hyphen));
+        assert_eq!(
+            err("a.\u{0301}bü"),
+            (1, IdnaErrorKind::LeadingCombiningMark)
+        );
+        assert_eq!(err("a..b"), (1, IdnaErrorKind::EmptyLabel));
+        assert_eq!(err(""), (0, IdnaErrorKind::EmptyLabel));
+        assert_eq!(err("\u{AD}.example"), (0, IdnaErrorKind::EmptyLabel));
+        assert_eq!(err("xn--zz.example"), (0, IdnaErrorKind::Punycode));
+    }
+}
@@ -61,7 +61,12 @@ pub use self::builder::{
     DnameBuilder, FromStrError, PushError, PushNameError,
 };
 pub use self::chain::{Chain, ChainIter, LongChainError, UncertainChainIter};
+pub use self::check::{NameCheckError, NameCheckErrorKind};
 pub use self::dname::{Dname, DnameError};
+#[cfg(feature = "idna")]
+pub use self::idna::{
+    punycode_decode, punycode_encode, IdnaError, IdnaErrorKind, PunycodeError,
+};
 pub use self::label::{
     Label, LabelTypeError, LongLabelError, OwnedLabel, SliceLabelsIter,
     SplitLabelError,
@@ -71,14 +76,25 @@ pub use self::relative::{
     DnameIter, RelativeDname, RelativeDnameError, RelativeFromStrError,
     StripSuffixError,
 };
+pub use self::reverse::{
+    AlignedPrefixes, IpPrefix, PrefixError, PrefixFromStrError, ReverseError,
+};
 pub use self::traits::{ToDname, ToLabelIter, ToRelativeDname};
+#[cfg(feature = "std")]
+pub use self::trie::{DnameTrie, DnameTrieIter};
 pub use self::uncertain::UncertainDname;
 
 mod builder;
 mod chain;
+mod check;
 mod dname;
+#[cfg(feature = "idna")]
+mod idna;
 mod label;
 mod parsed;
 mod relative;
+mod reverse;
 mod traits;
+#[cfg(feature = "std")]
+mod trie;
 mod uncertain;
@@ -5,6 +5,7 @@
 use super::super::wire::ParseError;
 use super::builder::{DnameBuilder, FromStrError, PushError};
 use super::chain::{Chain, LongChainError};
+use super::check::NameCheckError;
 use super::dname::Dname;
 use super::label::{Label, LabelTypeError, SplitLabelError};
 use super::traits::{ToLabelIter, ToRelativeDname};
@@ -369,6 +370,34 @@ impl<Octs: AsRef<[u8]> + ?Sized> RelativeDname<Octs> {
         <Self as ToLabelIter>::ends_with(self, base)
     }
 
+    /// Checks that the name is a valid host name.
+    ///
+    /// See [`ToLabelIter::check_hostname`] for details.
+    pub fn check_hostname(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_hostname(self)
+    }
+
+    /// Checks that the name is a host name with an optional wildcard.
+    ///
+    /// See [`ToLabelIter::check_wildcard_hostname`] for details.
+    pub fn check_wildcard_hostname(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_wildcard_hostname(self)
+    }
+
+    /// Checks that the name is a valid service name.
+    ///
+    /// See [`ToLabelIter::check_service_name`] for details.
+    pub fn check_service_name(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_service_name(self)
+    }
+
+    /// Checks that the name is a valid mailbox name.
+    ///
+    /// See [`ToLabelIter::check_mailbox`] for details.
+    pub fn check_mailbox(&self) -> Result<(), NameCheckError> {
+        <Self as ToLabelIter>::check_mailbox(self)
+    }
+
     /// Returns whether an index points to the first octet of a label.
     pub fn is_label_start(&self, mut index: usize) -> bool {
         if index == 0 {
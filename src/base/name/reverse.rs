@@ -0,0 +1,793 @@
+//! Reverse lookup names for IP addresses and prefixes.
+//!
+//! Addresses are mapped into the domain name space below `in-addr.arpa` for
+//! IPv4 (defined in [RFC 1035]) and below `ip6.arpa` for IPv6 (defined in
+//! [RFC 3596]). An IPv4 address is represented by one decimal label per
+//! octet, an IPv6 address by one hexadecimal label per nibble, in both
+//! cases starting with the least significant one.
+//!
+//! Prefixes map to the name of the reverse zone they span. For IPv6, this
+//! only works if the prefix ends on a nibble boundary. For IPv4 prefixes
+//! that don’t end on an octet boundary, the classless form described in
+//! [RFC 2317] is used where the leftmost label contains the value of the
+//! partial octet and the prefix length separated by a slash, e.g.,
+//! `0/25.2.0.192.in-addr.arpa.` for `192.0.2.0/25`.
+//!
+//! [RFC 1035]: https://tools.ietf.org/html/rfc1035
+//! [RFC 2317]: https://tools.ietf.org/html/rfc2317
+//! [RFC 3596]: https://tools.ietf.org/html/rfc3596
+
+use super::builder::{DnameBuilder, PushError};
+use super::dname::Dname;
+use super::label::Label;
+use super::traits::ToDname;
+use crate::base::net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
+use core::{fmt, str};
+use octseq::builder::{
+    EmptyBuilder, FreezeBuilder, FromBuilder, OctetsBuilder, ShortBuf,
+};
+
+//------------ Dname ---------------------------------------------------------
+
+impl<Octs> Dname<Octs> {
+    /// Creates the reverse lookup name for an IP address.
+    ///
+    /// The name will be below `in-addr.arpa.` for an IPv4 address and
+    /// below `ip6.arpa.` for an IPv6 address. Hexadecimal digits in the
+    /// latter are given in lower case.
+    ///
+    /// The function only fails if the octets builder runs out of space.
+    pub fn reverse_from_addr(addr: IpAddr) -> Result<Self, ShortBuf>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+            + FreezeBuilder<Octets = Octs>
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+    {
+        // Full-length prefixes are always nibble-aligned, so the builder
+        // is the only thing that can fail.
+        Self::reverse_from_prefix(IpPrefix::from_host(addr))
+            .map_err(|_| ShortBuf)
+    }
+
+    /// Creates the name of the reverse zone for an IP prefix.
+    ///
+    /// For IPv4 prefixes that do not end on an octet boundary, the
+    /// classless form of [RFC 2317] is used. IPv6 prefixes need to end on
+    /// a nibble boundary, i.e., their length must be divisible by four.
+    /// If that is not the case, the function fails with
+    /// [`ReverseError::NotNibbleAligned`]. Use
+    /// [`IpPrefix::aligned_prefixes`] to split such a prefix up into
+    /// prefixes that can be represented.
+    ///
+    /// [RFC 2317]: https://tools.ietf.org/html/rfc2317
+    pub fn reverse_from_prefix(prefix: IpPrefix) -> Result<Self, ReverseError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder
+            + FreezeBuilder<Octets = Octs>
+            + AsRef<[u8]>
+            + AsMut<[u8]>,
+    {
+        let mut builder = DnameBuilder::<Octs::Builder>::new();
+        match prefix.addr {
+            IpAddr::V4(addr) => {
+                let octets = addr.octets();
+                let full = usize::from(prefix.len / 8);
+                if prefix.len % 8 != 0 {
+                    push_decimal(&mut builder, octets[full])?;
+                    builder.push(b'/')?;
+                    push_decimal(&mut builder, prefix.len)?;
+                    builder.end_label();
+                }
+                for &octet in octets[..full].iter().rev() {
+                    push_decimal(&mut builder, octet)?;
+                    builder.end_label();
+                }
+                builder.append_label(b"in-addr")?;
+            }
+            IpAddr::V6(addr) => {
+                if prefix.len % 4 != 0 {
+                    return Err(ReverseError::NotNibbleAligned);
+                }
+                let octets = addr.octets();
+                for i in (0..usize::from(prefix.len / 4)).rev() {
+                    let nibble = if i % 2 == 0 {
+                        octets[i / 2] >> 4
+                    } else {
+                        octets[i / 2] & 0x0F
+                    };
+                    builder.append_label(&[hexdigit(nibble)])?;
+                }
+                builder.append_label(b"ip6")?;
+            }
+        }
+        builder.append_label(b"arpa")?;
+        builder.into_dname().map_err(Into::into)
+    }
+}
+
+//------------ IpPrefix ------------------------------------------------------
+
+/// An IP address prefix.
+///
+/// A prefix consists of an address and a prefix length, i.e., the number of
+/// leading bits of the address that are significant. All other bits of the
+/// address – the host bits – must be zero.
+///
+/// The type can be converted to and from its reverse lookup name via
+/// [`Dname::reverse_from_prefix`] and [`IpPrefix::from_reverse_name`],
+/// respectively. Its string representation is the usual CIDR notation of
+/// address and prefix length separated by a slash.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IpPrefix {
+    /// The address with all host bits set to zero.
+    addr: IpAddr,
+
+    /// The prefix length in bits.
+    len: u8,
+}
+
+impl IpPrefix {
+    /// Creates a new prefix from an address and a prefix length.
+    ///
+    /// The function fails if the length is greater than the number of bits
+    /// in the address or if any of the host bits of the address are set.
+    pub fn new(addr: IpAddr, len: u8) -> Result<Self, PrefixError> {
+        let (bits, max) = Self::addr_bits(addr);
+        if len > max {
+            return Err(PrefixError::LongPrefix);
+        }
+        if len < max && bits << len != 0 {
+            return Err(PrefixError::HostBits);
+        }
+        Ok(IpPrefix { addr, len })
+    }
+
+    /// Creates a new prefix covering exactly one address.
+    pub fn from_host(addr: IpAddr) -> Self {
+        IpPrefix {
+            addr,
+            len: Self::addr_bits(addr).1,
+        }
+    }
+
+    /// Creates a prefix from its reverse lookup name.
+    ///
+    /// The name must be below either `in-addr.arpa.` or `ip6.arpa.`. For
+    /// IPv4, each label must be an octet in decimal notation without leading
+    /// zeros. The leftmost label may instead be in the classless form of
+    /// [RFC 2317], i.e., the value of the partial octet and the prefix
+    /// length separated by a slash. For IPv6, each label must be a single
+    /// hexadecimal digit. The resulting prefix covers all the octets or
+    /// nibbles present in the name.
+    ///
+    /// [RFC 2317]: https://tools.ietf.org/html/rfc2317
+    pub fn from_reverse_name<N: ToDname + ?Sized>(
+        name: &N,
+    ) -> Result<Self, ReverseError> {
+        let mut labels = name.iter_labels().rev();
+
+        // The last label is always the root label.
+        let _ = labels.next();
+        if !labels.next().map_or(false, |label| label == b"arpa") {
+            return Err(ReverseError::NotReverse);
+        }
+        match labels.next() {
+            Some(label) if label == b"in-addr" => {
+                Self::from_v4_labels(labels)
+            }
+            Some(label) if label == b"ip6" => Self::from_v6_labels(labels),
+            _ => Err(ReverseError::NotReverse),
+        }
+    }
+
+    /// Creates an IPv4 prefix from the labels before `in-addr.arpa`.
+    ///
+    /// The labels need to be given starting with the rightmost one.
+    fn from_v4_labels<'a>(
+        labels: impl Iterator<Item = &'a Label>,
+    ) -> Result<Self, ReverseError> {
+        let mut octets = [0u8; 4];
+        let mut len = 0u8;
+        for label in labels {
+            // Either all octets are there already or the previous label
+            // was a classless one which must be the leftmost label.
+            if len == 32 || len % 8 != 0 {
+                return Err(ReverseError::InvalidLabel);
+            }
+            let idx = usize::from(len / 8);
+            match label.iter().position(|&ch| ch == b'/') {
+                Some(pos) => {
+                    octets[idx] = parse_decimal(&label[..pos])?;
+                    let prefix_len = parse_decimal(&label[pos + 1..])?;
+                    if prefix_len <= len || prefix_len >= len + 8 {
+                        return Err(ReverseError::InvalidLabel);
+                    }
+                    len = prefix_len;
+                }
+                None => {
+                    octets[idx] = parse_decimal(label)?;
+                    len += 8;
+                }
+            }
+        }
+        Self::new(Ipv4Addr::from(octets).into(), len)
+            .map_err(|_| ReverseError::InvalidLabel)
+    }
+
+    /// Creates an IPv6 prefix from the labels before `ip6.arpa`.
+    ///
+    /// The labels need to be given starting with the rightmost one.
+    fn from_v6_labels<'a>(
+        labels: impl Iterator<Item = &'a Label>,
+    ) -> Result<Self, ReverseError> {
+        let mut octets = [0u8; 16];
+        let mut len = 0u8;
+        for label in labels {
+            if len == 128 || label.len() != 1 {
+                return Err(ReverseError::InvalidLabel);
+            }
+            let nibble = parse_hexdigit(label[0])?;
+            let idx = usize::from(len / 8);
+            if len % 8 == 0 {
+                octets[idx] = nibble << 4;
+            } else {
+                octets[idx] |= nibble;
+            }
+            len += 4;
+        }
+        Ok(IpPrefix {
+            addr: Ipv6Addr::from(octets).into(),
+            len,
+        })
+    }
+
+    /// Returns the address of the prefix.
+    ///
+    /// All host bits of the address are zero.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the prefix in bits.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u8 {
+        self.len
+    }
+
+    /// Returns the address if the prefix covers exactly one address.
+    pub fn host(&self) -> Option<IpAddr> {
+        if self.len == Self::addr_bits(self.addr).1 {
+            Some(self.addr)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the prefix contains the given address.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let (own, max) = Self::addr_bits(self.addr);
+        let (other, other_max) = Self::addr_bits(addr);
+        if max != other_max {
+            return false;
+        }
+        if self.len == 0 {
+            return true;
+        }
+        let shift = u32::from(128 - self.len);
+        own >> shift == other >> shift
+    }
+
+    /// Returns an iterator over prefixes that have a reverse zone.
+    ///
+    /// For IPv6, the iterator splits the prefix into those prefixes at the
+    /// next nibble boundary that together cover the prefix. For IPv4, it
+    /// uses the next octet boundary. If the prefix already ends on such a
+    /// boundary, the iterator only returns the prefix itself.
+    ///
+    /// Note that IPv4 prefixes can also be represented directly using the
+    /// classless form of [RFC 2317]. Which of the two is appropriate
+    /// depends on how delegation for the address space is arranged.
+    ///
+    /// [RFC 2317]: https://tools.ietf.org/html/rfc2317
+    pub fn aligned_prefixes(&self) -> AlignedPrefixes {
+        let boundary = match self.addr {
+            IpAddr::V4(_) => 8,
+            IpAddr::V6(_) => 4,
+        };
+        let len = (self.len + boundary - 1) / boundary * boundary;
+        AlignedPrefixes {
+            base: *self,
+            len,
+            next: 0,
+            count: 1 << (len - self.len),
+        }
+    }
+
+    /// Returns the address as a left-aligned integer and its bit length.
+    fn addr_bits(addr: IpAddr) -> (u128, u8) {
+        match addr {
+            IpAddr::V4(addr) => {
+                (u128::from(u32::from_be_bytes(addr.octets())) << 96, 32)
+            }
+            IpAddr::V6(addr) => (u128::from_be_bytes(addr.octets()), 128),
+        }
+    }
+}
+
+//--- FromStr
+
+impl str::FromStr for IpPrefix {
+    type Err = PrefixFromStrError;
+
+    /// Parses a prefix in CIDR notation.
+    ///
+    /// If the prefix length is missing, the prefix will cover a single
+    /// address.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((addr, len)) => Self::new(
+                addr.parse()?,
+                len.parse().map_err(|_| PrefixFromStrError::Len)?,
+            )
+            .map_err(Into::into),
+            None => Ok(Self::from_host(s.parse()?)),
+        }
+    }
+}
+
+//--- Display
+
+impl fmt::Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.addr {
+            IpAddr::V4(addr) => write!(f, "{}/{}", addr, self.len),
+            IpAddr::V6(addr) => write!(f, "{}/{}", addr, self.len),
+        }
+    }
+}
+
+//------------ AlignedPrefixes -----------------------------------------------
+
+/// An iterator over the label-aligned prefixes covering a prefix.
+///
+/// This type is returned by [`IpPrefix::aligned_prefixes`].
+#[derive(Clone, Debug)]
+pub struct AlignedPrefixes {
+    /// The prefix we are splitting up.
+    base: IpPrefix,
+
+    /// The length of the resulting prefixes.
+    len: u8,
+
+    /// The index of the next prefix to return.
+    next: u8,
+
+    /// The number of prefixes to return.
+    count: u8,
+}
+
+impl Iterator for AlignedPrefixes {
+    type Item = IpPrefix;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.count {
+            return None;
+        }
+        let (bits, max) = IpPrefix::addr_bits(self.base.addr);
+        let bits = bits
+            | u128::from(self.next)
+                .checked_shl(128 - u32::from(self.len))
+                .unwrap_or(0);
+        self.next += 1;
+        let addr = match max {
+            32 => IpAddr::from(Ipv4Addr::from(
+                ((bits >> 96) as u32).to_be_bytes(),
+            )),
+            _ => IpAddr::from(Ipv6Addr::from(bits.to_be_bytes())),
+        };
+        Some(IpPrefix {
+            addr,
+            len: self.len,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.count - self.next);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for AlignedPrefixes {}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Pushes the decimal representation of an octet to a builder.
+fn push_decimal<Builder>(
+    builder: &mut DnameBuilder<Builder>,
+    value: u8,
+) -> Result<(), PushError>
+where
+    Builder: OctetsBuilder + AsRef<[u8]> + AsMut<[u8]>,
+{
+    if value >= 100 {
+        builder.push(b'0' + value / 100)?;
+    }
+    if value >= 10 {
+        builder.push(b'0' + value / 10 % 10)?;
+    }
+    builder.push(b'0' + value % 10)
+}
+
+/// Parses a decimal octet value without leading zeros.
+fn parse_decimal(slice: &[u8]) -> Result<u8, ReverseError> {
+    if slice.is_empty()
+        || slice.len() > 3
+        || (slice.len() > 1 && slice[0] == b'0')
+    {
+        return Err(ReverseError::InvalidLabel);
+    }
+    let mut res = 0u16;
+    for &ch in slice {
+        if !ch.is_ascii_digit() {
+            return Err(ReverseError::InvalidLabel);
+        }
+        res = res * 10 + u16::from(ch - b'0');
+    }
+    u8::try_from(res).map_err(|_| ReverseError::InvalidLabel)
+}
+
+/// Returns the lower case hexadecimal digit for a nibble.
+fn hexdigit(nibble: u8) -> u8 {
+    b"0123456789abcdef"[usize::from(nibble & 0x0F)]
+}
+
+/// Parses a hexadecimal digit in either case.
+fn parse_hexdigit(ch: u8) -> Result<u8, ReverseError> {
+    match ch {
+        b'0'..=b'9' => Ok(ch - b'0'),
+        b'a'..=b'f' => Ok(ch - b'a' + 10),
+        b'A'..=b'F' => Ok(ch - b'A' + 10),
+        _ => Err(ReverseError::InvalidLabel),
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ PrefixError ---------------------------------------------------
+
+/// An address and prefix length did not form a valid prefix.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrefixError {
+    /// The prefix length exceeds the length of the address.
+    LongPrefix,
+
+    /// Bits of the address beyond the prefix length are set.
+    HostBits,
+}
+
+//--- Display and Error
+
+impl fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PrefixError::LongPrefix => f.write_str("prefix length too long"),
+            PrefixError::HostBits => f.write_str("host bits set in prefix"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrefixError {}
+
+//------------ PrefixFromStrError --------------------------------------------
+
+/// A string could not be parsed into a prefix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PrefixFromStrError {
+    /// The address part was not a valid address.
+    Addr(AddrParseError),
+
+    /// The prefix length part was not a valid number.
+    Len,
+
+    /// Address and length did not form a valid prefix.
+    Prefix(PrefixError),
+}
+
+//--- From
+
+impl From<AddrParseError> for PrefixFromStrError {
+    fn from(err: AddrParseError) -> Self {
+        PrefixFromStrError::Addr(err)
+    }
+}
+
+impl From<PrefixError> for PrefixFromStrError {
+    fn from(err: PrefixError) -> Self {
+        PrefixFromStrError::Prefix(err)
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for PrefixFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PrefixFromStrError::Addr(_) => f.write_str("invalid address"),
+            PrefixFromStrError::Len => f.write_str("invalid prefix length"),
+            PrefixFromStrError::Prefix(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrefixFromStrError {}
+
+//------------ ReverseError --------------------------------------------------
+
+/// Converting between a prefix and a reverse name failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReverseError {
+    /// The name is not below `in-addr.arpa.` or `ip6.arpa.`.
+    NotReverse,
+
+    /// A label of the name does not represent part of an address.
+    InvalidLabel,
+
+    /// The IPv6 prefix does not end on a nibble boundary.
+    NotNibbleAligned,
+
+    /// The octets builder ran out of space.
+    ShortBuf,
+}
+
+//--- From
+
+impl From<PushError> for ReverseError {
+    fn from(_: PushError) -> Self {
+        // Reverse names are always well within the length limits, so
+        // running out of buffer is the only error we can encounter.
+        ReverseError::ShortBuf
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for ReverseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReverseError::NotReverse => f.write_str("not a reverse name"),
+            ReverseError::InvalidLabel => {
+                f.write_str("invalid label in reverse name")
+            }
+            ReverseError::NotNibbleAligned => {
+                f.write_str("prefix not aligned to nibble boundary")
+            }
+            ReverseError::ShortBuf => ShortBuf.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReverseError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    type Name = Dname<Vec<u8>>;
+
+    fn prefix(s: &str) -> IpPrefix {
+        IpPrefix::from_str(s).unwrap()
+    }
+
+    fn name(s: &str) -> Name {
+        Name::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn prefix_new() {
+        assert!(IpPrefix::new([192, 0, 2, 0].into(), 24).is_ok());
+        assert!(IpPrefix::new([192, 0, 2, 128].into(), 25).is_ok());
+        assert_eq!(
+            IpPrefix::new([192, 0, 2, 128].into(), 24),
+            Err(PrefixError::HostBits)
+        );
+        assert_eq!(
+            IpPrefix::new([192, 0, 2, 0].into(), 33),
+            Err(PrefixError::LongPrefix)
+        );
+        assert!(IpPrefix::new([0; 4].into(), 0).is_ok());
+        assert_eq!(
+            IpPrefix::new([1, 0, 0, 0].into(), 0),
+            Err(PrefixError::HostBits)
+        );
+        assert_eq!(
+            prefix("2001:db8::/32").addr(),
+            IpAddr::from_str("2001:db8::").unwrap()
+        );
+        assert_eq!(
+            IpPrefix::from_str("2001:db8::1/64"),
+            Err(PrefixFromStrError::Prefix(PrefixError::HostBits))
+        );
+        assert_eq!(
+            IpPrefix::from_str("2001:db8::/129"),
+            Err(PrefixFromStrError::Prefix(PrefixError::LongPrefix))
+        );
+        assert_eq!(
+            IpPrefix::from_str("192.0.2.0/x"),
+            Err(PrefixFromStrError::Len)
+        );
+        assert_eq!(prefix("192.0.2.1").host(), Some([192, 0, 2, 1].into()));
+        assert_eq!(prefix("192.0.2.0/24").host(), None);
+        assert!(prefix("192.0.2.0/25").contains([192, 0, 2, 127].into()));
+        assert!(!prefix("192.0.2.0/25").contains([192, 0, 2, 128].into()));
+        assert!(!prefix("0.0.0.0/0").contains([0u8; 16].into()));
+        assert_eq!(prefix("2001:db8::/32").to_string(), "2001:db8::/32");
+    }
+
+    #[test]
+    fn reverse_from_addr() {
+        assert_eq!(
+            Name::reverse_from_addr([192, 0, 2, 12].into()).unwrap(),
+            name("12.2.0.192.in-addr.arpa")
+        );
+        assert_eq!(
+            Name::reverse_from_addr([10, 0, 0, 255].into()).unwrap(),
+            name("255.0.0.10.in-addr.arpa")
+        );
+        let addr = Name::reverse_from_addr(
+            [0x2001, 0xdb8, 0x1234, 0x0, 0x5678, 0x1, 0x9abc, 0xdef].into(),
+        )
+        .unwrap();
+        assert_eq!(
+            addr.to_string(),
+            "f.e.d.0.c.b.a.9.1.0.0.0.8.7.6.5.\
+             0.0.0.0.4.3.2.1.8.b.d.0.1.0.0.2.\
+             ip6.arpa"
+        );
+    }
+
+    #[test]
+    fn reverse_from_prefix() {
+        fn from_prefix(s: &str) -> Result<String, ReverseError> {
+            Name::reverse_from_prefix(prefix(s)).map(|name| name.to_string())
+        }
+
+        assert_eq!(
+            from_prefix("192.0.2.0/24").unwrap(),
+            "2.0.192.in-addr.arpa"
+        );
+        assert_eq!(from_prefix("10.0.0.0/8").unwrap(), "10.in-addr.arpa");
+        assert_eq!(from_prefix("0.0.0.0/0").unwrap(), "in-addr.arpa");
+        assert_eq!(
+            from_prefix("192.0.2.0/25").unwrap(),
+            "0/25.2.0.192.in-addr.arpa"
+        );
+        assert_eq!(
+            from_prefix("192.0.2.64/26").unwrap(),
+            "64/26.2.0.192.in-addr.arpa"
+        );
+        assert_eq!(
+            from_prefix("2001:db8::/32").unwrap(),
+            "8.b.d.0.1.0.0.2.ip6.arpa"
+        );
+        assert_eq!(
+            from_prefix("2001:db8:f0::/44").unwrap(),
+            "f.0.0.8.b.d.0.1.0.0.2.ip6.arpa"
+        );
+        assert_eq!(from_prefix("::/0").unwrap(), "ip6.arpa");
+        assert_eq!(
+            from_prefix("2001:db8::/33"),
+            Err(ReverseError::NotNibbleAligned)
+        );
+    }
+
+    #[test]
+    fn from_reverse_name() {
+        fn from_name(s: &str) -> Result<IpPrefix, ReverseError> {
+            IpPrefix::from_reverse_name(&name(s))
+        }
+
+        for s in [
+            "192.0.2.12/32",
+            "192.0.2.0/24",
+            "10.0.0.0/8",
+            "0.0.0.0/0",
+            "192.0.2.0/25",
+            "192.0.2.192/27",
+            "2001:db8::/32",
+            "2001:db8:f0::/44",
+            "2001:db8:1234:0:5678:1:9abc:def/128",
+            "::/0",
+        ] {
+            let prefix = prefix(s);
+            assert_eq!(
+                from_name(
+                    &Name::reverse_from_prefix(prefix).unwrap().to_string()
+                ),
+                Ok(prefix)
+            );
+        }
+
+        assert_eq!(
+            from_name("8.B.D.0.1.0.0.2.IP6.ARPA"),
+            Ok(prefix("2001:db8::/32"))
+        );
+        assert_eq!(
+            from_name("2.0.192.IN-ADDR.arpa"),
+            Ok(prefix("192.0.2.0/24"))
+        );
+        assert_eq!(from_name("example.com"), Err(ReverseError::NotReverse));
+        assert_eq!(from_name("arpa"), Err(ReverseError::NotReverse));
+        assert_eq!(from_name("."), Err(ReverseError::NotReverse));
+        assert_eq!(
+            from_name("1.2.3.4.5.in-addr.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("02.0.192.in-addr.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("256.0.192.in-addr.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("1.0/25.2.0.192.in-addr.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("0/24.2.0.192.in-addr.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("1/25.2.0.192.in-addr.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("10.b.d.0.1.0.0.2.ip6.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+        assert_eq!(
+            from_name("g.b.d.0.1.0.0.2.ip6.arpa"),
+            Err(ReverseError::InvalidLabel)
+        );
+    }
+
+    #[test]
+    fn aligned_prefixes() {
+        fn aligned(s: &str) -> Vec<IpPrefix> {
+            prefix(s).aligned_prefixes().collect()
+        }
+
+        assert_eq!(aligned("192.0.2.0/24"), [prefix("192.0.2.0/24")]);
+        assert_eq!(
+            aligned("192.0.0.0/22"),
+            [
+                prefix("192.0.0.0/24"),
+                prefix("192.0.1.0/24"),
+                prefix("192.0.2.0/24"),
+                prefix("192.0.3.0/24"),
+            ]
+        );
+        assert_eq!(aligned("10.0.0.0/7").len(), 2);
+        assert_eq!(aligned("10.0.0.0/7")[1], prefix("11.0.0.0/8"));
+        assert_eq!(
+            aligned("2001:db8::/31"),
+            [prefix("2001:db8::/32"), prefix("2001:db9::/32")]
+        );
+        assert_eq!(aligned("::/1").len(), 8);
+        assert_eq!(aligned("::/1")[7], prefix("7000::/4"));
+        assert_eq!(aligned("192.0.2.128/25").len(), 128);
+    }
+}
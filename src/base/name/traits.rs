@@ -4,6 +4,7 @@
 
 use super::builder::PushError;
 use super::chain::{Chain, LongChainError};
+use super::check::{self, NameCheckError};
 use super::dname::Dname;
 use super::label::Label;
 use super::relative::RelativeDname;
@@ -81,6 +82,43 @@ pub trait ToLabelIter {
             }
         }
     }
+
+    /// Checks that the name is a valid host name.
+    ///
+    /// All labels must follow the LDH rule of RFC 952 and RFC 1123, i.e.,
+    /// consist of ASCII letters, digits, and hyphens only and neither start
+    /// nor end with a hyphen.
+    fn check_hostname(&self) -> Result<(), NameCheckError> {
+        check::check_hostname(self.iter_labels())
+    }
+
+    /// Checks that the name is a host name with an optional wildcard.
+    ///
+    /// This is the same as [`check_hostname`][Self::check_hostname] except
+    /// that the leftmost label may be the wildcard label `*`.
+    fn check_wildcard_hostname(&self) -> Result<(), NameCheckError> {
+        check::check_wildcard_hostname(self.iter_labels())
+    }
+
+    /// Checks that the name is a valid service name.
+    ///
+    /// A service name starts with a label consisting of an underscore
+    /// followed by a label following the LDH rule, such as `_sip`. Such
+    /// service labels may also appear further down the name, as in
+    /// `_ldap._tcp.dc._msdcs.example.com`. All other labels must follow the
+    /// LDH rule.
+    fn check_service_name(&self) -> Result<(), NameCheckError> {
+        check::check_service_name(self.iter_labels())
+    }
+
+    /// Checks that the name is a valid mailbox name.
+    ///
+    /// The first label of a mailbox name is the local part and may contain
+    /// any printable ASCII character. The remaining labels must form a host
+    /// name.
+    fn check_mailbox(&self) -> Result<(), NameCheckError> {
+        check::check_mailbox(self.iter_labels())
+    }
 }
 
 impl<'r, N: ToLabelIter + ?Sized> ToLabelIter for &'r N {
@@ -0,0 +1,481 @@
+//! A map keyed by domain names.
+//!
+//! This is a private module. Its public types are re-exported by the parent
+//! crate.
+
+use super::label::{Label, OwnedLabel};
+use super::traits::ToDname;
+use core::{fmt, iter};
+use std::collections::{btree_map, BTreeMap};
+use std::vec::Vec;
+
+//------------ DnameTrie -----------------------------------------------------
+
+/// A map from domain names to values organized as a tree of labels.
+///
+/// The trie stores its entries in a tree that mirrors the domain name
+/// hierarchy: each node represents one label and the children of a node
+/// are ordered by the canonical ordering of their labels. Labels are
+/// compared ignoring ASCII case.
+///
+/// Lookups accept any type implementing [`ToDname`] and do not allocate.
+/// Besides looking up exact names via [`get`][Self::get], you can find the
+/// stored name that is the closest enclosing suffix of a name via
+/// [`longest_suffix`][Self::longest_suffix] and perform wildcard matching
+/// via [`wildcard_match`][Self::wildcard_match].
+///
+/// The trie keeps the names it was given as keys, so it can hand them out
+/// again when looking up suffixes or iterating. Iteration happens in
+/// canonical name order as defined in [RFC 4034].
+///
+/// [RFC 4034]: https://tools.ietf.org/html/rfc4034
+#[derive(Clone)]
+pub struct DnameTrie<N, T> {
+    /// The node for the root label.
+    root: Node<N, T>,
+
+    /// The number of entries in the trie.
+    len: usize,
+}
+
+impl<N, T> DnameTrie<N, T> {
+    /// Creates a new, empty trie.
+    pub fn new() -> Self {
+        DnameTrie {
+            root: Node::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of entries in the trie.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the trie is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all entries from the trie.
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the entries in canonical name order.
+    pub fn iter(&self) -> DnameTrieIter<'_, N, T> {
+        DnameTrieIter {
+            next: Some(&self.root),
+            stack: Vec::new(),
+            len: self.len,
+        }
+    }
+}
+
+impl<N: ToDname, T> DnameTrie<N, T> {
+    /// Inserts a name and value into the trie.
+    ///
+    /// If the trie already contained an entry for the name, both the name
+    /// and the value are replaced and the old value is returned.
+    pub fn insert(&mut self, name: N, value: T) -> Option<T> {
+        let mut node = &mut self.root;
+        for label in name.iter_labels().rev().skip(1) {
+            node = node
+                .children
+                .entry(OwnedLabel::from_label(label))
+                .or_insert_with(Node::new);
+        }
+        match node.entry.replace((name, value)) {
+            Some((_, value)) => Some(value),
+            None => {
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes a name from the trie.
+    ///
+    /// Returns the stored name and value if the name was present.
+    pub fn remove<M: ToDname + ?Sized>(
+        &mut self,
+        name: &M,
+    ) -> Option<(N, T)> {
+        let res = self.root.remove(&mut name.iter_labels().rev().skip(1));
+        if res.is_some() {
+            self.len -= 1;
+        }
+        res
+    }
+}
+
+impl<N, T> DnameTrie<N, T> {
+    /// Returns whether the trie contains an entry for exactly this name.
+    pub fn contains<M: ToDname + ?Sized>(&self, name: &M) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns a reference to the value for exactly this name.
+    pub fn get<M: ToDname + ?Sized>(&self, name: &M) -> Option<&T> {
+        self.get_key_value(name).map(|(_, value)| value)
+    }
+
+    /// Returns the stored name and value for exactly this name.
+    pub fn get_key_value<M: ToDname + ?Sized>(
+        &self,
+        name: &M,
+    ) -> Option<(&N, &T)> {
+        self.find_node(name)?.entry()
+    }
+
+    /// Returns a mutable reference to the value for exactly this name.
+    pub fn get_mut<M: ToDname + ?Sized>(
+        &mut self,
+        name: &M,
+    ) -> Option<&mut T> {
+        let mut node = &mut self.root;
+        for label in name.iter_labels().rev().skip(1) {
+            node = node.children.get_mut(label)?;
+        }
+        node.entry.as_mut().map(|(_, value)| value)
+    }
+
+    /// Returns the entry for the longest stored suffix of a name.
+    ///
+    /// This is the entry for the stored name that is the closest enclosing
+    /// name of `name` or `name` itself if it is stored. Wildcard labels
+    /// are not treated specially by this method.
+    pub fn longest_suffix<M: ToDname + ?Sized>(
+        &self,
+        name: &M,
+    ) -> Option<(&N, &T)> {
+        let mut node = &self.root;
+        let mut res = node.entry();
+        for label in name.iter_labels().rev().skip(1) {
+            node = match node.children.get(label) {
+                Some(node) => node,
+                None => break,
+            };
+            res = node.entry().or(res);
+        }
+        res
+    }
+
+    /// Returns the entry matching a name with wildcard expansion.
+    ///
+    /// If the trie has an entry for exactly `name`, that entry is returned.
+    /// Otherwise, the method looks for stored names whose leftmost label
+    /// is the wildcard label `*` and returns the one with the longest
+    /// remaining suffix of `name`. A wildcard only matches names that have
+    /// at least one label in place of the asterisk, i.e., `*.example.com`
+    /// matches `www.example.com` and `a.b.example.com` but not
+    /// `example.com` itself.
+    ///
+    /// Note that unlike wildcard expansion in zones as described in
+    /// [RFC 4592], the wildcard does not need to be at the closest
+    /// encloser of `name`: with entries for `*.example.com` and
+    /// `www.example.com`, the name `a.www.example.com` matches the
+    /// wildcard. This is usually what is wanted for policy lookups.
+    ///
+    /// [RFC 4592]: https://tools.ietf.org/html/rfc4592
+    pub fn wildcard_match<M: ToDname + ?Sized>(
+        &self,
+        name: &M,
+    ) -> Option<(&N, &T)> {
+        let mut node = &self.root;
+        let mut res = None;
+        for label in name.iter_labels().rev().skip(1) {
+            if let Some(wildcard) = node.children.get(Label::wildcard()) {
+                res = wildcard.entry().or(res);
+            }
+            node = match node.children.get(label) {
+                Some(node) => node,
+                None => return res,
+            };
+        }
+        node.entry().or(res)
+    }
+
+    /// Returns the node for exactly the given name.
+    fn find_node<M: ToDname + ?Sized>(
+        &self,
+        name: &M,
+    ) -> Option<&Node<N, T>> {
+        let mut node = &self.root;
+        for label in name.iter_labels().rev().skip(1) {
+            node = node.children.get(label)?;
+        }
+        Some(node)
+    }
+}
+
+//--- Default
+
+impl<N, T> Default for DnameTrie<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//--- FromIterator and Extend
+
+impl<N: ToDname, T> iter::FromIterator<(N, T)> for DnameTrie<N, T> {
+    fn from_iter<I: IntoIterator<Item = (N, T)>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<N: ToDname, T> Extend<(N, T)> for DnameTrie<N, T> {
+    fn extend<I: IntoIterator<Item = (N, T)>>(&mut self, iter: I) {
+        for (name, value) in iter {
+            self.insert(name, value);
+        }
+    }
+}
+
+//--- IntoIterator
+
+impl<'a, N, T> IntoIterator for &'a DnameTrie<N, T> {
+    type Item = (&'a N, &'a T);
+    type IntoIter = DnameTrieIter<'a, N, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//--- Debug
+
+impl<N: fmt::Debug, T: fmt::Debug> fmt::Debug for DnameTrie<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+//------------ Node ----------------------------------------------------------
+
+/// A node in the trie.
+#[derive(Clone)]
+struct Node<N, T> {
+    /// The entry for the name ending in this node, if there is one.
+    entry: Option<(N, T)>,
+
+    /// The nodes for the labels below this node.
+    children: BTreeMap<OwnedLabel, Node<N, T>>,
+}
+
+impl<N, T> Node<N, T> {
+    /// Creates a new node without an entry or children.
+    fn new() -> Self {
+        Node {
+            entry: None,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the entry of the node as a pair of references.
+    fn entry(&self) -> Option<(&N, &T)> {
+        self.entry.as_ref().map(|(name, value)| (name, value))
+    }
+
+    /// Removes the entry for the remaining labels below this node.
+    ///
+    /// Nodes left without entries or children are dropped on the way back.
+    fn remove<'a, I: Iterator<Item = &'a Label>>(
+        &mut self,
+        labels: &mut I,
+    ) -> Option<(N, T)> {
+        let label = match labels.next() {
+            Some(label) => label,
+            None => return self.entry.take(),
+        };
+        let child = self.children.get_mut(label)?;
+        let res = child.remove(labels);
+        if child.entry.is_none() && child.children.is_empty() {
+            self.children.remove(label);
+        }
+        res
+    }
+}
+
+//------------ DnameTrieIter -------------------------------------------------
+
+/// An iterator over the entries of a [`DnameTrie`] in canonical order.
+pub struct DnameTrieIter<'a, N, T> {
+    /// The node to visit next.
+    next: Option<&'a Node<N, T>>,
+
+    /// The children of the nodes on the path to the current node.
+    stack: Vec<btree_map::Values<'a, OwnedLabel, Node<N, T>>>,
+
+    /// The number of entries left.
+    len: usize,
+}
+
+impl<'a, N, T> Iterator for DnameTrieIter<'a, N, T> {
+    type Item = (&'a N, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(node) = self.next.take() {
+                // A name sorts before all names below it, so we return
+                // the node’s own entry before descending.
+                self.stack.push(node.children.values());
+                if let Some(entry) = node.entry() {
+                    self.len -= 1;
+                    return Some(entry);
+                }
+            }
+            let children = self.stack.last_mut()?;
+            match children.next() {
+                Some(node) => self.next = Some(node),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, N, T> ExactSizeIterator for DnameTrieIter<'a, N, T> {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::name::Dname;
+    use core::str::FromStr;
+
+    type Name = Dname<Vec<u8>>;
+
+    fn name(s: &str) -> Name {
+        Name::from_str(s).unwrap()
+    }
+
+    fn trie(names: &[&str]) -> DnameTrie<Name, usize> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| (name(s), idx))
+            .collect()
+    }
+
+    fn found<'a>(res: Option<(&'a Name, &'a usize)>) -> Option<Name> {
+        res.map(|(name, _)| name.clone())
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut trie = trie(&["example.com", "www.example.com", "."]);
+        assert_eq!(trie.len(), 3);
+        assert_eq!(trie.get(&name("EXAMPLE.com")), Some(&0));
+        assert_eq!(trie.get(&name("www.example.com")), Some(&1));
+        assert_eq!(trie.get(&name(".")), Some(&2));
+        assert_eq!(trie.get(&name("com")), None);
+        assert_eq!(trie.get(&name("ftp.example.com")), None);
+        assert!(trie.contains(&name("www.Example.COM")));
+
+        assert_eq!(trie.insert(name("Example.com"), 10), Some(0));
+        assert_eq!(trie.len(), 3);
+        assert_eq!(
+            trie.get_key_value(&name("example.com")),
+            Some((&name("Example.com"), &10))
+        );
+        *trie.get_mut(&name("www.example.com")).unwrap() += 10;
+        assert_eq!(trie.get(&name("www.example.com")), Some(&11));
+
+        assert_eq!(trie.remove(&name("com")), None);
+        assert_eq!(
+            trie.remove(&name("example.com")),
+            Some((name("example.com"), 10))
+        );
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get(&name("example.com")), None);
+        assert_eq!(trie.get(&name("www.example.com")), Some(&11));
+        assert!(trie.remove(&name("www.example.com")).is_some());
+        assert!(trie.root.children.is_empty());
+        assert!(trie.remove(&name(".")).is_some());
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn longest_suffix() {
+        let trie = trie(&["com", "example.com", "a.b.example.com"]);
+        assert_eq!(
+            found(trie.longest_suffix(&name("www.example.com"))),
+            Some(name("example.com"))
+        );
+        assert_eq!(
+            found(trie.longest_suffix(&name("b.EXAMPLE.com"))),
+            Some(name("example.com"))
+        );
+        assert_eq!(
+            found(trie.longest_suffix(&name("x.a.b.example.com"))),
+            Some(name("a.b.example.com"))
+        );
+        assert_eq!(
+            found(trie.longest_suffix(&name("example.com"))),
+            Some(name("example.com"))
+        );
+        assert_eq!(found(trie.longest_suffix(&name("example.net"))), None);
+        assert_eq!(found(trie.longest_suffix(&name("."))), None);
+    }
+
+    #[test]
+    fn wildcard_match() {
+        let trie =
+            trie(&["*.example.com", "www.example.com", "*.a.example.com"]);
+        assert_eq!(
+            found(trie.wildcard_match(&name("www.example.com"))),
+            Some(name("www.example.com"))
+        );
+        assert_eq!(
+            found(trie.wildcard_match(&name("ftp.example.com"))),
+            Some(name("*.example.com"))
+        );
+        assert_eq!(
+            found(trie.wildcard_match(&name("x.www.example.com"))),
+            Some(name("*.example.com"))
+        );
+        assert_eq!(
+            found(trie.wildcard_match(&name("x.y.a.example.com"))),
+            Some(name("*.a.example.com"))
+        );
+        assert_eq!(
+            found(trie.wildcard_match(&name("a.example.com"))),
+            Some(name("*.example.com"))
+        );
+        assert_eq!(found(trie.wildcard_match(&name("example.com"))), None);
+        assert_eq!(found(trie.wildcard_match(&name("com"))), None);
+    }
+
+    #[test]
+    fn iter() {
+        let names = [
+            "example",
+            "a.example",
+            "yljkjljk.a.example",
+            "Z.a.example",
+            "zABC.a.EXAMPLE",
+            "z.example",
+            "\\001.z.example",
+            "*.z.example",
+            "\\200.z.example",
+        ];
+        let mut trie = trie(&names);
+        trie.insert(name("."), 100);
+        let iter = trie.iter();
+        assert_eq!(iter.len(), names.len() + 1);
+        let res: Vec<_> = iter.map(|(_, idx)| *idx).collect();
+        // Names in canonical order from RFC 4034, section 6.1.
+        assert_eq!(res, [100, 0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+}
@@ -101,5 +101,19 @@ macro_rules! opt_types {
                 }
             }
         }
+
+        //--- Display
+
+        impl<Octs, Name> fmt::Display for AllOptData<Octs, Name>
+        where Octs: AsRef<[u8]>, Name: fmt::Display {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match *self {
+                    $( $(
+                        AllOptData::$opt(ref inner) => inner.fmt(f),
+                    )* )*
+                    AllOptData::Other(ref inner) => inner.fmt(f),
+                }
+            }
+        }
     }
 }
@@ -248,6 +248,40 @@ impl<Octs: AsRef<[u8]> + ?Sized> ComposeRecordData for Opt<Octs> {
     }
 }
 
+//--- Serialize and Deserialize
+
+#[cfg(feature = "serde")]
+impl<Octs> serde::Serialize for Opt<Octs>
+where
+    Octs: AsRef<[u8]> + octseq::serde::SerializeOctets,
+{
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::utils::base16::serde::serialize(&self.octets, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Octs> serde::Deserialize<'de> for Opt<Octs>
+where
+    Octs: AsRef<[u8]>
+        + octseq::builder::FromBuilder
+        + octseq::serde::DeserializeOctets<'de>,
+    <Octs as octseq::builder::FromBuilder>::Builder:
+        octseq::builder::EmptyBuilder,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Opt::from_octets(crate::utils::base16::serde::deserialize(
+            deserializer,
+        )?)
+        .map_err(serde::de::Error::custom)
+    }
+}
+
 //--- Display
 
 impl<Octs: AsRef<[u8]> + ?Sized> fmt::Display for Opt<Octs> {
@@ -812,6 +846,17 @@ impl<Octs: AsRef<[u8]>> ComposeOptData for UnknownOptData<Octs> {
     }
 }
 
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for UnknownOptData<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ch in self.data.as_ref() {
+            write!(f, "{:02x}", *ch)?
+        }
+        Ok(())
+    }
+}
+
 //============ Tests =========================================================
 
 #[cfg(test)]
@@ -861,7 +906,9 @@ pub(super) mod test {
     fn opt_iter() {
         // Push two options and check that both are parseable
         let nsid = opt::Nsid::from_octets(&b"example"[..]);
-        let cookie = opt::Cookie::new(1234u64.to_be_bytes());
+        let cookie = opt::Cookie::from(opt::rfc7873::ClientCookie::new(
+            1234u64.to_be_bytes(),
+        ));
         let msg = {
             let mut mb = MessageBuilder::new_vec().additional();
             mb.opt(|mb| {
@@ -879,6 +926,26 @@ pub(super) mod test {
         assert_eq!(Some(Ok(cookie)), opt.iter::<opt::Cookie>().next());
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn opt_ser_de() {
+        use serde_test::{
+            assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+        };
+
+        let opt = Opt::from_octets(Vec::from(b"\x00\x0a\x00\x00".as_ref()))
+            .unwrap();
+        assert_tokens(
+            &opt.clone().compact(),
+            &[Token::ByteBuf(b"\x00\x0a\x00\x00")],
+        );
+        assert_tokens(&opt.readable(), &[Token::Str("000A0000")]);
+        assert_de_tokens_error::<Readable<Opt<Vec<u8>>>>(
+            &[Token::Str("000A00")],
+            "unexpected end of input",
+        );
+    }
+
     pub fn test_option_compose_parse<In, F, Out>(data: &In, parse: F)
     where
         In: ComposeOptData + PartialEq<Out> + Debug,
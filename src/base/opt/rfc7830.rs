@@ -1,14 +1,13 @@
 //! EDNS Options from RFC 7830
 
-use core::fmt;
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
 use super::super::wire::{Compose, Composer, ParseError};
-use super::{OptData, ComposeOptData, ParseOptData};
+use super::{ComposeOptData, OptData, ParseOptData};
+use core::fmt;
 use octseq::builder::OctetsBuilder;
 use octseq::parse::Parser;
 
-
 //------------ PaddingMode ---------------------------------------------------
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -18,15 +17,12 @@ pub enum PaddingMode {
     Random,
 }
 
-
 //------------ Padding -------------------------------------------------------
 
-
-
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Padding {
     len: u16,
-    mode: PaddingMode
+    mode: PaddingMode,
 }
 
 impl Padding {
@@ -51,7 +47,7 @@ impl Padding {
     }
 
     pub fn parse<Octs: AsRef<[u8]>>(
-        parser: &mut Parser<Octs>
+        parser: &mut Parser<Octs>,
     ) -> Result<Self, ParseError> {
         // XXX Check whether there really are all zeros.
         let len = parser.remaining();
@@ -75,8 +71,7 @@ impl<'a, Octs: AsRef<[u8]>> ParseOptData<'a, Octs> for Padding {
     ) -> Result<Option<Self>, ParseError> {
         if code == OptionCode::Padding {
             Self::parse(parser).map(Some)
-        }
-        else {
+        } else {
             Ok(None)
         }
     }
@@ -88,7 +83,8 @@ impl ComposeOptData for Padding {
     }
 
     fn compose_option<Target: OctetsBuilder + ?Sized>(
-        &self, target: &mut Target
+        &self,
+        target: &mut Target,
     ) -> Result<(), Target::AppendError> {
         match self.mode {
             PaddingMode::Zero => {
@@ -121,6 +117,83 @@ impl fmt::Display for Padding {
     }
 }
 
+//------------ PaddingPolicy -------------------------------------------------
+
+/// A strategy for deciding how much padding to add to a message.
+///
+/// The policies are described in [RFC 8467]. The recommended policy is
+/// block-length padding which pads a message to the next multiple of a
+/// fixed block length. The recommended block lengths are available via
+/// the [`query`][Self::query] and [`response`][Self::response] functions.
+///
+/// The padding is added via [`AdditionalBuilder::opt_padded`] which
+/// determines the amount of padding only after all other options of the
+/// OPT record have been added.
+///
+/// [RFC 8467]: https://tools.ietf.org/html/rfc8467
+/// [`AdditionalBuilder::opt_padded`]: crate::base::message_builder::AdditionalBuilder::opt_padded
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PaddingPolicy {
+    /// Pad the message to a multiple of the given block length.
+    BlockLength(u16),
+
+    /// Pad the message to its maximum size.
+    ///
+    /// The maximum size is the push limit of the message builder. Since
+    /// this is unlimited by default, make sure to set it.
+    Maximal,
+
+    /// Pad by a random number of octets up to and including the given value.
+    #[cfg(feature = "random")]
+    Random(u16),
+}
+
+impl PaddingPolicy {
+    /// The block length recommended for queries.
+    pub const QUERY_BLOCK_LEN: u16 = 128;
+
+    /// The block length recommended for responses.
+    pub const RESPONSE_BLOCK_LEN: u16 = 468;
+
+    /// Returns the recommended policy for queries.
+    pub fn query() -> Self {
+        PaddingPolicy::BlockLength(Self::QUERY_BLOCK_LEN)
+    }
+
+    /// Returns the recommended policy for responses.
+    pub fn response() -> Self {
+        PaddingPolicy::BlockLength(Self::RESPONSE_BLOCK_LEN)
+    }
+
+    /// Returns the length of the padding option data to add.
+    ///
+    /// The `len` argument is the length of the message without the padding
+    /// data, i.e., including the padding option’s header and anything that
+    /// will be added after it. The `limit` is the maximum size of the
+    /// message. The padding will never make the message exceed this limit.
+    pub fn pad_len(self, len: usize, limit: usize) -> u16 {
+        let limit = limit.min(usize::from(u16::MAX));
+        let avail = limit.saturating_sub(len);
+        let pad = match self {
+            PaddingPolicy::BlockLength(block) => {
+                let block = usize::from(block);
+                if block == 0 {
+                    0
+                } else {
+                    (block - len % block) % block
+                }
+            }
+            PaddingPolicy::Maximal => avail,
+            #[cfg(feature = "random")]
+            PaddingPolicy::Random(max) => {
+                usize::from(::rand::random::<u16>()) % (usize::from(max) + 1)
+            }
+        };
+        // Both values are bounded by u16::MAX thanks to `limit`.
+        pad.min(avail) as u16
+    }
+}
+
 //------------ OptBuilder ----------------------------------------------------
 
 impl<'a, Target: Composer> OptBuilder<'a, Target> {
@@ -129,9 +202,10 @@ impl<'a, Target: Composer> OptBuilder<'a, Target> {
     }
 
     pub fn padding_with_mode(
-        &mut self, len: u16, mode: PaddingMode
+        &mut self,
+        len: u16,
+        mode: PaddingMode,
     ) -> Result<(), Target::AppendError> {
         self.push(&Padding::with_mode(len, mode))
     }
 }
-
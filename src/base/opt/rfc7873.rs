@@ -1,9 +1,30 @@
 //! EDNS Options form RFC 7873
+//!
+//! DNS cookies provide a lightweight mechanism for clients and servers to
+//! recognize each other. The client includes a random client cookie with
+//! each query. The server answers with the client cookie and a server
+//! cookie derived from the client cookie, the client’s IP address, and a
+//! secret only known to the server. The client includes this server cookie
+//! in later queries allowing the server to verify that the query really
+//! comes from the client at that address.
+//!
+//! The option itself is defined in [RFC 7873]. [RFC 9018] defines an
+//! interoperable format for server cookies based on the SipHash-2-4 hash
+//! function that allows multiple servers of an anycast group to share a
+//! secret. If the `siphasher` feature is enabled, [`ServerCookies`]
+//! implements creating and checking such server cookies.
+//!
+//! [RFC 7873]: https://tools.ietf.org/html/rfc7873
+//! [RFC 9018]: https://tools.ietf.org/html/rfc9018
 
-use core::fmt;
+use core::{fmt, hash};
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
-use super::super::wire::{Composer, ParseError};
+#[cfg(feature = "siphasher")]
+use super::super::net::IpAddr;
+#[cfg(feature = "siphasher")]
+use super::super::serial::Serial;
+use super::super::wire::{Composer, FormError, ParseError};
 use super::{OptData, ComposeOptData, ParseOptData};
 use octseq::builder::OctetsBuilder;
 use octseq::parse::Parser;
@@ -11,27 +32,62 @@ use octseq::parse::Parser;
 
 //------------ Cookie --------------------------------------------------------
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Cookie([u8; 8]);
+/// The data of the cookie option.
+///
+/// The option consists of a mandatory client cookie and an optional server
+/// cookie. Queries sent to a server for the first time only contain the
+/// client cookie, every other query and all responses contain both.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cookie {
+    /// The client cookie.
+    client: ClientCookie,
+
+    /// The optional server cookie.
+    server: Option<ServerCookie>,
+}
 
 impl Cookie {
-    pub fn new(cookie: [u8; 8]) -> Self {
-        Cookie(cookie)
+    /// Creates new cookie option data from its components.
+    pub fn new(client: ClientCookie, server: Option<ServerCookie>) -> Self {
+        Cookie { client, server }
     }
 
-    pub fn cookie(self) -> [u8; 8] {
-        self.0
+    /// Returns the client cookie.
+    pub fn client(&self) -> ClientCookie {
+        self.client
+    }
+
+    /// Returns a reference to the server cookie if present.
+    pub fn server(&self) -> Option<&ServerCookie> {
+        self.server.as_ref()
+    }
+
+    /// Returns a new cookie with the same client cookie and a server cookie.
+    pub fn with_server(self, server: ServerCookie) -> Self {
+        Cookie::new(self.client, Some(server))
     }
 
     pub fn parse<Octs: AsRef<[u8]>>(
         parser: &mut Parser<Octs>
     ) -> Result<Self, ParseError> {
-        let mut res = [0u8; 8];
-        parser.parse_buf(&mut res[..])?;
-        Ok(Self::new(res))
+        let client = ClientCookie::parse(parser)?;
+        let server = if parser.remaining() > 0 {
+            Some(ServerCookie::parse(parser)?)
+        }
+        else {
+            None
+        };
+        Ok(Cookie::new(client, server))
     }
 }
 
+//--- From
+
+impl From<ClientCookie> for Cookie {
+    fn from(client: ClientCookie) -> Self {
+        Cookie::new(client, None)
+    }
+}
 
 //--- OptData
 
@@ -57,32 +113,614 @@ impl<'a, Octs: AsRef<[u8]>> ParseOptData<'a, Octs> for Cookie {
 
 impl ComposeOptData for Cookie {
     fn compose_len(&self) -> u16 {
-        8
+        match self.server {
+            Some(ref server) => 8 + server.len() as u16,
+            None => 8
+        }
     }
 
     fn compose_option<Target: OctetsBuilder + ?Sized>(
         &self, target: &mut Target
     ) -> Result<(), Target::AppendError> {
-        target.append_slice(&self.0[..])
+        target.append_slice(&self.client.0)?;
+        if let Some(ref server) = self.server {
+            target.append_slice(server.as_slice())?;
+        }
+        Ok(())
     }
 }
 
 impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.client.fmt(f)?;
+        if let Some(ref server) = self.server {
+            server.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+
+//------------ ClientCookie --------------------------------------------------
+
+/// A client cookie.
+///
+/// The client cookie is an eight octet value chosen by the client. It should
+/// be chosen randomly and be different for each server the client talks to.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ClientCookie([u8; 8]);
+
+impl ClientCookie {
+    /// Creates a client cookie from its octets.
+    pub fn new(cookie: [u8; 8]) -> Self {
+        ClientCookie(cookie)
+    }
+
+    /// Creates a new random client cookie.
+    #[cfg(feature = "random")]
+    pub fn new_random() -> Self {
+        ClientCookie(::rand::random())
+    }
+
+    /// Returns the octets of the client cookie.
+    pub fn into_octets(self) -> [u8; 8] {
+        self.0
+    }
+
+    pub fn parse<Octs: AsRef<[u8]>>(
+        parser: &mut Parser<Octs>
+    ) -> Result<Self, ParseError> {
+        let mut res = [0u8; 8];
+        parser.parse_buf(&mut res[..])?;
+        Ok(Self::new(res))
+    }
+}
+
+//--- From
+
+impl From<[u8; 8]> for ClientCookie {
+    fn from(cookie: [u8; 8]) -> Self {
+        ClientCookie(cookie)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for ClientCookie {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for c in self.0 {
-            write!(f, "{:X}", c)?
+            write!(f, "{:02x}", c)?
+        }
+        Ok(())
+    }
+}
+
+
+//------------ ServerCookie --------------------------------------------------
+
+/// A server cookie.
+///
+/// A server cookie is between 8 and 32 octets long. Its content is up to the
+/// server. [RFC 9018] defines an interoperable format which is implemented
+/// by [`ServerCookies`] if the `siphasher` feature is enabled.
+///
+/// [RFC 9018]: https://tools.ietf.org/html/rfc9018
+#[derive(Clone, Copy)]
+pub struct ServerCookie {
+    /// The buffer with the cookie at its beginning.
+    octets: [u8; 32],
+
+    /// The length of the cookie.
+    len: u8,
+}
+
+impl ServerCookie {
+    /// Creates a server cookie from an octets slice.
+    ///
+    /// Returns an error if the slice is not between 8 and 32 octets long.
+    pub fn from_slice(slice: &[u8]) -> Result<Self, ParseError> {
+        if !(8..=32).contains(&slice.len()) {
+            return Err(FormError::new("invalid server cookie length").into())
+        }
+        let mut octets = [0u8; 32];
+        octets[..slice.len()].copy_from_slice(slice);
+        Ok(ServerCookie { octets, len: slice.len() as u8 })
+    }
+
+    /// Returns an octets slice with the server cookie.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.octets[..usize::from(self.len)]
+    }
+
+    /// Returns the length of the server cookie in octets.
+    pub fn len(&self) -> usize {
+        usize::from(self.len)
+    }
+
+    /// Returns whether the server cookie is empty.
+    ///
+    /// This is always `false` since a server cookie has at least eight
+    /// octets.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn parse<Octs: AsRef<[u8]>>(
+        parser: &mut Parser<Octs>
+    ) -> Result<Self, ParseError> {
+        let len = parser.remaining();
+        if !(8..=32).contains(&len) {
+            return Err(FormError::new("invalid server cookie length").into())
+        }
+        let mut octets = [0u8; 32];
+        parser.parse_buf(&mut octets[..len])?;
+        Ok(ServerCookie { octets, len: len as u8 })
+    }
+}
+
+//--- PartialEq, Eq, and Hash
+
+impl PartialEq for ServerCookie {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for ServerCookie { }
+
+impl hash::Hash for ServerCookie {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+//--- Display and Debug
+
+impl fmt::Display for ServerCookie {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.as_slice() {
+            write!(f, "{:02x}", c)?
         }
         Ok(())
     }
 }
 
+impl fmt::Debug for ServerCookie {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ServerCookie")
+            .field(&format_args!("{}", self))
+            .finish()
+    }
+}
+
+
+//------------ ServerCookies -------------------------------------------------
+
+/// Creating and checking server cookies.
+///
+/// A value of this type keeps the secret used to create server cookies in
+/// the interoperable format defined in [RFC 9018] and the policy for
+/// checking the server cookies in received queries.
+///
+/// Such a server cookie is sixteen octets long and consists of a version
+/// octet (currently always 1), three reserved octets, a four octet
+/// timestamp of when the cookie was created, and an eight octet SipHash-2-4
+/// hash over the client cookie, the first eight octets of the server
+/// cookie, and the client’s IP address.
+///
+/// The secret can be rolled over via [`rotate`][Self::rotate]. The
+/// previous secret will still be accepted when checking cookies until the
+/// next rollover, but new cookies are always created with the current
+/// secret.
+///
+/// A server cookie is accepted if its timestamp is not older than the
+/// validity period (one hour by default) and not more than five minutes in
+/// the future. A valid cookie is renewed if it is older than the refresh
+/// period (half an hour by default).
+///
+/// The [`check`][Self::check] method inspects the cookie of a query and
+/// returns a [`CookieVerdict`] with what to do about the query. If the
+/// verdict provides a cookie, it should be added to the response via
+/// [`OptBuilder::push`].
+///
+/// [RFC 9018]: https://tools.ietf.org/html/rfc9018
+#[cfg(feature = "siphasher")]
+#[derive(Clone, Debug)]
+pub struct ServerCookies {
+    /// The current secret.
+    secret: [u8; 16],
+
+    /// The previous secret which is still accepted.
+    previous: Option<[u8; 16]>,
+
+    /// The number of seconds after which a cookie is renewed.
+    refresh: u32,
+
+    /// The number of seconds a cookie is valid.
+    validity: u32,
+
+    /// Whether queries over UDP require a valid server cookie.
+    required: bool,
+}
+
+#[cfg(feature = "siphasher")]
+impl ServerCookies {
+    /// The version of the server cookie format.
+    const VERSION: u8 = 1;
+
+    /// The number of seconds a timestamp may be in the future.
+    const MAX_FUTURE: u32 = 300;
+
+    /// The largest number of seconds after which a cookie is renewed.
+    const MAX_REFRESH: u32 = 3600;
+
+    /// The largest number of seconds a cookie is valid.
+    const MAX_VALIDITY: u32 = 86400;
+
+    /// Creates a new value using the given secret.
+    pub fn new(secret: [u8; 16]) -> Self {
+        ServerCookies {
+            secret,
+            previous: None,
+            refresh: 1800,
+            validity: 3600,
+            required: false,
+        }
+    }
+
+    /// Creates a new value with a random secret.
+    #[cfg(feature = "random")]
+    pub fn new_random() -> Self {
+        Self::new(::rand::random())
+    }
+
+    /// Replaces the secret with a new one.
+    ///
+    /// The current secret is kept as the previous secret and will still be
+    /// accepted. Any earlier secret is dropped.
+    pub fn rotate(&mut self, secret: [u8; 16]) {
+        self.previous = Some(self.secret);
+        self.secret = secret;
+    }
+
+    /// Sets the number of seconds after which a cookie is renewed.
+    ///
+    /// Following RFC 9018, cookies are renewed at least once an hour, so
+    /// larger values are reduced to one hour.
+    pub fn set_refresh(&mut self, secs: u32) {
+        self.refresh = secs.min(Self::MAX_REFRESH)
+    }
+
+    /// Sets the number of seconds a cookie is accepted.
+    ///
+    /// Values larger than one day are reduced to one day.
+    pub fn set_validity(&mut self, secs: u32) {
+        self.validity = secs.min(Self::MAX_VALIDITY)
+    }
+
+    /// Sets whether queries over UDP need a valid server cookie.
+    ///
+    /// If this is `false`, which is the default, queries without a valid
+    /// server cookie will be answered normally, albeit with a new server
+    /// cookie. Otherwise they will receive a BADCOOKIE or truncated
+    /// response. Queries over TCP are always answered.
+    pub fn set_required(&mut self, required: bool) {
+        self.required = required
+    }
+
+    /// Creates a new server cookie.
+    pub fn create(
+        &self, client: ClientCookie, client_ip: IpAddr, now: Serial
+    ) -> ServerCookie {
+        let mut octets = [0u8; 16];
+        octets[0] = Self::VERSION;
+        octets[4..8].copy_from_slice(&now.into_int().to_be_bytes());
+        let hash = Self::hash(&self.secret, client, &octets, client_ip);
+        octets[8..].copy_from_slice(&hash);
+        // A sixteen octet slice is always a valid server cookie.
+        ServerCookie::from_slice(&octets).unwrap()
+    }
+
+    /// Checks whether a cookie contains a valid server cookie.
+    ///
+    /// Returns the timestamp of the server cookie if it is valid or `None`
+    /// otherwise.
+    pub fn verify(
+        &self, cookie: &Cookie, client_ip: IpAddr, now: Serial
+    ) -> Option<Serial> {
+        let server = cookie.server()?.as_slice();
+        if server.len() != 16 || server[0] != Self::VERSION {
+            return None
+        }
+        let mut timestamp = [0u8; 4];
+        timestamp.copy_from_slice(&server[4..8]);
+        let timestamp = Serial(u32::from_be_bytes(timestamp));
+        if !(now <= timestamp.add(self.validity)
+            && timestamp <= now.add(Self::MAX_FUTURE))
+        {
+            return None
+        }
+        let matches = |secret| {
+            Self::hash(secret, cookie.client(), server, client_ip)
+                == server[8..]
+        };
+        if matches(&self.secret)
+            || self.previous.as_ref().map(matches).unwrap_or(false)
+        {
+            Some(timestamp)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Decides how to respond to a query with the given cookie.
+    ///
+    /// The `cookie` argument contains the cookie option of the query if
+    /// there is one. The `client_ip` is the address the query was received
+    /// from and `udp` indicates whether it was received over UDP.
+    pub fn check(
+        &self,
+        cookie: Option<&Cookie>,
+        client_ip: IpAddr,
+        udp: bool,
+        now: Serial,
+    ) -> CookieVerdict {
+        let enforce = self.required && udp;
+        let cookie = match cookie {
+            Some(cookie) => cookie,
+            None => {
+                if enforce {
+                    return CookieVerdict::Truncate
+                }
+                return CookieVerdict::NoCookie
+            }
+        };
+        match self.verify(cookie, client_ip, now) {
+            Some(timestamp) => {
+                if now <= timestamp.add(self.refresh) {
+                    CookieVerdict::Answer(*cookie)
+                }
+                else {
+                    CookieVerdict::Answer(self.renew(cookie, client_ip, now))
+                }
+            }
+            None => {
+                let cookie = self.renew(cookie, client_ip, now);
+                if enforce {
+                    CookieVerdict::BadCookie(cookie)
+                }
+                else {
+                    CookieVerdict::Answer(cookie)
+                }
+            }
+        }
+    }
+
+    /// Returns the cookie with a freshly created server cookie.
+    fn renew(
+        &self, cookie: &Cookie, client_ip: IpAddr, now: Serial
+    ) -> Cookie {
+        cookie.with_server(self.create(cookie.client(), client_ip, now))
+    }
+
+    /// Calculates the hash part of a server cookie.
+    ///
+    /// The `server` slice needs to contain at least the first eight octets
+    /// of the server cookie.
+    fn hash(
+        secret: &[u8; 16],
+        client: ClientCookie,
+        server: &[u8],
+        client_ip: IpAddr,
+    ) -> [u8; 8] {
+        use core::hash::Hasher;
+
+        let mut hasher = siphasher::sip::SipHasher24::new_with_key(secret);
+        hasher.write(&client.0);
+        hasher.write(&server[..8]);
+        match client_ip {
+            IpAddr::V4(addr) => hasher.write(&addr.octets()),
+            IpAddr::V6(addr) => hasher.write(&addr.octets()),
+        }
+        hasher.finish().to_le_bytes()
+    }
+}
+
+
+//------------ CookieVerdict -------------------------------------------------
+
+/// What to do with a query based on its cookie.
+///
+/// This type is returned by [`ServerCookies::check`].
+#[cfg(feature = "siphasher")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CookieVerdict {
+    /// The query didn’t contain a cookie.
+    ///
+    /// The query should be answered normally without a cookie option.
+    NoCookie,
+
+    /// The query should be answered normally.
+    ///
+    /// The response should contain the cookie option given.
+    Answer(Cookie),
+
+    /// The query should be answered with a BADCOOKIE response.
+    ///
+    /// The response should only contain the question and an OPT record
+    /// with the extended response code BADCOOKIE and the cookie option
+    /// given.
+    BadCookie(Cookie),
+
+    /// The query should be answered with a truncated response.
+    ///
+    /// The response should be empty and have the TC bit set so that the
+    /// client retries over TCP.
+    Truncate,
+}
+
+
 //------------ OptBuilder ----------------------------------------------------
 
 impl<'a, Target: Composer> OptBuilder<'a, Target> {
     pub fn cookie(
-        &mut self, cookie: [u8; 8]
+        &mut self, cookie: Cookie
+    ) -> Result<(), Target::AppendError> {
+        self.push(&cookie)
+    }
+
+    pub fn client_cookie(
+        &mut self, client: ClientCookie
     ) -> Result<(), Target::AppendError> {
-        self.push(&Cookie::new(cookie))
+        self.push(&Cookie::from(client))
     }
 }
 
+
+//============ Testing ======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use super::super::test::test_option_compose_parse;
+
+    #[test]
+    fn cookie_compose_parse() {
+        let client = ClientCookie::new(*b"\x24\x64\xc4\xab\xcf\x10\xc9\x57");
+        test_option_compose_parse(
+            &Cookie::from(client),
+            |parser| Cookie::parse(parser)
+        );
+        test_option_compose_parse(
+            &Cookie::new(
+                client,
+                Some(ServerCookie::from_slice(b"0123456789abcdef").unwrap())
+            ),
+            |parser| Cookie::parse(parser)
+        );
+        assert!(ServerCookie::from_slice(b"0123456").is_err());
+        assert!(ServerCookie::from_slice(&[0u8; 33]).is_err());
+    }
+
+    #[cfg(feature = "siphasher")]
+    #[test]
+    fn rfc9018_test_vectors() {
+        use std::str::FromStr;
+
+        // Appendix A.1 of RFC 9018.
+        let client = ClientCookie::new(*b"\x24\x64\xc4\xab\xcf\x10\xc9\x57");
+        let client_ip = IpAddr::from_str("198.51.100.100").unwrap();
+        let secret = *b"\xe5\xe9\x73\xe5\xa6\xb2\xa4\x3f\
+                        \x48\xe7\xdc\x84\x9e\x37\xbf\xcf";
+        let now = Serial(1559731985);
+        let cookies = ServerCookies::new(secret);
+        let server = cookies.create(client, client_ip, now);
+        assert_eq!(
+            server.as_slice(),
+            b"\x01\x00\x00\x00\x5c\xf7\x9f\x11\
+              \x1f\x81\x30\xc3\xee\xe2\x94\x80"
+        );
+
+        let cookie = Cookie::new(client, Some(server));
+        assert_eq!(cookies.verify(&cookie, client_ip, now), Some(now));
+        assert_eq!(
+            cookies.verify(&cookie, client_ip, now.add(3601)), None
+        );
+        assert_eq!(
+            cookies.verify(
+                &cookie, IpAddr::from_str("198.51.100.101").unwrap(), now
+            ),
+            None
+        );
+    }
+
+    #[cfg(feature = "siphasher")]
+    #[test]
+    fn server_cookies_check() {
+        use std::str::FromStr;
+
+        let client = ClientCookie::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        let client_ip = IpAddr::from_str("2001:db8::1").unwrap();
+        let now = Serial(1_000_000);
+        let mut cookies = ServerCookies::new([1; 16]);
+
+        // No cookie at all.
+        assert_eq!(
+            cookies.check(None, client_ip, true, now),
+            CookieVerdict::NoCookie
+        );
+
+        // Only a client cookie gets a fresh server cookie.
+        let fresh = Cookie::new(
+            client, Some(cookies.create(client, client_ip, now))
+        );
+        assert_eq!(
+            cookies.check(Some(&client.into()), client_ip, true, now),
+            CookieVerdict::Answer(fresh)
+        );
+
+        // A valid cookie is echoed until it needs refreshing.
+        assert_eq!(
+            cookies.check(Some(&fresh), client_ip, true, now.add(1000)),
+            CookieVerdict::Answer(fresh)
+        );
+        assert_ne!(
+            cookies.check(Some(&fresh), client_ip, true, now.add(2000)),
+            CookieVerdict::Answer(fresh)
+        );
+
+        // After a rollover, the old secret is still accepted but not the
+        // one before it.
+        cookies.rotate([2; 16]);
+        assert!(cookies.verify(&fresh, client_ip, now).is_some());
+        cookies.rotate([3; 16]);
+        assert!(cookies.verify(&fresh, client_ip, now).is_none());
+
+        // Enforcing cookies over UDP.
+        cookies.set_required(true);
+        assert_eq!(
+            cookies.check(None, client_ip, true, now),
+            CookieVerdict::Truncate
+        );
+        assert_eq!(
+            cookies.check(None, client_ip, false, now),
+            CookieVerdict::NoCookie
+        );
+        assert!(matches!(
+            cookies.check(Some(&fresh), client_ip, true, now),
+            CookieVerdict::BadCookie(_)
+        ));
+        assert!(matches!(
+            cookies.check(Some(&fresh), client_ip, false, now),
+            CookieVerdict::Answer(_)
+        ));
+    }
+
+    #[cfg(feature = "siphasher")]
+    #[test]
+    fn server_cookies_limits() {
+        use std::str::FromStr;
+
+        let client = ClientCookie::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        let client_ip = IpAddr::from_str("192.0.2.1").unwrap();
+        let now = Serial(1_000_000);
+        let mut cookies = ServerCookies::new([1; 16]);
+        cookies.set_refresh(u32::MAX);
+        cookies.set_validity(u32::MAX);
+        assert_eq!(cookies.refresh, 3600);
+        assert_eq!(cookies.validity, 86400);
+
+        // Checking a cookie must not overflow the serial arithmetic.
+        let fresh = Cookie::new(
+            client, Some(cookies.create(client, client_ip, now))
+        );
+        assert_eq!(
+            cookies.check(Some(&fresh), client_ip, true, now.add(3000)),
+            CookieVerdict::Answer(fresh)
+        );
+        assert!(cookies.verify(&fresh, client_ip, now.add(80000)).is_some());
+        assert!(cookies.verify(&fresh, client_ip, now.add(90000)).is_none());
+    }
+}
@@ -160,13 +160,14 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> IntoIterator for &'a KeyTag<Octs> {
 impl<Octets: AsRef<[u8]> + ?Sized> fmt::Display  for KeyTag<Octets> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut first = true;
-        
-        for v in self.octets.as_ref() {
+
+        for v in self.octets.as_ref().chunks_exact(2) {
+            let tag = u16::from_be_bytes([v[0], v[1]]);
             if first {
-                write!(f, "{:X}", ((*v as u16) << 8) | *v as u16)?;
+                write!(f, "{}", tag)?;
                 first = false;
             } else {
-                write!(f, ", {:X}", ((*v as u16) << 8) | *v as u16)?;
+                write!(f, ", {}", tag)?;
             }
         }
 
@@ -0,0 +1,529 @@
+//! Resource record sets.
+//!
+//! This module provides the type [`Rrset`] for an owned set of resource
+//! records sharing the same owner name, class, and record type. Such a set
+//! is the smallest unit of data DNS deals with: records are always added,
+//! removed, signed, and cached as complete sets.
+//!
+//! An [`Rrset`] upholds the rules [RFC 2181] imposes on such sets: all
+//! records have the same TTL (section 5.2), there are no duplicate records
+//! (section 5), and the records are kept in canonical order as defined in
+//! [RFC 4034], section 6.3, so they can readily be signed or validated.
+//!
+//! [RFC 2181]: https://tools.ietf.org/html/rfc2181
+//! [RFC 4034]: https://tools.ietf.org/html/rfc4034
+
+use super::cmp::CanonicalOrd;
+use super::iana::{Class, Rtype};
+use super::message::RecordSection;
+use super::name::{ParsedDname, ToDname};
+use super::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use super::record::Record;
+use super::wire::{Composer, ParseError};
+use core::{cmp, fmt, slice};
+use octseq::octets::Octets;
+use std::vec::Vec;
+
+//------------ Rrset ---------------------------------------------------------
+
+/// An owned set of records with the same owner name, class, and type.
+///
+/// The owner name, class, record type, and TTL are kept only once for the
+/// whole set. Only the record data is stored for each member. The record
+/// data is kept in canonical order and duplicates are dropped when adding
+/// new data.
+///
+/// If records with differing TTLs are added, the TTL of the set is reduced
+/// to the smallest of them. This follows the advice of section 5.2 of
+/// [RFC 2181] to treat such sets as if all records had the lowest TTL.
+///
+/// Records can be added via [`push`][Self::push] or
+/// [`push_data`][Self::push_data]. Adding a record that doesn’t belong to
+/// the set results in an error.
+///
+/// A set can be collected from a section of a parsed message via
+/// [`from_section`][Self::from_section]. It can be added to a message via
+/// the `push_rrset` method of the section builders using the iterator
+/// returned by [`records`][Self::records].
+///
+/// [RFC 2181]: https://tools.ietf.org/html/rfc2181
+#[derive(Clone)]
+pub struct Rrset<N, D> {
+    /// The owner name of the set.
+    owner: N,
+
+    /// The class of the set.
+    class: Class,
+
+    /// The record type of the set.
+    rtype: Rtype,
+
+    /// The TTL of all records of the set.
+    ttl: u32,
+
+    /// The record data of the set in canonical order.
+    data: Vec<D>,
+}
+
+/// # Creation and Conversion
+///
+impl<N, D> Rrset<N, D> {
+    /// Creates a new, empty record set.
+    pub fn new(owner: N, class: Class, rtype: Rtype, ttl: u32) -> Self {
+        Rrset {
+            owner,
+            class,
+            rtype,
+            ttl,
+            data: Vec::new(),
+        }
+    }
+
+    /// Creates a new record set from a single record.
+    pub fn from_record(record: Record<N, D>) -> Self
+    where
+        D: RecordData,
+    {
+        let rtype = record.rtype();
+        let class = record.class();
+        let ttl = record.ttl();
+        let (owner, data) = record.into_owner_and_data();
+        Rrset {
+            owner,
+            class,
+            rtype,
+            ttl,
+            data: vec![data],
+        }
+    }
+
+    /// Converts the set into its owner name and the record data.
+    pub fn into_owner_and_data(self) -> (N, Vec<D>) {
+        (self.owner, self.data)
+    }
+}
+
+/// # Access to the Set’s Properties
+///
+impl<N, D> Rrset<N, D> {
+    /// Returns a reference to the owner name of the set.
+    pub fn owner(&self) -> &N {
+        &self.owner
+    }
+
+    /// Returns the class of the set.
+    pub fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Returns the record type of the set.
+    pub fn rtype(&self) -> Rtype {
+        self.rtype
+    }
+
+    /// Returns the TTL of the set.
+    pub fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    /// Sets the TTL of the set.
+    ///
+    /// This changes the TTL of all records of the set.
+    pub fn set_ttl(&mut self, ttl: u32) {
+        self.ttl = ttl
+    }
+
+    /// Returns the number of records in the set.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the record data of the set in canonical order.
+    pub fn data(&self) -> &[D] {
+        self.data.as_ref()
+    }
+
+    /// Returns an iterator over the record data of the set.
+    pub fn iter(&self) -> slice::Iter<D> {
+        self.data.iter()
+    }
+
+    /// Returns an iterator over the records of the set.
+    ///
+    /// The records returned by the iterator reference the owner name and
+    /// data of the set. They can be composed or pushed to a message
+    /// builder.
+    pub fn records(&self) -> RrsetRecords<N, D> {
+        RrsetRecords {
+            rrset: self,
+            data: self.data.iter(),
+        }
+    }
+}
+
+/// # Adding Records
+///
+impl<N: ToDname, D: RecordData + CanonicalOrd> Rrset<N, D> {
+    /// Adds a record to the set.
+    ///
+    /// The record needs to have the same owner name, class, and record
+    /// type as the set. Otherwise an error is returned.
+    ///
+    /// If the record has a smaller TTL than the set, the TTL of the set is
+    /// lowered. Returns whether the record was added, i.e., `Ok(false)` if
+    /// the set already contained the record.
+    pub fn push<NN: ToDname>(
+        &mut self,
+        record: Record<NN, D>,
+    ) -> Result<bool, RrsetError> {
+        if !record.owner().name_eq(&self.owner) {
+            return Err(RrsetError::OwnerMismatch);
+        }
+        if record.class() != self.class {
+            return Err(RrsetError::ClassMismatch);
+        }
+        let ttl = record.ttl();
+        let res = self.push_data(record.into_data())?;
+        self.ttl = cmp::min(self.ttl, ttl);
+        Ok(res)
+    }
+
+    /// Adds record data to the set.
+    ///
+    /// The data needs to be of the record type of the set. Otherwise an
+    /// error is returned. Returns whether the data was added, i.e.,
+    /// `Ok(false)` if the set already contained the data.
+    pub fn push_data(&mut self, data: D) -> Result<bool, RrsetError> {
+        if data.rtype() != self.rtype {
+            return Err(RrsetError::RtypeMismatch);
+        }
+        Ok(self.insert(data))
+    }
+}
+
+impl<N, D: CanonicalOrd> Rrset<N, D> {
+    /// Inserts data at its canonical position unless it is a duplicate.
+    fn insert(&mut self, data: D) -> bool {
+        match self.data.binary_search_by(|item| item.canonical_cmp(&data)) {
+            Ok(_) => false,
+            Err(pos) => {
+                self.data.insert(pos, data);
+                true
+            }
+        }
+    }
+}
+
+/// # Parsing and Composing
+///
+impl<Octs, D> Rrset<ParsedDname<Octs>, D> {
+    /// Collects a record set from a section of a message.
+    ///
+    /// The function looks at all the remaining records of `section` and
+    /// collects those with the given owner name, class, and record type.
+    /// The records don’t need to follow each other in the section. Returns
+    /// `Ok(None)` if there are no such records.
+    pub fn from_section<'a, Src, Name>(
+        section: RecordSection<'a, Src>,
+        owner: &Name,
+        class: Class,
+        rtype: Rtype,
+    ) -> Result<Option<Self>, ParseError>
+    where
+        Src: Octets<Range<'a> = Octs> + ?Sized + 'a,
+        D: ParseRecordData<'a, Src> + CanonicalOrd,
+        Name: ToDname + ?Sized,
+    {
+        let mut res: Option<Self> = None;
+        for record in section {
+            let record = record?;
+            if record.rtype() != rtype
+                || record.class() != class
+                || !record.owner().name_eq(owner)
+            {
+                continue;
+            }
+            let record = match record.into_record::<D>()? {
+                Some(record) => record,
+                None => continue,
+            };
+            let ttl = record.ttl();
+            let (record_owner, data) = record.into_owner_and_data();
+            match res {
+                Some(ref mut rrset) => {
+                    rrset.ttl = cmp::min(rrset.ttl, ttl);
+                    rrset.insert(data);
+                }
+                None => {
+                    let mut rrset =
+                        Rrset::new(record_owner, class, rtype, ttl);
+                    rrset.insert(data);
+                    res = Some(rrset);
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<N: ToDname, D: RecordData + ComposeRecordData> Rrset<N, D> {
+    /// Appends the wire format of all records of the set to a target.
+    ///
+    /// Note that this doesn’t update any record counts. Use the
+    /// `push_rrset` methods of the message builder for that.
+    pub fn compose<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        for record in self.records() {
+            record.compose(target)?;
+        }
+        Ok(())
+    }
+
+    /// Appends the canonical wire format of all records to a target.
+    ///
+    /// Since the records are kept in canonical order, this produces the
+    /// record part of the data to be signed for an RRSIG record as
+    /// described in section 3.1.8.1 of [RFC 4034].
+    ///
+    /// [RFC 4034]: https://tools.ietf.org/html/rfc4034
+    pub fn compose_canonical<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        for record in self.records() {
+            record.compose_canonical(target)?;
+        }
+        Ok(())
+    }
+}
+
+//--- IntoIterator
+
+impl<'a, N, D> IntoIterator for &'a Rrset<N, D> {
+    type Item = &'a D;
+    type IntoIter = slice::Iter<'a, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN, D, DD> PartialEq<Rrset<NN, DD>> for Rrset<N, D>
+where
+    N: ToDname,
+    NN: ToDname,
+    D: PartialEq<DD>,
+{
+    fn eq(&self, other: &Rrset<NN, DD>) -> bool {
+        self.owner.name_eq(&other.owner)
+            && self.class == other.class
+            && self.rtype == other.rtype
+            && self.ttl == other.ttl
+            && self.data.as_slice() == other.data.as_slice()
+    }
+}
+
+impl<N: ToDname, D: Eq> Eq for Rrset<N, D> {}
+
+//--- Debug
+
+impl<N: fmt::Debug, D: fmt::Debug> fmt::Debug for Rrset<N, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rrset")
+            .field("owner", &self.owner)
+            .field("class", &self.class)
+            .field("rtype", &self.rtype)
+            .field("ttl", &self.ttl)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+//------------ RrsetRecords --------------------------------------------------
+
+/// An iterator over the records of a record set.
+///
+/// The iterator is returned by [`Rrset::records`].
+#[derive(Clone, Debug)]
+pub struct RrsetRecords<'a, N, D> {
+    /// The set we are iterating over.
+    rrset: &'a Rrset<N, D>,
+
+    /// The iterator over the set’s data.
+    data: slice::Iter<'a, D>,
+}
+
+impl<'a, N, D> Iterator for RrsetRecords<'a, N, D> {
+    type Item = Record<&'a N, &'a D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.data.next().map(|data| {
+            Record::new(
+                &self.rrset.owner,
+                self.rrset.class,
+                self.rrset.ttl,
+                data,
+            )
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+impl<'a, N, D> ExactSizeIterator for RrsetRecords<'a, N, D> {}
+
+//============ Errors ========================================================
+
+/// A record could not be added to a record set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RrsetError {
+    /// The owner name of the record differs from that of the set.
+    OwnerMismatch,
+
+    /// The class of the record differs from that of the set.
+    ClassMismatch,
+
+    /// The record type of the record differs from that of the set.
+    RtypeMismatch,
+}
+
+impl fmt::Display for RrsetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RrsetError::OwnerMismatch => f.write_str("owner name mismatch"),
+            RrsetError::ClassMismatch => f.write_str("class mismatch"),
+            RrsetError::RtypeMismatch => f.write_str("record type mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for RrsetError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::message_builder::MessageBuilder;
+    use crate::base::name::Dname;
+    use crate::base::Message;
+    use crate::rdata::{Mx, A};
+    use core::str::FromStr;
+
+    fn name(s: &str) -> Dname<Vec<u8>> {
+        Dname::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn push() {
+        let mut rrset =
+            Rrset::new(name("example.com"), Class::In, Rtype::A, 3600);
+        assert!(rrset.is_empty());
+        assert_eq!(
+            rrset.push(Record::new(
+                name("example.com"),
+                Class::In,
+                3600,
+                A::from_octets(192, 0, 2, 2)
+            )),
+            Ok(true)
+        );
+        assert_eq!(
+            rrset.push(Record::new(
+                name("EXAMPLE.com"),
+                Class::In,
+                600,
+                A::from_octets(192, 0, 2, 1)
+            )),
+            Ok(true)
+        );
+        assert_eq!(
+            rrset.push(Record::new(
+                name("example.com"),
+                Class::In,
+                3600,
+                A::from_octets(192, 0, 2, 2)
+            )),
+            Ok(false)
+        );
+        assert_eq!(
+            rrset.push(Record::new(
+                name("example.org"),
+                Class::In,
+                3600,
+                A::from_octets(192, 0, 2, 3)
+            )),
+            Err(RrsetError::OwnerMismatch)
+        );
+        assert_eq!(
+            rrset.push(Record::new(
+                name("example.com"),
+                Class::Ch,
+                3600,
+                A::from_octets(192, 0, 2, 3)
+            )),
+            Err(RrsetError::ClassMismatch)
+        );
+        assert_eq!(rrset.ttl(), 600);
+        assert_eq!(
+            rrset.data(),
+            &[A::from_octets(192, 0, 2, 1), A::from_octets(192, 0, 2, 2)]
+        );
+
+        let mut rrset =
+            Rrset::new(name("example.com"), Class::In, Rtype::Aaaa, 3600);
+        assert_eq!(
+            rrset.push_data(A::from_octets(192, 0, 2, 1)),
+            Err(RrsetError::RtypeMismatch)
+        );
+    }
+
+    #[test]
+    fn compose_and_parse() {
+        let mut rrset = Rrset::from_record(Record::new(
+            name("example.com"),
+            Class::In,
+            3600,
+            Mx::new(20, name("mx2.example.com")),
+        ));
+        rrset
+            .push_data(Mx::new(10, name("mx1.example.com")))
+            .unwrap();
+
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((name("example.com"), 60, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push_rrset(rrset.records()).unwrap();
+        let msg = Message::from_octets(msg.finish()).unwrap();
+        assert_eq!(msg.header_counts().ancount(), 3);
+
+        let parsed = Rrset::<_, Mx<ParsedDname<_>>>::from_section(
+            msg.answer().unwrap(),
+            &name("example.com"),
+            Class::In,
+            Rtype::Mx,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(parsed, rrset);
+        assert!(Rrset::<_, Mx<ParsedDname<_>>>::from_section(
+            msg.answer().unwrap(),
+            &name("example.org"),
+            Class::In,
+            Rtype::Mx,
+        )
+        .unwrap()
+        .is_none());
+    }
+}
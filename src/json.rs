@@ -0,0 +1,729 @@
+//! JSON representation of DNS messages.
+//!
+//! [RFC 8427] defines a representation of DNS messages and their parts in
+//! JSON. This module provides functions to convert messages, questions,
+//! and resource records into this representation and back.
+//!
+//! The JSON values are represented by the [`Value`] type of the
+//! [serde_json] crate which is re-exported by this module for convenience.
+//!
+//! When encoding, the members for the header, the question, and the record
+//! sections of a message are produced. Record data of all types that can
+//! appear in zone files is given as a member named `rdata` followed by the
+//! mnemonic of the record type containing the data in presentation format.
+//! All other record data is given in the `RDATAHEX` member. As an
+//! extension, the options of the OPT record are given as an array in the
+//! `rdataOPT` member with each option being an object with the members
+//! `CODE`, `CODEname`, `DATAHEX`, and, for options known to the crate,
+//! `DATA` containing the presentation format of the option data.
+//!
+//! When decoding, only the members necessary to re-create the message are
+//! considered. Record data can be given either via `RDATAHEX` or via a
+//! member with the record type’s mnemonic. The latter is scanned using the
+//! [zonefile scanner][crate::zonefile::inplace] with relative names taken as
+//! relative to the root. The counts in the header are ignored and
+//! re-calculated from the actual content.
+//!
+//! Because decoding relies on the zonefile scanner, this module is only
+//! available if the `json` feature is enabled which in turn enables the
+//! `zonefile` feature.
+//!
+//! [RFC 8427]: https://tools.ietf.org/html/rfc8427
+//! [serde_json]: https://github.com/serde-rs/json
+#![cfg(feature = "json")]
+#![cfg_attr(docsrs, doc(cfg(feature = "json")))]
+
+use crate::base::iana::{Class, Opcode, OptionCode, Rcode, Rtype};
+use crate::base::message::{Message, RecordSection};
+use crate::base::message_builder::{MessageBuilder, PushError};
+use crate::base::name::{Dname, ParsedDname, ToDname};
+use crate::base::opt::{AllOptData, ComposeOptData, Opt, OptData};
+use crate::base::question::Question;
+use crate::base::rdata::{
+    ComposeRecordData, ParseRecordData, RecordData, UnknownRecordData,
+};
+use crate::base::record::Record;
+use crate::base::wire::ParseError;
+use crate::rdata::{AllRecordData, ZoneRecordData};
+use crate::utils::base16;
+use crate::zonefile::inplace::{self, Entry, Zonefile};
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+use octseq::builder::infallible;
+use octseq::octets::Octets;
+use octseq::parse::Parser;
+use serde_json::Map;
+use std::format;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+//------------ Re-exports ----------------------------------------------------
+
+pub use serde_json::Value;
+
+//------------ Encoding ------------------------------------------------------
+
+/// Converts a message into its JSON representation.
+///
+/// The header members are always included. If the message has at least
+/// one question, the members for the first question are included. If it
+/// has more than one question, all questions are also given in the
+/// `questionRRs` member. The members for the three record sections are only
+/// included if the section isn’t empty.
+///
+/// Returns an error if parsing the message fails.
+pub fn message_to_json<Octs: Octets>(
+    msg: &Message<Octs>,
+) -> Result<Value, ParseError> {
+    let header = msg.header();
+    let counts = msg.header_counts();
+    let mut res = Map::new();
+    res.insert("ID".into(), header.id().into());
+    res.insert("QR".into(), u8::from(header.qr()).into());
+    res.insert("Opcode".into(), header.opcode().to_int().into());
+    res.insert("AA".into(), u8::from(header.aa()).into());
+    res.insert("TC".into(), u8::from(header.tc()).into());
+    res.insert("RD".into(), u8::from(header.rd()).into());
+    res.insert("RA".into(), u8::from(header.ra()).into());
+    res.insert("AD".into(), u8::from(header.ad()).into());
+    res.insert("CD".into(), u8::from(header.cd()).into());
+    res.insert("RCODE".into(), header.rcode().to_int().into());
+    res.insert("QDCOUNT".into(), counts.qdcount().into());
+    res.insert("ANCOUNT".into(), counts.ancount().into());
+    res.insert("NSCOUNT".into(), counts.nscount().into());
+    res.insert("ARCOUNT".into(), counts.arcount().into());
+
+    let questions = msg.question().collect::<Result<Vec<_>, _>>()?;
+    if let Some(question) = questions.first() {
+        res.insert("QNAME".into(), name_to_json(question.qname()));
+        res.insert("QTYPE".into(), question.qtype().to_int().into());
+        res.insert("QTYPEname".into(), question.qtype().to_string().into());
+        res.insert("QCLASS".into(), question.qclass().to_int().into());
+        res.insert("QCLASSname".into(), question.qclass().to_string().into());
+    }
+    if questions.len() > 1 {
+        res.insert(
+            "questionRRs".into(),
+            questions.iter().map(question_to_json).collect(),
+        );
+    }
+
+    let mut section = msg.answer()?;
+    for key in ["answerRRs", "authorityRRs", "additionalRRs"] {
+        let records = section_to_json(section)?;
+        if !records.is_empty() {
+            res.insert(key.into(), records.into());
+        }
+        section = match section.next_section()? {
+            Some(section) => section,
+            None => break,
+        }
+    }
+
+    Ok(res.into())
+}
+
+/// Converts a question into its JSON representation.
+///
+/// The question is represented as an object with the members `NAME`,
+/// `TYPE`, `TYPEname`, `CLASS`, and `CLASSname`.
+pub fn question_to_json<N: ToDname>(question: &Question<N>) -> Value {
+    let mut res = Map::new();
+    res.insert("NAME".into(), name_to_json(question.qname()));
+    insert_rtype(&mut res, question.qtype());
+    insert_class(&mut res, question.qclass());
+    res.into()
+}
+
+/// Converts a record into its JSON representation.
+///
+/// The record data is converted into its wire format and then re-parsed in
+/// order to determine how to represent it. Returns an error if this fails.
+pub fn record_to_json<N, D>(
+    record: &Record<N, D>,
+) -> Result<Value, ParseError>
+where
+    N: ToDname,
+    D: RecordData + ComposeRecordData,
+{
+    let mut rdata = Vec::new();
+    infallible(record.data().compose_rdata(&mut rdata));
+    let mut parser = Parser::from_ref(&rdata);
+    let data = AllRecordData::<&[u8], ParsedDname<&[u8]>>::parse_rdata(
+        record.rtype(),
+        &mut parser,
+    )?
+    .ok_or_else(|| ParseError::form_error("unparseable record data"))?;
+    rr_to_json(record.owner(), record.class(), record.ttl(), data)
+}
+
+/// Converts the records of a section into their JSON representations.
+fn section_to_json<'a, Octs: Octets>(
+    section: RecordSection<'a, Octs>,
+) -> Result<Vec<Value>, ParseError> {
+    let mut res = Vec::new();
+    for record in section.limit_to::<AllRecordData<
+        Octs::Range<'a>,
+        ParsedDname<Octs::Range<'a>>,
+    >>() {
+        let record = record?;
+        let (class, ttl) = (record.class(), record.ttl());
+        let (owner, data) = record.into_owner_and_data();
+        res.push(rr_to_json(&owner, class, ttl, data)?);
+    }
+    Ok(res)
+}
+
+/// Converts the parts of a record into its JSON representation.
+fn rr_to_json<N, O, DN>(
+    owner: &N,
+    class: Class,
+    ttl: u32,
+    data: AllRecordData<O, DN>,
+) -> Result<Value, ParseError>
+where
+    N: ToDname,
+    O: Octets,
+    DN: ToDname + fmt::Display,
+{
+    let rtype = RecordData::rtype(&data);
+    let mut res = Map::new();
+    res.insert("NAME".into(), name_to_json(owner));
+    insert_rtype(&mut res, rtype);
+    insert_class(&mut res, class);
+    res.insert("TTL".into(), ttl.into());
+    match Result::<ZoneRecordData<O, DN>, _>::from(data) {
+        Ok(ZoneRecordData::Unknown(data)) => {
+            res.insert("RDATAHEX".into(), hex_to_json(data.data()));
+        }
+        Ok(data) => {
+            res.insert(format!("rdata{}", rtype), data.to_string().into());
+        }
+        Err(AllRecordData::Opt(opt)) => {
+            res.insert("rdataOPT".into(), opt_to_json(&opt)?);
+        }
+        Err(data) => {
+            let mut rdata = Vec::new();
+            infallible(data.compose_rdata(&mut rdata));
+            res.insert("RDATAHEX".into(), hex_to_json(&rdata));
+        }
+    }
+    Ok(res.into())
+}
+
+/// Converts the options of an OPT record into an array.
+fn opt_to_json<O: Octets>(opt: &Opt<O>) -> Result<Value, ParseError> {
+    let mut res = Vec::new();
+    for option in opt.iter::<AllOptData<_, Dname<_>>>() {
+        let option = option?;
+        let code = option.code();
+        let mut data = Vec::new();
+        infallible(option.compose_option(&mut data));
+        let mut item = Map::new();
+        item.insert("CODE".into(), code.to_int().into());
+        item.insert("CODEname".into(), code.to_string().into());
+        item.insert("DATAHEX".into(), hex_to_json(&data));
+        if !matches!(option, AllOptData::Other(_)) {
+            item.insert("DATA".into(), option.to_string().into());
+        }
+        res.push(item.into());
+    }
+    Ok(res.into())
+}
+
+/// Converts a domain name into an absolute name string.
+fn name_to_json<N: ToDname + ?Sized>(name: &N) -> Value {
+    // The display format omits the trailing dot, so for the root name it
+    // is empty.
+    format!("{}.", name.to_cow()).into()
+}
+
+/// Converts octets into a hex string.
+fn hex_to_json<Octs: AsRef<[u8]> + ?Sized>(octets: &Octs) -> Value {
+    base16::encode_string(octets).into()
+}
+
+/// Adds the `TYPE` and `TYPEname` members.
+fn insert_rtype(res: &mut Map<String, Value>, rtype: Rtype) {
+    res.insert("TYPE".into(), rtype.to_int().into());
+    res.insert("TYPEname".into(), rtype.to_string().into());
+}
+
+/// Adds the `CLASS` and `CLASSname` members.
+fn insert_class(res: &mut Map<String, Value>, class: Class) {
+    res.insert("CLASS".into(), class.to_int().into());
+    res.insert("CLASSname".into(), class.to_string().into());
+}
+
+//------------ Decoding ------------------------------------------------------
+
+/// Creates a message from its JSON representation.
+///
+/// Header members that are missing are taken to be zero. The question is
+/// taken from the `questionRRs` member if present or from the `QNAME`,
+/// `QTYPE`, and `QCLASS` members otherwise.
+pub fn message_from_json(json: &Value) -> Result<Message<Vec<u8>>, Error> {
+    let json = json.as_object().ok_or(Error::member("message"))?;
+
+    let mut msg = MessageBuilder::new_vec();
+    let header = msg.header_mut();
+    header.set_id(get_int(json, "ID")?.unwrap_or(0));
+    header.set_qr(get_flag(json, "QR")?);
+    header.set_opcode(Opcode::from_int(get_nibble(json, "Opcode")?));
+    header.set_aa(get_flag(json, "AA")?);
+    header.set_tc(get_flag(json, "TC")?);
+    header.set_rd(get_flag(json, "RD")?);
+    header.set_ra(get_flag(json, "RA")?);
+    header.set_ad(get_flag(json, "AD")?);
+    header.set_cd(get_flag(json, "CD")?);
+    header.set_rcode(Rcode::from_int(get_nibble(json, "RCODE")?));
+
+    let mut msg = msg.question();
+    if let Some(questions) = get_array(json, "questionRRs")? {
+        for question in questions {
+            msg.push(question_from_json(question)?)?;
+        }
+    } else if let Some(qname) = get_str(json, "QNAME")? {
+        msg.push(Question::new(
+            Dname::<Vec<u8>>::from_str(qname)
+                .map_err(|_| Error::member("QNAME"))?,
+            get_rtype(json, "QTYPE", "QTYPEname")?
+                .ok_or(Error::member("QTYPE"))?,
+            get_class(json, "QCLASS", "QCLASSname")?.unwrap_or(Class::In),
+        ))?;
+    }
+
+    let mut msg = msg.answer();
+    for record in get_array(json, "answerRRs")?.unwrap_or_default() {
+        msg.push(record_from_json(record)?)?;
+    }
+    let mut msg = msg.authority();
+    for record in get_array(json, "authorityRRs")?.unwrap_or_default() {
+        msg.push(record_from_json(record)?)?;
+    }
+    let mut msg = msg.additional();
+    for record in get_array(json, "additionalRRs")?.unwrap_or_default() {
+        msg.push(record_from_json(record)?)?;
+    }
+    Ok(msg.into_message())
+}
+
+/// Creates a question from its JSON representation.
+///
+/// The question needs to have the members `NAME` and either `TYPE` or
+/// `TYPEname`. If neither `CLASS` nor `CLASSname` are present, the class
+/// defaults to IN.
+pub fn question_from_json(
+    json: &Value,
+) -> Result<Question<Dname<Vec<u8>>>, Error> {
+    let json = json.as_object().ok_or(Error::member("question"))?;
+    Ok(Question::new(
+        get_name(json)?,
+        get_rtype(json, "TYPE", "TYPEname")?.ok_or(Error::member("TYPE"))?,
+        get_class(json, "CLASS", "CLASSname")?.unwrap_or(Class::In),
+    ))
+}
+
+/// Creates a record from its JSON representation.
+///
+/// The record needs to have the members `NAME`, either `TYPE` or
+/// `TYPEname`, and the record data. If neither `CLASS` nor `CLASSname` are
+/// present, the class defaults to IN. If `TTL` is missing, it defaults to
+/// zero.
+///
+/// The record data is returned in wire format as
+/// [`UnknownRecordData`]. It is taken from the `RDATAHEX` member if present
+/// or scanned from the member named `rdata` followed by the mnemonic of the
+/// record type otherwise. For OPT records, the `rdataOPT` member is used
+/// instead.
+pub fn record_from_json(
+    json: &Value,
+) -> Result<Record<Dname<Vec<u8>>, UnknownRecordData<Vec<u8>>>, Error> {
+    let json = json.as_object().ok_or(Error::member("record"))?;
+    let rtype =
+        get_rtype(json, "TYPE", "TYPEname")?.ok_or(Error::member("TYPE"))?;
+    let rdata = if let Some(hex) = get_str(json, "RDATAHEX")? {
+        base16::decode(hex).map_err(|_| Error::member("RDATAHEX"))?
+    } else if rtype == Rtype::Opt {
+        opt_from_json(
+            get_array(json, "rdataOPT")?.ok_or(Error::member("rdataOPT"))?,
+        )?
+    } else {
+        scan_rdata(
+            rtype,
+            json.get(&format!("rdata{}", rtype))
+                .and_then(Value::as_str)
+                .ok_or(Error::member("rdata"))?,
+        )?
+    };
+    Ok(Record::new(
+        get_name(json)?,
+        get_class(json, "CLASS", "CLASSname")?.unwrap_or(Class::In),
+        get_int(json, "TTL")?.unwrap_or(0),
+        UnknownRecordData::from_octets(rtype, rdata)
+            .map_err(|_| Error::member("rdata"))?,
+    ))
+}
+
+/// Creates the wire format of OPT record data from an array of options.
+fn opt_from_json(options: &[Value]) -> Result<Vec<u8>, Error> {
+    let mut res = Vec::new();
+    for option in options {
+        let option = option.as_object().ok_or(Error::member("rdataOPT"))?;
+        let code = match get_int(option, "CODE")? {
+            Some(code) => OptionCode::from_int(code),
+            None => get_str(option, "CODEname")?
+                .and_then(|code| OptionCode::from_str(code).ok())
+                .ok_or(Error::member("CODE"))?,
+        };
+        let data: Vec<u8> = base16::decode(
+            get_str(option, "DATAHEX")?.ok_or(Error::member("DATAHEX"))?,
+        )
+        .map_err(|_| Error::member("DATAHEX"))?;
+        let len = u16::try_from(data.len())
+            .map_err(|_| Error::member("DATAHEX"))?;
+        res.extend_from_slice(&code.to_int().to_be_bytes());
+        res.extend_from_slice(&len.to_be_bytes());
+        res.extend_from_slice(&data);
+    }
+    Ok(res)
+}
+
+/// Scans record data in presentation format into its wire format.
+fn scan_rdata(rtype: Rtype, rdata: &str) -> Result<Vec<u8>, Error> {
+    let mut zonefile =
+        Zonefile::from(format!(". 0 IN {} {}\n", rtype, rdata).as_str());
+    zonefile.set_origin(Dname::root_bytes());
+    let record = match zonefile.next_entry()? {
+        Some(Entry::Record(record)) => record,
+        _ => return Err(Error::member("rdata")),
+    };
+    if zonefile.next_entry()?.is_some() {
+        return Err(Error::member("rdata"));
+    }
+    let mut res = Vec::new();
+    infallible(record.data().compose_rdata(&mut res));
+    Ok(res)
+}
+
+/// Returns the domain name in the `NAME` member.
+fn get_name(json: &Map<String, Value>) -> Result<Dname<Vec<u8>>, Error> {
+    get_str(json, "NAME")?
+        .and_then(|name| Dname::from_str(name).ok())
+        .ok_or(Error::member("NAME"))
+}
+
+/// Returns the record type from either the integer or mnemonic member.
+fn get_rtype(
+    json: &Map<String, Value>,
+    int_key: &'static str,
+    name_key: &'static str,
+) -> Result<Option<Rtype>, Error> {
+    if let Some(rtype) = get_int(json, int_key)? {
+        return Ok(Some(Rtype::from_int(rtype)));
+    }
+    get_str(json, name_key)?
+        .map(|rtype| {
+            Rtype::from_str(rtype).map_err(|_| Error::member(name_key))
+        })
+        .transpose()
+}
+
+/// Returns the class from either the integer or mnemonic member.
+fn get_class(
+    json: &Map<String, Value>,
+    int_key: &'static str,
+    name_key: &'static str,
+) -> Result<Option<Class>, Error> {
+    if let Some(class) = get_int(json, int_key)? {
+        return Ok(Some(Class::from_int(class)));
+    }
+    get_str(json, name_key)?
+        .map(|class| {
+            Class::from_str(class).map_err(|_| Error::member(name_key))
+        })
+        .transpose()
+}
+
+/// Returns the integer value of a member if present.
+fn get_int<T: TryFrom<u64>>(
+    json: &Map<String, Value>,
+    key: &'static str,
+) -> Result<Option<T>, Error> {
+    json.get(key)
+        .map(|value| {
+            value
+                .as_u64()
+                .and_then(|value| T::try_from(value).ok())
+                .ok_or(Error::member(key))
+        })
+        .transpose()
+}
+
+/// Returns the value of a four bit header field.
+///
+/// Returns zero if the member is missing.
+fn get_nibble(
+    json: &Map<String, Value>,
+    key: &'static str,
+) -> Result<u8, Error> {
+    match get_int(json, key)? {
+        Some(value) if value > 0x0F => Err(Error::member(key)),
+        Some(value) => Ok(value),
+        None => Ok(0),
+    }
+}
+
+/// Returns the value of a header flag.
+///
+/// The flag can either be given as a boolean or as the integers 0 or 1.
+/// Returns `false` if the member is missing.
+fn get_flag(
+    json: &Map<String, Value>,
+    key: &'static str,
+) -> Result<bool, Error> {
+    match json.get(key) {
+        None => Ok(false),
+        Some(Value::Bool(value)) => Ok(*value),
+        Some(value) => match value.as_u64() {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            _ => Err(Error::member(key)),
+        },
+    }
+}
+
+/// Returns the value of a string member if present.
+fn get_str<'a>(
+    json: &'a Map<String, Value>,
+    key: &'static str,
+) -> Result<Option<&'a str>, Error> {
+    json.get(key)
+        .map(|value| value.as_str().ok_or(Error::member(key)))
+        .transpose()
+}
+
+/// Returns the value of an array member if present.
+fn get_array<'a>(
+    json: &'a Map<String, Value>,
+    key: &'static str,
+) -> Result<Option<&'a [Value]>, Error> {
+    json.get(key)
+        .map(|value| {
+            value
+                .as_array()
+                .map(Vec::as_slice)
+                .ok_or(Error::member(key))
+        })
+        .transpose()
+}
+
+//------------ Error ---------------------------------------------------------
+
+/// An error happened while creating a value from its JSON representation.
+#[derive(Debug)]
+pub struct Error(ErrorKind);
+
+#[derive(Debug)]
+enum ErrorKind {
+    /// A member is missing or has an invalid value.
+    Member(&'static str),
+
+    /// Scanning record data failed.
+    Zonefile(inplace::Error),
+
+    /// The message became too large.
+    Push(PushError),
+}
+
+impl Error {
+    fn member(key: &'static str) -> Self {
+        Error(ErrorKind::Member(key))
+    }
+}
+
+//--- From
+
+impl From<inplace::Error> for Error {
+    fn from(err: inplace::Error) -> Self {
+        Error(ErrorKind::Zonefile(err))
+    }
+}
+
+impl From<PushError> for Error {
+    fn from(err: PushError) -> Self {
+        Error(ErrorKind::Push(err))
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            ErrorKind::Member(key) => {
+                write!(f, "missing or invalid member '{}'", key)
+            }
+            ErrorKind::Zonefile(ref err) => {
+                write!(f, "invalid record data: {}", err)
+            }
+            ErrorKind::Push(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::OptRcode;
+    use crate::base::opt::rfc7873::ClientCookie;
+    use crate::base::opt::Cookie;
+    use crate::rdata::{Ns, Txt, A};
+    use serde_json::json;
+
+    fn test_message() -> Message<Vec<u8>> {
+        let name = Dname::<Vec<u8>>::from_str("example.com.").unwrap();
+        let mut msg = MessageBuilder::new_vec();
+        msg.header_mut().set_id(4711);
+        msg.header_mut().set_qr(true);
+        msg.header_mut().set_rd(true);
+        let mut msg = msg.question();
+        msg.push((&name, Rtype::A)).unwrap();
+        let mut msg = msg.answer();
+        msg.push((&name, 3600, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push((&name, 3600, Txt::build_from_slice(b"foo bar").unwrap()))
+            .unwrap();
+        let mut msg = msg.authority();
+        msg.push((
+            &name,
+            86400,
+            Ns::new(Dname::<Vec<u8>>::from_str("ns.example.com.").unwrap()),
+        ))
+        .unwrap();
+        msg.push((
+            &name,
+            86400,
+            UnknownRecordData::from_octets(Rtype::Int(65000), vec![1, 2])
+                .unwrap(),
+        ))
+        .unwrap();
+        let mut msg = msg.additional();
+        msg.opt(|opt| {
+            opt.set_udp_payload_size(1232);
+            opt.set_rcode(OptRcode::BadCookie);
+            opt.push(&Cookie::from(ClientCookie::new([
+                1, 2, 3, 4, 5, 6, 7, 8,
+            ])))
+        })
+        .unwrap();
+        msg.into_message()
+    }
+
+    #[test]
+    fn encode_message() {
+        let json = message_to_json(&test_message()).unwrap();
+        assert_eq!(json["ID"], 4711);
+        assert_eq!(json["QR"], 1);
+        assert_eq!(json["AA"], 0);
+        assert_eq!(json["RCODE"], 7);
+        assert_eq!(json["QNAME"], "example.com.");
+        assert_eq!(json["QTYPEname"], "A");
+        assert!(json.get("questionRRs").is_none());
+        assert_eq!(
+            json["answerRRs"],
+            json!([
+                {
+                    "NAME": "example.com.",
+                    "TYPE": 1, "TYPEname": "A",
+                    "CLASS": 1, "CLASSname": "IN",
+                    "TTL": 3600,
+                    "rdataA": "192.0.2.1"
+                },
+                {
+                    "NAME": "example.com.",
+                    "TYPE": 16, "TYPEname": "TXT",
+                    "CLASS": 1, "CLASSname": "IN",
+                    "TTL": 3600,
+                    "rdataTXT": "foo\\ bar"
+                }
+            ])
+        );
+        assert_eq!(json["authorityRRs"][0]["rdataNS"], "ns.example.com.");
+        assert_eq!(json["authorityRRs"][1]["RDATAHEX"], "0102");
+        assert_eq!(
+            json["additionalRRs"],
+            json!([
+                {
+                    "NAME": ".",
+                    "TYPE": 41, "TYPEname": "OPT",
+                    "CLASS": 1232, "CLASSname": "CLASS1232",
+                    "TTL": 0x0100_0000,
+                    "rdataOPT": [
+                        {
+                            "CODE": 10, "CODEname": "COOKIE",
+                            "DATAHEX": "0102030405060708",
+                            "DATA": "0102030405060708"
+                        }
+                    ]
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let msg = test_message();
+        let json = message_to_json(&msg).unwrap();
+        let decoded = message_from_json(&json).unwrap();
+        assert_eq!(msg.as_slice(), decoded.as_slice());
+    }
+
+    #[test]
+    fn decode_rfc8427_example() {
+        // The example from section 4.1 of RFC 8427.
+        let msg = message_from_json(&json!({
+            "ID": 19678, "QR": 0, "Opcode": 0,
+            "AA": 0, "TC": 0, "RD": 0, "RA": 0, "AD": 0, "CD": 0,
+            "RCODE": 0,
+            "QDCOUNT": 1, "ANCOUNT": 0, "NSCOUNT": 0, "ARCOUNT": 0,
+            "QNAME": "example.com", "QTYPE": 1, "QCLASS": 1
+        }))
+        .unwrap();
+        assert_eq!(msg.header().id(), 19678);
+        let question = msg.sole_question().unwrap();
+        assert_eq!(question.qname().to_string(), "example.com");
+        assert_eq!(question.qtype(), Rtype::A);
+        assert_eq!(question.qclass(), Class::In);
+    }
+
+    #[test]
+    fn decode_record() {
+        let record = record_from_json(&json!({
+            "NAME": "www.example.com", "TYPEname": "MX", "TTL": 300,
+            "rdataMX": "10 mail.example.com."
+        }))
+        .unwrap();
+        assert_eq!(record.class(), Class::In);
+        assert_eq!(record.ttl(), 300);
+        assert_eq!(record.data().rtype(), Rtype::Mx);
+        assert_eq!(
+            record.data().data().as_slice(),
+            b"\x00\x0a\x04mail\x07example\x03com\x00"
+        );
+
+        assert!(record_from_json(&json!({
+            "NAME": "www.example.com", "TYPE": 1, "rdataA": "bogus"
+        }))
+        .is_err());
+        assert!(record_from_json(&json!({
+            "NAME": "www.example.com", "TYPE": 1
+        }))
+        .is_err());
+    }
+}
@@ -27,6 +27,9 @@
 //!
 //! Currently, there are the following modules:
 //!
+#![cfg_attr(feature = "json", doc = "* [json]:")]
+#![cfg_attr(not(feature = "json"), doc = "* json:")]
+//!   Conversion of DNS messages from and to their JSON representation.
 #![cfg_attr(feature = "resolv", doc = "* [resolv]:")]
 #![cfg_attr(not(feature = "resolv"), doc = "* resolv:")]
 //!   An asynchronous DNS resolver based on the
@@ -58,11 +61,24 @@
 //! * `heapless`: enables the use of the `Vec` type from the
 //!   [heapless](https://github.com/japaric/heapless) crate as octet
 //!   sequences.
+//! * `idna`: Enables converting internationalized domain names from and to
+//!   Unicode via the
+//!   [unicode-normalization](https://github.com/unicode-rs/unicode-normalization)
+//!   and [unicode-bidi](https://github.com/servo/unicode-bidi) crates.
 //! * `interop`: Activate interoperability tests that rely on other software
 //!   to be installed in the system (currently NSD and dig) and will fail if
 //!   it isn’t. This feature is not meaningful for users of the crate.
+//! * `json`: Enables the
+#![cfg_attr(feature = "json", doc = "  [json]")]
+#![cfg_attr(not(feature = "json"), doc = "  json")]
+//!   module for converting DNS messages from and to JSON as defined in
+//!   RFC 8427. This adds the
+//!   [serde_json](https://github.com/serde-rs/json) crate as a dependency
+//!   and also enables the `zonefile` feature.
 //! * `random`: Enables a number of methods that rely on a random number
 //!   generator being available in the system.
+//! * `regex`: Enables applying the substitution expressions of NAPTR
+//!   records via the [regex](https://github.com/rust-lang/regex) crate.
 //! * `resolv`: Enables the asynchronous stub resolver via the
 #![cfg_attr(feature = "resolv", doc = "  [resolv]")]
 #![cfg_attr(not(feature = "resolv"), doc = "  resolv")]
@@ -78,6 +94,9 @@
 //!   enable actual signing. For that you will also need to pick a crypto
 //!   module via an additional feature. Currently we only support the `ring`
 //!   module, but support for OpenSSL is coming soon.
+//! * `siphasher`: Enables creating and checking interoperable server
+//!   cookies as defined in RFC 9018 via the
+//!   [siphasher](https://github.com/jedisct1/rust-siphash) crate.
 //! * `smallvec`: enables the use of the `Smallvec` type from the
 //!   [smallvec](https://github.com/servo/rust-smallvec) crate as octet
 //!   sequences.
@@ -114,6 +133,7 @@ extern crate std;
 extern crate core;
 
 pub mod base;
+pub mod json;
 pub mod rdata;
 pub mod resolv;
 pub mod sign;
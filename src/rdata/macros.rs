@@ -479,6 +479,28 @@ macro_rules! rdata_types {
         /// This enum collects the record data types for all currently
         /// implemented record types.
         #[derive(Clone)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize)
+        )]
+        #[cfg_attr(
+            feature = "serde",
+            serde(bound(
+                serialize = "
+                    O: AsRef<[u8]> + octseq::serde::SerializeOctets,
+                    N: serde::Serialize,
+                ",
+                deserialize = "
+                    O: AsRef<[u8]> + octseq::builder::FromBuilder
+                        + octseq::serde::DeserializeOctets<'de>,
+                    <O as octseq::builder::FromBuilder>::Builder:
+                          octseq::builder::EmptyBuilder
+                        + octseq::builder::Truncate
+                        + AsRef<[u8]> + AsMut<[u8]>,
+                    N: serde::Deserialize<'de>,
+                ",
+            ))
+        )]
         #[non_exhaustive]
         pub enum AllRecordData<O, N> {
             $( $( $(
@@ -78,11 +78,41 @@ rdata_types! {
             Ptr<N>,
             Soa<N>,
             Txt<O>,
+            Wks<O>,
         }
         pseudo {
             Null<O>
         }
     }
+    rfc1183::{
+        zone {
+            Afsdb<N>,
+            Rp<N>,
+            X25<O>,
+            Isdn<O>,
+            Rt<N>,
+        }
+    }
+    rfc1712::{
+        zone {
+            Gpos<O>,
+        }
+    }
+    rfc1876::{
+        zone {
+            Loc,
+        }
+    }
+    rfc2163::{
+        zone {
+            Px<N>,
+        }
+    }
+    rfc2230::{
+        zone {
+            Kx<N>,
+        }
+    }
     rfc2782::{
         zone {
             Srv<N>,
@@ -93,6 +123,16 @@ rdata_types! {
             Tsig<O, N>,
         }
     }
+    rfc3123::{
+        zone {
+            Apl<O>,
+        }
+    }
+    rfc3403::{
+        zone {
+            Naptr<O, N>,
+        }
+    }
     rfc3596::{
         zone {
             Aaaa,
@@ -106,6 +146,21 @@ rdata_types! {
             Ds<O>,
         }
     }
+    rfc4255::{
+        zone {
+            Sshfp<O>,
+        }
+    }
+    rfc4398::{
+        zone {
+            Cert<O>,
+        }
+    }
+    rfc4701::{
+        zone {
+            Dhcid<O>,
+        }
+    }
     rfc6672::{
         zone {
             Dname<N>,
@@ -117,12 +172,58 @@ rdata_types! {
             Nsec3param<O>,
         }
     }
+    rfc6698::{
+        zone {
+            Tlsa<O>,
+        }
+    }
+    rfc7043::{
+        zone {
+            Eui48,
+            Eui64,
+        }
+    }
     rfc7344::{
         zone {
             Cdnskey<O>,
             Cds<O>,
         }
     }
+    rfc7477::{
+        zone {
+            Csync<O>,
+        }
+    }
+    rfc7553::{
+        zone {
+            Uri<O>,
+        }
+    }
+    rfc7929::{
+        zone {
+            Openpgpkey<O>,
+        }
+    }
+    rfc8005::{
+        zone {
+            Hip<O>,
+        }
+    }
+    rfc8162::{
+        zone {
+            Smimea<O>,
+        }
+    }
+    rfc8659::{
+        zone {
+            Caa<O>,
+        }
+    }
+    rfc8976::{
+        zone {
+            Zonemd<O>,
+        }
+    }
     svcb::{
         pseudo {
             Svcb<O, N>,
@@ -130,3 +231,89 @@ rdata_types! {
         }
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "serde", feature = "std"))]
+mod test {
+    use super::*;
+    use crate::base::iana::SvcbParamKey;
+    use crate::base::name::Dname;
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    type Data = AllRecordData<Vec<u8>, Dname<Vec<u8>>>;
+
+    fn name(s: &str) -> Dname<Vec<u8>> {
+        Dname::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn all_record_data_ser_de() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let mx: Data = Mx::new(10, name("mail.example.com")).into();
+        assert_tokens(
+            &mx.clone().compact(),
+            &[
+                Token::NewtypeVariant {
+                    name: "AllRecordData",
+                    variant: "Mx",
+                },
+                Token::Struct { name: "Mx", len: 2 },
+                Token::Str("preference"),
+                Token::U16(10),
+                Token::Str("exchange"),
+                Token::NewtypeStruct { name: "Dname" },
+                Token::ByteBuf(b"\x04mail\x07example\x03com\0"),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &mx.readable(),
+            &[
+                Token::NewtypeVariant {
+                    name: "AllRecordData",
+                    variant: "Mx",
+                },
+                Token::Struct { name: "Mx", len: 2 },
+                Token::Str("preference"),
+                Token::U16(10),
+                Token::Str("exchange"),
+                Token::NewtypeStruct { name: "Dname" },
+                Token::Str("mail.example.com"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn all_record_data_round_trip() {
+        let mut alpn = svcb::param::Alpn::new(Vec::new());
+        alpn.push("h2").unwrap();
+        let mut mandatory = svcb::param::Mandatory::new(Vec::new());
+        mandatory.push(SvcbParamKey::Alpn).unwrap();
+        let mut https = Https::new(1, name("svc.example.com"), Vec::new());
+        https.push(mandatory.into()).unwrap();
+        https.push(alpn.into()).unwrap();
+        https
+            .push::<&[u8]>(svcb::param::Port::new(8443).into())
+            .unwrap();
+
+        let records: [Data; 5] = [
+            A::from_octets(192, 0, 2, 1).into(),
+            Mx::new(10, name("mail.example.com")).into(),
+            Txt::from_octets(Vec::from(b"\x03foo\x03bar".as_ref()))
+                .unwrap()
+                .into(),
+            Svcb::new(0, name("svc.example.com"), Vec::new()).into(),
+            https.freeze().into(),
+        ];
+        for data in records {
+            let yaml = serde_yaml::to_string(&data).unwrap();
+            let parsed: Data = serde_yaml::from_str(&yaml).unwrap();
+            assert_eq!(data, parsed, "{}", yaml);
+        }
+    }
+}
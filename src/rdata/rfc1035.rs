@@ -2216,6 +2216,458 @@ impl<Builder: OctetsBuilder + EmptyBuilder> Default for TxtBuilder<Builder> {
     }
 }
 
+//------------ Wks ----------------------------------------------------------
+
+/// Wks record data.
+///
+/// Wks records describe the well known services supported by a particular
+/// protocol on a particular internet address. The services are given as a
+/// bitmap where each bit represents the port of the same number.
+///
+/// In the presentation format, the protocol and services can be given
+/// either as decimal numbers or as mnemonics. They are always displayed as
+/// numbers.
+///
+/// Only a fixed set of mnemonics is understood: `tcp` and `udp` for the
+/// protocol and, for the services, `echo`, `discard`, `daytime`,
+/// `ftp-data`, `ftp`, `ssh`, `telnet`, `smtp`, `time`, `whois`, `domain`,
+/// `tftp`, `gopher`, `finger`, `http`, `kerberos`, `pop3`, `sunrpc`,
+/// `nntp`, `ntp`, `imap`, `snmp`, `ldap`, and `https`. They are matched
+/// ignoring case. No services database such as `/etc/services` is
+/// consulted, so any other protocol or service has to be given as a
+/// number.
+///
+/// The Wks record type is defined in RFC 1035, section 3.4.2.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Wks<Octs> {
+    address: Ipv4Addr,
+    protocol: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    bitmap: Octs,
+}
+
+impl<Octs> Wks<Octs> {
+    /// Creates new Wks record data from its components.
+    ///
+    /// The function will fail if the bitmap is longer than 65,530 octets.
+    pub fn new(
+        address: Ipv4Addr,
+        protocol: u8,
+        bitmap: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            5usize
+                .checked_add(bitmap.as_ref().len())
+                .expect("long bitmap"),
+        )?;
+        Ok(unsafe { Wks::new_unchecked(address, protocol, bitmap) })
+    }
+
+    /// Creates new Wks record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        address: Ipv4Addr,
+        protocol: u8,
+        bitmap: Octs,
+    ) -> Self {
+        Wks {
+            address,
+            protocol,
+            bitmap,
+        }
+    }
+
+    /// The internet address the services are provided on.
+    pub fn address(&self) -> Ipv4Addr {
+        self.address
+    }
+
+    /// The IP protocol number of the services.
+    pub fn protocol(&self) -> u8 {
+        self.protocol
+    }
+
+    /// The raw bitmap of the services.
+    pub fn bitmap(&self) -> &Octs {
+        &self.bitmap
+    }
+
+    /// Converts the record data into the raw bitmap.
+    pub fn into_bitmap(self) -> Octs {
+        self.bitmap
+    }
+
+    /// Returns whether the service on the given port is supported.
+    pub fn serves(&self, port: u16) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let (octet, mask) = split_port(port);
+        match self.bitmap.as_ref().get(octet) {
+            Some(bits) => bits & mask != 0,
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over the ports of the supported services.
+    pub fn ports(&self) -> WksPorts
+    where
+        Octs: AsRef<[u8]>,
+    {
+        WksPorts::new(self.bitmap.as_ref())
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Wks<Target>, Target::Error> {
+        Ok(unsafe {
+            Wks::new_unchecked(
+                self.address,
+                self.protocol,
+                self.bitmap.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let address = Ipv4Addr::parse(parser)?;
+        let protocol = u8::parse(parser)?;
+        let len = parser.remaining();
+        Ok(unsafe {
+            Self::new_unchecked(address, protocol, parser.parse_octets(len)?)
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let address = scanner.scan_ascii_str(|s| {
+            Ipv4Addr::from_str(s)
+                .map_err(|_| S::Error::custom("expected IPv4 address"))
+        })?;
+        let protocol = scanner.scan_ascii_str(|s| {
+            scan_mnemonic(s, WKS_PROTOCOLS)
+                .and_then(|protocol| u8::try_from(protocol).ok())
+                .ok_or_else(|| S::Error::custom("expected protocol"))
+        })?;
+        let mut bitmap = scanner.octets_builder()?;
+        while scanner.continues() {
+            let port = scanner.scan_ascii_str(|s| {
+                scan_mnemonic(s, WKS_SERVICES)
+                    .ok_or_else(|| S::Error::custom("expected service"))
+            })?;
+            let (octet, mask) = split_port(port);
+            while bitmap.as_ref().len() <= octet {
+                bitmap
+                    .append_slice(&[0])
+                    .map_err(|_| S::Error::short_buf())?;
+            }
+            bitmap.as_mut()[octet] |= mask;
+        }
+        Self::new(address, protocol, bitmap.freeze())
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Wks<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Wks<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            address,
+            protocol,
+            bitmap,
+        } = self;
+        Ok(unsafe {
+            Wks::new_unchecked(
+                address,
+                protocol,
+                bitmap.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Wks<SrcOcts>> for Wks<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Wks<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Wks::new_unchecked(
+                source.address,
+                source.protocol,
+                Octs::try_octets_from(source.bitmap)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Wks<Other>> for Wks<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Wks<Other>) -> bool {
+        self.address == other.address
+            && self.protocol == other.protocol
+            && self.bitmap.as_ref() == other.bitmap.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Wks<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Wks<Other>> for Wks<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Wks<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Wks<Other>> for Wks<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Wks<Other>) -> Ordering {
+        match self.address.octets().cmp(&other.address.octets()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.protocol.cmp(&other.protocol) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.bitmap.as_ref().cmp(other.bitmap.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Wks<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Wks<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.address.hash(state);
+        self.protocol.hash(state);
+        self.bitmap.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Wks<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Wks
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Wks<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Wks {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Wks<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.bitmap.as_ref().len())
+                .expect("long bitmap")
+                .checked_add(4 + u8::COMPOSE_LEN)
+                .expect("long bitmap"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(&self.address.octets())?;
+        self.protocol.compose(target)?;
+        target.append_slice(self.bitmap.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Wks<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.address, self.protocol)?;
+        for port in self.ports() {
+            write!(f, " {}", port)?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Wks<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Wks")
+            .field("address", &self.address)
+            .field("protocol", &self.protocol)
+            .field("bitmap", &self.bitmap.as_ref())
+            .finish()
+    }
+}
+
+//------------ WksPorts ------------------------------------------------------
+
+/// An iterator over the ports of the services of Wks record data.
+#[derive(Clone, Debug)]
+pub struct WksPorts<'a> {
+    /// The bitmap.
+    bitmap: &'a [u8],
+
+    /// The next port to check.
+    next: usize,
+}
+
+impl<'a> WksPorts<'a> {
+    fn new(bitmap: &'a [u8]) -> Self {
+        WksPorts { bitmap, next: 0 }
+    }
+}
+
+impl<'a> Iterator for WksPorts<'a> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Bits beyond port 65535 can’t be represented and are ignored.
+        let end = core::cmp::min(self.bitmap.len() * 8, 0x1_0000);
+        while self.next < end {
+            let port = self.next as u16;
+            self.next += 1;
+            let (octet, mask) = split_port(port);
+            if self.bitmap[octet] & mask != 0 {
+                return Some(port);
+            }
+        }
+        None
+    }
+}
+
+//------------ Wks Helpers ---------------------------------------------------
+
+/// The protocol mnemonics accepted when scanning Wks record data.
+const WKS_PROTOCOLS: &[(&str, u16)] = &[("tcp", 6), ("udp", 17)];
+
+/// The service mnemonics accepted when scanning Wks record data.
+///
+/// These are the services from the list of well known ports of RFC 1700
+/// that one might still come across in the wild. The list is repeated in
+/// the documentation of [`Wks`], so keep the two in sync.
+const WKS_SERVICES: &[(&str, u16)] = &[
+    ("echo", 7),
+    ("discard", 9),
+    ("daytime", 13),
+    ("ftp-data", 20),
+    ("ftp", 21),
+    ("ssh", 22),
+    ("telnet", 23),
+    ("smtp", 25),
+    ("time", 37),
+    ("whois", 43),
+    ("domain", 53),
+    ("tftp", 69),
+    ("gopher", 70),
+    ("finger", 79),
+    ("http", 80),
+    ("kerberos", 88),
+    ("pop3", 110),
+    ("sunrpc", 111),
+    ("nntp", 119),
+    ("ntp", 123),
+    ("imap", 143),
+    ("snmp", 161),
+    ("ldap", 389),
+    ("https", 443),
+];
+
+/// Scans a decimal number or one of the given mnemonics.
+///
+/// Mnemonics are matched ignoring case.
+fn scan_mnemonic(s: &str, mnemonics: &[(&str, u16)]) -> Option<u16> {
+    if let Ok(value) = u16::from_str(s) {
+        return Some(value);
+    }
+    mnemonics
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|(_, value)| *value)
+}
+
+/// Splits a port into the octet index and the mask within the octet.
+fn split_port(port: u16) -> (usize, u8) {
+    (usize::from(port >> 3), 0x80 >> (port & 0x07))
+}
+
 //============ Error Types ===================================================
 
 //------------ TxtError ------------------------------------------------------
@@ -2524,4 +2976,25 @@ mod test {
             ],
         );
     }
+
+    //--- Wks
+
+    #[test]
+    fn wks_compose_parse_scan() {
+        let rdata = Wks::new(
+            Ipv4Addr::new(192, 0, 2, 1),
+            6,
+            b"\x00\x00\x00\x40\x00\x00\x00\x00\x00\x00\x80".as_ref(),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Wks::parse(parser));
+        test_scan(&["192.0.2.1", "6", "25", "80"], Wks::scan, &rdata);
+        test_scan(&["192.0.2.1", "TCP", "smtp", "HTTP"], Wks::scan, &rdata);
+        assert!(rdata.serves(25));
+        assert!(!rdata.serves(24));
+        assert!(!rdata.serves(1024));
+        assert_eq!(rdata.ports().collect::<Vec<_>>(), [25, 80]);
+        assert_eq!(format!("{}", rdata), "192.0.2.1 6 25 80");
+    }
 }
@@ -0,0 +1,1149 @@
+//! Record data from [RFC 1183]: AFSDB, RP, X25, ISDN, and RT records.
+//!
+//! This RFC defines a number of experimental record types. The AFSDB
+//! record locates AFS and DCE servers, the RP record names the person
+//! responsible for a domain name, and the X25, ISDN, and RT records were
+//! intended to support routing through networks other than the Internet.
+//!
+//! [RFC 1183]: https://tools.ietf.org/html/rfc1183
+
+use crate::base::charstr::CharStr;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{Dname, ParsedDname, PushError, ToDname};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::builder::{EmptyBuilder, FromBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Afsdb ---------------------------------------------------------
+
+/// AFSDB record data.
+///
+/// The AFSDB record specifies the location of an AFS cell database server
+/// or a DCE authenticated name server for the owner name. The subtype
+/// determines which of the two the host provides.
+///
+/// The AFSDB record type is defined in [RFC 1183, section 1].
+///
+/// [RFC 1183, section 1]: https://tools.ietf.org/html/rfc1183#section-1
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Afsdb<N> {
+    subtype: u16,
+    hostname: N,
+}
+
+impl<N> Afsdb<N> {
+    /// Creates new AFSDB record data from the components.
+    pub fn new(subtype: u16, hostname: N) -> Self {
+        Afsdb { subtype, hostname }
+    }
+
+    /// The subtype of the server.
+    ///
+    /// A value of 1 denotes an AFS version 3.0 volume location server, a
+    /// value of 2 a DCE authenticated name server.
+    pub fn subtype(&self) -> u16 {
+        self.subtype
+    }
+
+    /// The name of the host providing the service.
+    pub fn hostname(&self) -> &N {
+        &self.hostname
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Afsdb<Target>, Target::Error> {
+        Ok(Afsdb::new(self.subtype, self.hostname.try_octets_into()?))
+    }
+
+    pub fn scan<S: Scanner<Dname = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(u16::scan(scanner)?, scanner.scan_dname()?))
+    }
+}
+
+impl<Octs: Octets> Afsdb<ParsedDname<Octs>> {
+    pub fn flatten_into<Target>(
+        self,
+    ) -> Result<Afsdb<Dname<Target>>, PushError>
+    where
+        Target: for<'a> OctetsFrom<Octs::Range<'a>> + FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let Self { subtype, hostname } = self;
+        Ok(Afsdb::new(subtype, hostname.flatten_into()?))
+    }
+}
+
+impl<Octs> Afsdb<ParsedDname<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, ParsedDname::parse(parser)?))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Name, SrcName> OctetsFrom<Afsdb<SrcName>> for Afsdb<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Afsdb<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Afsdb::new(
+            source.subtype,
+            Name::try_octets_from(source.hostname)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Afsdb<NN>> for Afsdb<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn eq(&self, other: &Afsdb<NN>) -> bool {
+        self.subtype == other.subtype
+            && self.hostname.name_eq(&other.hostname)
+    }
+}
+
+impl<N: ToDname> Eq for Afsdb<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Afsdb<NN>> for Afsdb<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn partial_cmp(&self, other: &Afsdb<NN>) -> Option<Ordering> {
+        match self.subtype.partial_cmp(&other.subtype) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.hostname.name_cmp(&other.hostname))
+    }
+}
+
+impl<N: ToDname> Ord for Afsdb<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.subtype.cmp(&other.subtype) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.hostname.name_cmp(&other.hostname)
+    }
+}
+
+impl<N: ToDname, NN: ToDname> CanonicalOrd<Afsdb<NN>> for Afsdb<N> {
+    fn canonical_cmp(&self, other: &Afsdb<NN>) -> Ordering {
+        match self.subtype.cmp(&other.subtype) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.hostname.lowercase_composed_cmp(&other.hostname)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Afsdb<N> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Afsdb
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Afsdb<ParsedDname<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Afsdb {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToDname> ComposeRecordData for Afsdb<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // AFSDB records are not compressed.
+        Some(u16::COMPOSE_LEN + self.hostname.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.subtype.compose(target)?;
+        self.hostname.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... but the name is lowercased for the canonical form.
+        self.subtype.compose(target)?;
+        self.hostname.compose_canonical(target)
+    }
+}
+
+//--- Display
+
+impl<N: fmt::Display> fmt::Display for Afsdb<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}.", self.subtype, self.hostname)
+    }
+}
+
+//------------ Rp ------------------------------------------------------------
+
+/// RP record data.
+///
+/// The RP record identifies the person responsible for the owner name. It
+/// contains the mailbox of that person encoded as a domain name and the
+/// name of a TXT record with further information. Either can be the root
+/// name to indicate that it is not available.
+///
+/// The RP record type is defined in [RFC 1183, section 2.2].
+///
+/// [RFC 1183, section 2.2]: https://tools.ietf.org/html/rfc1183#section-2.2
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rp<N> {
+    mbox: N,
+    txt: N,
+}
+
+impl<N> Rp<N> {
+    /// Creates new RP record data from the components.
+    pub fn new(mbox: N, txt: N) -> Self {
+        Rp { mbox, txt }
+    }
+
+    /// The mailbox of the responsible person.
+    pub fn mbox(&self) -> &N {
+        &self.mbox
+    }
+
+    /// The name of the TXT records with further information.
+    pub fn txt(&self) -> &N {
+        &self.txt
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Rp<Target>, Target::Error> {
+        Ok(Rp::new(
+            self.mbox.try_octets_into()?,
+            self.txt.try_octets_into()?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Dname = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(scanner.scan_dname()?, scanner.scan_dname()?))
+    }
+}
+
+impl<Octs: Octets> Rp<ParsedDname<Octs>> {
+    pub fn flatten_into<Target>(self) -> Result<Rp<Dname<Target>>, PushError>
+    where
+        Target: for<'a> OctetsFrom<Octs::Range<'a>> + FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let Self { mbox, txt } = self;
+        Ok(Rp::new(mbox.flatten_into()?, txt.flatten_into()?))
+    }
+}
+
+impl<Octs> Rp<ParsedDname<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            ParsedDname::parse(parser)?,
+            ParsedDname::parse(parser)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Name, SrcName> OctetsFrom<Rp<SrcName>> for Rp<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Rp<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Rp::new(
+            Name::try_octets_from(source.mbox)?,
+            Name::try_octets_from(source.txt)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Rp<NN>> for Rp<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn eq(&self, other: &Rp<NN>) -> bool {
+        self.mbox.name_eq(&other.mbox) && self.txt.name_eq(&other.txt)
+    }
+}
+
+impl<N: ToDname> Eq for Rp<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Rp<NN>> for Rp<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn partial_cmp(&self, other: &Rp<NN>) -> Option<Ordering> {
+        match self.mbox.name_cmp(&other.mbox) {
+            Ordering::Equal => {}
+            other => return Some(other),
+        }
+        Some(self.txt.name_cmp(&other.txt))
+    }
+}
+
+impl<N: ToDname> Ord for Rp<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.mbox.name_cmp(&other.mbox) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.txt.name_cmp(&other.txt)
+    }
+}
+
+impl<N: ToDname, NN: ToDname> CanonicalOrd<Rp<NN>> for Rp<N> {
+    fn canonical_cmp(&self, other: &Rp<NN>) -> Ordering {
+        match self.mbox.lowercase_composed_cmp(&other.mbox) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.txt.lowercase_composed_cmp(&other.txt)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Rp<N> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Rp
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Rp<ParsedDname<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Rp {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToDname> ComposeRecordData for Rp<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // RP records are not compressed.
+        Some(self.mbox.compose_len() + self.txt.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.mbox.compose(target)?;
+        self.txt.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... but the names are lowercased for the canonical form.
+        self.mbox.compose_canonical(target)?;
+        self.txt.compose_canonical(target)
+    }
+}
+
+//--- Display
+
+impl<N: fmt::Display> fmt::Display for Rp<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}. {}.", self.mbox, self.txt)
+    }
+}
+
+//------------ X25 -----------------------------------------------------------
+
+/// X25 record data.
+///
+/// The X25 record contains the PSDN address of the owner name in the X.25
+/// network. The address is a character string of decimal digits starting
+/// with the four digit DNIC.
+///
+/// The X25 record type is defined in [RFC 1183, section 3.1].
+///
+/// [RFC 1183, section 3.1]: https://tools.ietf.org/html/rfc1183#section-3.1
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "Octs: AsRef<[u8]> + octseq::serde::SerializeOctets",
+        deserialize = "Octs: \
+                FromBuilder \
+                + octseq::serde::DeserializeOctets<'de>, \
+            <Octs as FromBuilder>::Builder: AsRef<[u8]> + EmptyBuilder ",
+    ))
+)]
+pub struct X25<Octs> {
+    psdn_address: CharStr<Octs>,
+}
+
+impl<Octs> X25<Octs> {
+    /// Creates new X25 record data from the PSDN address.
+    pub fn new(psdn_address: CharStr<Octs>) -> Self {
+        X25 { psdn_address }
+    }
+
+    /// The PSDN address of the owner.
+    pub fn psdn_address(&self) -> &CharStr<Octs> {
+        &self.psdn_address
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<X25<Target>, Target::Error> {
+        Ok(X25::new(self.psdn_address.try_octets_into()?))
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(CharStr::parse(parser)?))
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(scanner.scan_charstr()?))
+    }
+}
+
+impl<SrcOcts> X25<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<X25<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        Ok(X25::new(
+            self.psdn_address.try_octets_into().map_err(Into::into)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<X25<SrcOcts>> for X25<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: X25<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(X25::new(CharStr::try_octets_from(source.psdn_address)?))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<X25<Other>> for X25<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &X25<Other>) -> bool {
+        self.psdn_address.eq(&other.psdn_address)
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for X25<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<X25<Other>> for X25<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &X25<Other>) -> Option<Ordering> {
+        self.psdn_address.partial_cmp(&other.psdn_address)
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<X25<Other>> for X25<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &X25<Other>) -> Ordering {
+        self.psdn_address.canonical_cmp(&other.psdn_address)
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for X25<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.psdn_address.cmp(&other.psdn_address)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for X25<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.psdn_address.hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for X25<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::X25
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for X25<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::X25 {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for X25<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(self.psdn_address.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.psdn_address.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for X25<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.psdn_address.fmt(f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for X25<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("X25")
+            .field("psdn_address", &self.psdn_address)
+            .finish()
+    }
+}
+
+//------------ Isdn ----------------------------------------------------------
+
+/// ISDN record data.
+///
+/// The ISDN record contains the ISDN number of the owner name and,
+/// optionally, a subaddress. Both are character strings of decimal digits.
+///
+/// The ISDN record type is defined in [RFC 1183, section 3.2].
+///
+/// [RFC 1183, section 3.2]: https://tools.ietf.org/html/rfc1183#section-3.2
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "Octs: AsRef<[u8]> + octseq::serde::SerializeOctets",
+        deserialize = "Octs: \
+                FromBuilder \
+                + octseq::serde::DeserializeOctets<'de>, \
+            <Octs as FromBuilder>::Builder: AsRef<[u8]> + EmptyBuilder ",
+    ))
+)]
+pub struct Isdn<Octs> {
+    address: CharStr<Octs>,
+    subaddress: Option<CharStr<Octs>>,
+}
+
+impl<Octs> Isdn<Octs> {
+    /// Creates new ISDN record data from the components.
+    pub fn new(
+        address: CharStr<Octs>,
+        subaddress: Option<CharStr<Octs>>,
+    ) -> Self {
+        Isdn {
+            address,
+            subaddress,
+        }
+    }
+
+    /// The ISDN number of the owner.
+    pub fn address(&self) -> &CharStr<Octs> {
+        &self.address
+    }
+
+    /// The subaddress if present.
+    pub fn subaddress(&self) -> Option<&CharStr<Octs>> {
+        self.subaddress.as_ref()
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Isdn<Target>, Target::Error> {
+        Ok(Isdn::new(
+            self.address.try_octets_into()?,
+            self.subaddress.map(|sa| sa.try_octets_into()).transpose()?,
+        ))
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let address = CharStr::parse(parser)?;
+        let subaddress = if parser.remaining() > 0 {
+            Some(CharStr::parse(parser)?)
+        } else {
+            None
+        };
+        Ok(Self::new(address, subaddress))
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        let address = scanner.scan_charstr()?;
+        let subaddress = if scanner.continues() {
+            Some(scanner.scan_charstr()?)
+        } else {
+            None
+        };
+        Ok(Self::new(address, subaddress))
+    }
+}
+
+impl<SrcOcts> Isdn<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Isdn<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            address,
+            subaddress,
+        } = self;
+        Ok(Isdn::new(
+            address.try_octets_into().map_err(Into::into)?,
+            subaddress
+                .map(|sa| sa.try_octets_into())
+                .transpose()
+                .map_err(Into::into)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Isdn<SrcOcts>> for Isdn<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Isdn<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(Isdn::new(
+            CharStr::try_octets_from(source.address)?,
+            source
+                .subaddress
+                .map(CharStr::try_octets_from)
+                .transpose()?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Isdn<Other>> for Isdn<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Isdn<Other>) -> bool {
+        self.address.eq(&other.address)
+            && match (self.subaddress.as_ref(), other.subaddress.as_ref()) {
+                (Some(left), Some(right)) => left.eq(right),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Isdn<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Isdn<Other>> for Isdn<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Isdn<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Isdn<Other>> for Isdn<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Isdn<Other>) -> Ordering {
+        match self.address.canonical_cmp(&other.address) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match (self.subaddress.as_ref(), other.subaddress.as_ref()) {
+            (Some(left), Some(right)) => left.canonical_cmp(right),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Isdn<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Isdn<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.address.hash(state);
+        self.subaddress.hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Isdn<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Isdn
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Isdn<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Isdn {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Isdn<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            self.address.compose_len()
+                + self
+                    .subaddress
+                    .as_ref()
+                    .map(CharStr::compose_len)
+                    .unwrap_or(0),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.address.compose(target)?;
+        if let Some(subaddress) = self.subaddress.as_ref() {
+            subaddress.compose(target)?;
+        }
+        Ok(())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Isdn<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.address.fmt(f)?;
+        if let Some(subaddress) = self.subaddress.as_ref() {
+            write!(f, " {}", subaddress)?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Isdn<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Isdn")
+            .field("address", &self.address)
+            .field("subaddress", &self.subaddress)
+            .finish()
+    }
+}
+
+//------------ Rt ------------------------------------------------------------
+
+/// RT record data.
+///
+/// The RT record specifies an intermediate host through which a host
+/// without a direct connection to the wide area network can be reached.
+/// Its structure is identical to that of the MX record but the domain
+/// name is never compressed.
+///
+/// The RT record type is defined in [RFC 1183, section 3.3].
+///
+/// [RFC 1183, section 3.3]: https://tools.ietf.org/html/rfc1183#section-3.3
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rt<N> {
+    preference: u16,
+    intermediate: N,
+}
+
+impl<N> Rt<N> {
+    /// Creates new RT record data from the components.
+    pub fn new(preference: u16, intermediate: N) -> Self {
+        Rt {
+            preference,
+            intermediate,
+        }
+    }
+
+    /// The preference for this record.
+    ///
+    /// Defines an order if there are several RT records for the same
+    /// owner. Lower values are preferred.
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The name of the intermediate host.
+    pub fn intermediate(&self) -> &N {
+        &self.intermediate
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Rt<Target>, Target::Error> {
+        Ok(Rt::new(
+            self.preference,
+            self.intermediate.try_octets_into()?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Dname = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(u16::scan(scanner)?, scanner.scan_dname()?))
+    }
+}
+
+impl<Octs: Octets> Rt<ParsedDname<Octs>> {
+    pub fn flatten_into<Target>(self) -> Result<Rt<Dname<Target>>, PushError>
+    where
+        Target: for<'a> OctetsFrom<Octs::Range<'a>> + FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let Self {
+            preference,
+            intermediate,
+        } = self;
+        Ok(Rt::new(preference, intermediate.flatten_into()?))
+    }
+}
+
+impl<Octs> Rt<ParsedDname<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, ParsedDname::parse(parser)?))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Name, SrcName> OctetsFrom<Rt<SrcName>> for Rt<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Rt<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Rt::new(
+            source.preference,
+            Name::try_octets_from(source.intermediate)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Rt<NN>> for Rt<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn eq(&self, other: &Rt<NN>) -> bool {
+        self.preference == other.preference
+            && self.intermediate.name_eq(&other.intermediate)
+    }
+}
+
+impl<N: ToDname> Eq for Rt<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Rt<NN>> for Rt<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn partial_cmp(&self, other: &Rt<NN>) -> Option<Ordering> {
+        match self.preference.partial_cmp(&other.preference) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.intermediate.name_cmp(&other.intermediate))
+    }
+}
+
+impl<N: ToDname> Ord for Rt<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.intermediate.name_cmp(&other.intermediate)
+    }
+}
+
+impl<N: ToDname, NN: ToDname> CanonicalOrd<Rt<NN>> for Rt<N> {
+    fn canonical_cmp(&self, other: &Rt<NN>) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.intermediate
+            .lowercase_composed_cmp(&other.intermediate)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Rt<N> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Rt
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Rt<ParsedDname<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Rt {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToDname> ComposeRecordData for Rt<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // RT records are not compressed.
+        Some(u16::COMPOSE_LEN + self.intermediate.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        self.intermediate.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... but the name is lowercased for the canonical form.
+        self.preference.compose(target)?;
+        self.intermediate.compose_canonical(target)
+    }
+}
+
+//--- Display
+
+impl<N: fmt::Display> fmt::Display for Rt<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}.", self.preference, self.intermediate)
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    fn name(s: &str) -> Dname<Vec<u8>> {
+        Dname::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn afsdb_compose_parse_scan() {
+        let rdata = Afsdb::new(1, name("afs.example.com"));
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Afsdb::parse(parser));
+        test_scan(&["1", "afs.example.com"], Afsdb::scan, &rdata);
+    }
+
+    #[test]
+    fn rp_compose_parse_scan() {
+        let rdata =
+            Rp::new(name("admin.example.com"), name("info.example.com"));
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Rp::parse(parser));
+        test_scan(
+            &["admin.example.com", "info.example.com"],
+            Rp::scan,
+            &rdata,
+        );
+    }
+
+    #[test]
+    fn rp_canonical() {
+        let mut buf = Vec::new();
+        Rp::new(name("Admin.Example.com"), name("."))
+            .compose_canonical_rdata(&mut buf)
+            .unwrap();
+        assert_eq!(buf.as_slice(), b"\x05admin\x07example\x03com\x00\x00");
+    }
+
+    #[test]
+    fn x25_compose_parse_scan() {
+        let rdata = X25::new(CharStr::from_octets("311061700956").unwrap());
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| X25::parse(parser));
+        test_scan(&["311061700956"], X25::scan, &rdata);
+    }
+
+    #[test]
+    fn isdn_compose_parse_scan() {
+        let rdata =
+            Isdn::new(CharStr::from_octets("150862028003217").unwrap(), None);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Isdn::parse(parser));
+        test_scan(&["150862028003217"], Isdn::scan, &rdata);
+
+        let rdata = Isdn::new(
+            CharStr::from_octets("150862028003217").unwrap(),
+            Some(CharStr::from_octets("004").unwrap()),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Isdn::parse(parser));
+        test_scan(&["150862028003217", "004"], Isdn::scan, &rdata);
+        assert_eq!(rdata.to_string(), "150862028003217 004");
+    }
+
+    #[test]
+    fn rt_compose_parse_scan() {
+        let rdata = Rt::new(10, name("relay.example.com"));
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Rt::parse(parser));
+        test_scan(&["10", "relay.example.com"], Rt::scan, &rdata);
+    }
+}
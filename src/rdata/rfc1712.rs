@@ -0,0 +1,307 @@
+//! Record data from [RFC 1712]: GPOS records.
+//!
+//! This RFC defines the GPOS record type for the geographical position of
+//! a host. It has been superseded by the LOC record type defined in
+//! [RFC 1876].
+//!
+//! [RFC 1712]: https://tools.ietf.org/html/rfc1712
+//! [RFC 1876]: https://tools.ietf.org/html/rfc1876
+
+use crate::base::charstr::CharStr;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::PushError;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::Scanner;
+use crate::base::wire::{Composer, ParseError};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "serde")]
+use octseq::builder::{EmptyBuilder, FromBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Gpos ----------------------------------------------------------
+
+/// GPOS record data.
+///
+/// The GPOS record contains the geographical position of the owner name as
+/// three character strings holding the longitude and latitude in degrees
+/// and the altitude in meters as decimal numbers.
+///
+/// The GPOS record type is defined in [RFC 1712].
+///
+/// [RFC 1712]: https://tools.ietf.org/html/rfc1712
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "Octs: AsRef<[u8]> + octseq::serde::SerializeOctets",
+        deserialize = "Octs: \
+                FromBuilder \
+                + octseq::serde::DeserializeOctets<'de>, \
+            <Octs as FromBuilder>::Builder: AsRef<[u8]> + EmptyBuilder ",
+    ))
+)]
+pub struct Gpos<Octs> {
+    longitude: CharStr<Octs>,
+    latitude: CharStr<Octs>,
+    altitude: CharStr<Octs>,
+}
+
+impl<Octs> Gpos<Octs> {
+    /// Creates new GPOS record data from the components.
+    pub fn new(
+        longitude: CharStr<Octs>,
+        latitude: CharStr<Octs>,
+        altitude: CharStr<Octs>,
+    ) -> Self {
+        Gpos {
+            longitude,
+            latitude,
+            altitude,
+        }
+    }
+
+    /// The longitude in degrees.
+    ///
+    /// Positive values are east of the prime meridian.
+    pub fn longitude(&self) -> &CharStr<Octs> {
+        &self.longitude
+    }
+
+    /// The latitude in degrees.
+    ///
+    /// Positive values are north of the equator.
+    pub fn latitude(&self) -> &CharStr<Octs> {
+        &self.latitude
+    }
+
+    /// The altitude in meters.
+    pub fn altitude(&self) -> &CharStr<Octs> {
+        &self.altitude
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Gpos<Target>, Target::Error> {
+        Ok(Gpos::new(
+            self.longitude.try_octets_into()?,
+            self.latitude.try_octets_into()?,
+            self.altitude.try_octets_into()?,
+        ))
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            CharStr::parse(parser)?,
+            CharStr::parse(parser)?,
+            CharStr::parse(parser)?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(
+            scanner.scan_charstr()?,
+            scanner.scan_charstr()?,
+            scanner.scan_charstr()?,
+        ))
+    }
+}
+
+impl<SrcOcts> Gpos<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Gpos<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            longitude,
+            latitude,
+            altitude,
+        } = self;
+        Ok(Gpos::new(
+            longitude.try_octets_into().map_err(Into::into)?,
+            latitude.try_octets_into().map_err(Into::into)?,
+            altitude.try_octets_into().map_err(Into::into)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Gpos<SrcOcts>> for Gpos<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Gpos<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(Gpos::new(
+            CharStr::try_octets_from(source.longitude)?,
+            CharStr::try_octets_from(source.latitude)?,
+            CharStr::try_octets_from(source.altitude)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Gpos<Other>> for Gpos<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Gpos<Other>) -> bool {
+        self.longitude.eq(&other.longitude)
+            && self.latitude.eq(&other.latitude)
+            && self.altitude.eq(&other.altitude)
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Gpos<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Gpos<Other>> for Gpos<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Gpos<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Gpos<Other>> for Gpos<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Gpos<Other>) -> Ordering {
+        match self.longitude.canonical_cmp(&other.longitude) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.latitude.canonical_cmp(&other.latitude) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.altitude.canonical_cmp(&other.altitude)
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Gpos<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Gpos<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.longitude.hash(state);
+        self.latitude.hash(state);
+        self.altitude.hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Gpos<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Gpos
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Gpos<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Gpos {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Gpos<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            self.longitude.compose_len()
+                + self.latitude.compose_len()
+                + self.altitude.compose_len(),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.longitude.compose(target)?;
+        self.latitude.compose(target)?;
+        self.altitude.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Gpos<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.longitude, self.latitude, self.altitude)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Gpos<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Gpos")
+            .field("longitude", &self.longitude)
+            .field("latitude", &self.latitude)
+            .field("altitude", &self.altitude)
+            .finish()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn gpos_compose_parse_scan() {
+        let rdata = Gpos::new(
+            CharStr::from_octets("4.8923").unwrap(),
+            CharStr::from_octets("52.3731").unwrap(),
+            CharStr::from_octets("-2.0").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Gpos::parse(parser));
+        test_scan(&["4.8923", "52.3731", "-2.0"], Gpos::scan, &rdata);
+        assert_eq!(rdata.to_string(), "4.8923 52.3731 -2.0");
+    }
+}
@@ -0,0 +1,288 @@
+//! Record data from [RFC 2163]: PX records.
+//!
+//! This RFC defines the PX record type used to map between RFC 822 mail
+//! domains and X.400 addresses.
+//!
+//! [RFC 2163]: https://tools.ietf.org/html/rfc2163
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{Dname, ParsedDname, PushError, ToDname};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::fmt;
+use octseq::builder::{EmptyBuilder, FromBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Px ------------------------------------------------------------
+
+/// PX record data.
+///
+/// The PX record maps an RFC 822 mail domain to an X.400 address space
+/// and vice versa. Both the RFC 822 domain and the X.400 address space are
+/// encoded as domain names.
+///
+/// The PX record type is defined in [RFC 2163, section 4].
+///
+/// [RFC 2163, section 4]: https://tools.ietf.org/html/rfc2163#section-4
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Px<N> {
+    preference: u16,
+    map822: N,
+    mapx400: N,
+}
+
+impl<N> Px<N> {
+    /// Creates new PX record data from the components.
+    pub fn new(preference: u16, map822: N, mapx400: N) -> Self {
+        Px {
+            preference,
+            map822,
+            mapx400,
+        }
+    }
+
+    /// The preference for this record.
+    ///
+    /// Defines an order if there are several PX records for the same
+    /// owner. Lower values are preferred.
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The RFC 822 part of the mapping.
+    pub fn map822(&self) -> &N {
+        &self.map822
+    }
+
+    /// The X.400 part of the mapping.
+    pub fn mapx400(&self) -> &N {
+        &self.mapx400
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Px<Target>, Target::Error> {
+        Ok(Px::new(
+            self.preference,
+            self.map822.try_octets_into()?,
+            self.mapx400.try_octets_into()?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Dname = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(
+            u16::scan(scanner)?,
+            scanner.scan_dname()?,
+            scanner.scan_dname()?,
+        ))
+    }
+}
+
+impl<Octs: Octets> Px<ParsedDname<Octs>> {
+    pub fn flatten_into<Target>(self) -> Result<Px<Dname<Target>>, PushError>
+    where
+        Target: for<'a> OctetsFrom<Octs::Range<'a>> + FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let Self {
+            preference,
+            map822,
+            mapx400,
+        } = self;
+        Ok(Px::new(
+            preference,
+            map822.flatten_into()?,
+            mapx400.flatten_into()?,
+        ))
+    }
+}
+
+impl<Octs> Px<ParsedDname<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            u16::parse(parser)?,
+            ParsedDname::parse(parser)?,
+            ParsedDname::parse(parser)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Name, SrcName> OctetsFrom<Px<SrcName>> for Px<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Px<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Px::new(
+            source.preference,
+            Name::try_octets_from(source.map822)?,
+            Name::try_octets_from(source.mapx400)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Px<NN>> for Px<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn eq(&self, other: &Px<NN>) -> bool {
+        self.preference == other.preference
+            && self.map822.name_eq(&other.map822)
+            && self.mapx400.name_eq(&other.mapx400)
+    }
+}
+
+impl<N: ToDname> Eq for Px<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Px<NN>> for Px<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn partial_cmp(&self, other: &Px<NN>) -> Option<Ordering> {
+        match self.preference.partial_cmp(&other.preference) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.map822.name_cmp(&other.map822) {
+            Ordering::Equal => {}
+            other => return Some(other),
+        }
+        Some(self.mapx400.name_cmp(&other.mapx400))
+    }
+}
+
+impl<N: ToDname> Ord for Px<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.map822.name_cmp(&other.map822) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.mapx400.name_cmp(&other.mapx400)
+    }
+}
+
+impl<N: ToDname, NN: ToDname> CanonicalOrd<Px<NN>> for Px<N> {
+    fn canonical_cmp(&self, other: &Px<NN>) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.map822.lowercase_composed_cmp(&other.map822) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.mapx400.lowercase_composed_cmp(&other.mapx400)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Px<N> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Px
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Px<ParsedDname<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Px {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToDname> ComposeRecordData for Px<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // PX records are not compressed.
+        Some(
+            u16::COMPOSE_LEN
+                + self.map822.compose_len()
+                + self.mapx400.compose_len(),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        self.map822.compose(target)?;
+        self.mapx400.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... but the names are lowercased for the canonical form.
+        self.preference.compose(target)?;
+        self.map822.compose_canonical(target)?;
+        self.mapx400.compose_canonical(target)
+    }
+}
+
+//--- Display
+
+impl<N: fmt::Display> fmt::Display for Px<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}. {}.", self.preference, self.map822, self.mapx400)
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    #[test]
+    fn px_compose_parse_scan() {
+        let rdata = Px::new(
+            10,
+            Dname::<Vec<u8>>::from_str("ab.net2.it").unwrap(),
+            Dname::<Vec<u8>>::from_str("o-ab.prmd-net2.admdb.c-it").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Px::parse(parser));
+        test_scan(
+            &["10", "ab.net2.it", "o-ab.prmd-net2.admdb.c-it"],
+            Px::scan,
+            &rdata,
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Record data from [RFC 2230]: KX records.
+//!
+//! This RFC defines the KX record type which points to hosts willing to
+//! act as key exchanger for a domain.
+//!
+//! [RFC 2230]: https://tools.ietf.org/html/rfc2230
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{Dname, ParsedDname, PushError, ToDname};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::fmt;
+use octseq::builder::{EmptyBuilder, FromBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Kx ------------------------------------------------------------
+
+/// KX record data.
+///
+/// The KX record specifies a host willing to act as a key exchanger for
+/// the owner name. Its structure is identical to that of the MX record
+/// but the domain name is never compressed.
+///
+/// The KX record type is defined in [RFC 2230].
+///
+/// [RFC 2230]: https://tools.ietf.org/html/rfc2230
+#[derive(Clone, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kx<N> {
+    preference: u16,
+    exchanger: N,
+}
+
+impl<N> Kx<N> {
+    /// Creates new KX record data from the components.
+    pub fn new(preference: u16, exchanger: N) -> Self {
+        Kx {
+            preference,
+            exchanger,
+        }
+    }
+
+    /// The preference for this record.
+    ///
+    /// Defines an order if there are several KX records for the same
+    /// owner. Lower values are preferred.
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The name of the host that is the key exchanger.
+    pub fn exchanger(&self) -> &N {
+        &self.exchanger
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<N>>(
+        self,
+    ) -> Result<Kx<Target>, Target::Error> {
+        Ok(Kx::new(self.preference, self.exchanger.try_octets_into()?))
+    }
+
+    pub fn scan<S: Scanner<Dname = N>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(u16::scan(scanner)?, scanner.scan_dname()?))
+    }
+}
+
+impl<Octs: Octets> Kx<ParsedDname<Octs>> {
+    pub fn flatten_into<Target>(self) -> Result<Kx<Dname<Target>>, PushError>
+    where
+        Target: for<'a> OctetsFrom<Octs::Range<'a>> + FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let Self {
+            preference,
+            exchanger,
+        } = self;
+        Ok(Kx::new(preference, exchanger.flatten_into()?))
+    }
+}
+
+impl<Octs> Kx<ParsedDname<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(u16::parse(parser)?, ParsedDname::parse(parser)?))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Name, SrcName> OctetsFrom<Kx<SrcName>> for Kx<Name>
+where
+    Name: OctetsFrom<SrcName>,
+{
+    type Error = Name::Error;
+
+    fn try_octets_from(source: Kx<SrcName>) -> Result<Self, Self::Error> {
+        Ok(Kx::new(
+            source.preference,
+            Name::try_octets_from(source.exchanger)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<N, NN> PartialEq<Kx<NN>> for Kx<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn eq(&self, other: &Kx<NN>) -> bool {
+        self.preference == other.preference
+            && self.exchanger.name_eq(&other.exchanger)
+    }
+}
+
+impl<N: ToDname> Eq for Kx<N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<N, NN> PartialOrd<Kx<NN>> for Kx<N>
+where
+    N: ToDname,
+    NN: ToDname,
+{
+    fn partial_cmp(&self, other: &Kx<NN>) -> Option<Ordering> {
+        match self.preference.partial_cmp(&other.preference) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.exchanger.name_cmp(&other.exchanger))
+    }
+}
+
+impl<N: ToDname> Ord for Kx<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.exchanger.name_cmp(&other.exchanger)
+    }
+}
+
+impl<N: ToDname, NN: ToDname> CanonicalOrd<Kx<NN>> for Kx<N> {
+    fn canonical_cmp(&self, other: &Kx<NN>) -> Ordering {
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.exchanger.lowercase_composed_cmp(&other.exchanger)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<N> RecordData for Kx<N> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Kx
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Kx<ParsedDname<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Kx {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Name: ToDname> ComposeRecordData for Kx<Name> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // KX records are not compressed.
+        Some(u16::COMPOSE_LEN + self.exchanger.compose_len())
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.preference.compose(target)?;
+        self.exchanger.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // ... but the name is lowercased for the canonical form.
+        self.preference.compose(target)?;
+        self.exchanger.compose_canonical(target)
+    }
+}
+
+//--- Display
+
+impl<N: fmt::Display> fmt::Display for Kx<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}.", self.preference, self.exchanger)
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    #[test]
+    fn kx_compose_parse_scan() {
+        let rdata = Kx::new(
+            10,
+            Dname::<Vec<u8>>::from_str("kx.example.com").unwrap(),
+        );
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Kx::parse(parser));
+        test_scan(&["10", "kx.example.com"], Kx::scan, &rdata);
+    }
+}
@@ -0,0 +1,929 @@
+//! Record data from [RFC 3123]: APL records.
+//!
+//! This RFC defines the APL record type which contains a list of address
+//! prefixes. Each prefix can be negated, which allows using the list to
+//! describe which addresses are permitted and which are not.
+//!
+//! [RFC 3123]: https://tools.ietf.org/html/rfc3123
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{AddressFamily, Rtype};
+use crate::base::name::PushError;
+use crate::base::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, FormError, ParseError};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::str::FromStr;
+use core::{fmt, hash};
+use octseq::builder::{EmptyBuilder, FreezeBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Apl -----------------------------------------------------------
+
+/// APL record data.
+///
+/// APL records contain a list of address prefixes, each of which may be
+/// negated. The record data is kept in its wire format and the prefixes
+/// can be accessed via [`iter`][Self::iter]. Use [`AplBuilder`] to create
+/// new record data from individual prefixes.
+///
+/// In the presentation format, each prefix is given as the address family
+/// number, a colon, the address, a slash, and the prefix length, preceded
+/// by an exclamation mark if the prefix is negated, e.g.,
+/// `1:192.168.32.0/21 !1:192.168.38.0/28`. Since there is no address
+/// format for families other than IPv4 and IPv6, their address part is
+/// given in hex, e.g., `5:C0FFEE/24`.
+///
+/// The [`permits`][Self::permits] method evaluates the list for a given
+/// address.
+///
+/// The APL record type is defined in [RFC 3123].
+///
+/// [RFC 3123]: https://tools.ietf.org/html/rfc3123
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Apl<Octs> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    items: Octs,
+}
+
+impl<Octs> Apl<Octs> {
+    /// Creates new APL record data from its encoded content.
+    pub fn from_octets(octets: Octs) -> Result<Self, AplError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::check_slice(octets.as_ref())?;
+        Ok(unsafe { Apl::from_octets_unchecked(octets) })
+    }
+
+    /// Creates new APL record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The passed octets must contain correctly encoded APL record data,
+    /// that is a sequence of encoded address prefix items, and must be at
+    /// most 65,535 octets long.
+    pub unsafe fn from_octets_unchecked(octets: Octs) -> Self {
+        Apl { items: octets }
+    }
+
+    /// Returns a reference to the encoded items.
+    pub fn as_octets(&self) -> &Octs {
+        &self.items
+    }
+
+    /// Converts the record data into the encoded items.
+    pub fn into_octets(self) -> Octs {
+        self.items
+    }
+
+    /// Returns an iterator over the address prefix items.
+    pub fn iter(&self) -> AplIter
+    where
+        Octs: AsRef<[u8]>,
+    {
+        AplIter(self.items.as_ref())
+    }
+
+    /// Returns whether the list is empty.
+    pub fn is_empty(&self) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.items.as_ref().is_empty()
+    }
+
+    /// Returns the item with the longest prefix that contains `addr`.
+    ///
+    /// If there are several such items with the same prefix length, a
+    /// negated item is preferred. Returns `None` if no item contains the
+    /// address.
+    pub fn longest_match(&self, addr: IpAddr) -> Option<AplItem>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let mut res: Option<AplItem> = None;
+        for item in self.iter().filter(|item| item.contains(addr)) {
+            match res {
+                Some(best) if best.prefix > item.prefix => {}
+                Some(best)
+                    if best.prefix == item.prefix
+                        && (best.negation || !item.negation) => {}
+                _ => res = Some(item),
+            }
+        }
+        res
+    }
+
+    /// Returns whether the list permits the given address.
+    ///
+    /// The address is permitted if the item with the longest prefix that
+    /// contains the address is not negated. If no item contains the
+    /// address at all, it is not permitted.
+    pub fn permits(&self, addr: IpAddr) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.longest_match(addr)
+            .map(|item| !item.negation())
+            .unwrap_or(false)
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Apl<Target>, Target::Error> {
+        Ok(unsafe {
+            Apl::from_octets_unchecked(self.items.try_octets_into()?)
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let len = parser.remaining();
+        let items = parser.parse_octets(len)?;
+        Self::check_slice(items.as_ref()).map_err(FormError::from)?;
+        Ok(unsafe { Apl::from_octets_unchecked(items) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let mut builder = AplBuilder {
+            builder: scanner.octets_builder()?,
+        };
+        while scanner.continues() {
+            let item = scanner.scan_ascii_str(|s| {
+                scan_item(s)
+                    .ok_or_else(|| S::Error::custom("expected APL item"))
+            })?;
+            let res = match item {
+                ScannedItem::Addr(negation, addr, prefix) => {
+                    builder.push_addr(negation, addr, prefix)
+                }
+                ScannedItem::Other {
+                    negation,
+                    family,
+                    prefix,
+                    afdpart,
+                    len,
+                } => AplItem::new(family, prefix, negation, &afdpart[..len])
+                    .and_then(|item| builder.push(&item)),
+            };
+            res.map_err(|err| S::Error::custom(err.as_str()))?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Checks that a slice contains correctly encoded APL data.
+    fn check_slice(mut slice: &[u8]) -> Result<(), AplError> {
+        LongRecordData::check_len(slice.len())?;
+        while !slice.is_empty() {
+            slice = AplItem::split_from(slice)?.1;
+        }
+        Ok(())
+    }
+}
+
+impl<SrcOcts> Apl<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Apl<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        Ok(unsafe {
+            Apl::from_octets_unchecked(
+                self.items.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Apl<SrcOcts>> for Apl<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Apl<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Apl::from_octets_unchecked(Octs::try_octets_from(source.items)?)
+        })
+    }
+}
+
+//--- IntoIterator
+
+impl<'a, Octs: AsRef<[u8]>> IntoIterator for &'a Apl<Octs> {
+    type Item = AplItem<'a>;
+    type IntoIter = AplIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Apl<Other>> for Apl<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Apl<Other>) -> bool {
+        self.items.as_ref().eq(other.items.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Apl<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Apl<Other>> for Apl<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Apl<Other>) -> Option<Ordering> {
+        self.items.as_ref().partial_cmp(other.items.as_ref())
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Apl<Other>> for Apl<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Apl<Other>) -> Ordering {
+        self.items.as_ref().cmp(other.items.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Apl<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.items.as_ref().cmp(other.items.as_ref())
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Apl<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.items.as_ref().hash(state)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Apl<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Apl
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Apl<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Apl {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Apl<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.items.as_ref().len()).expect("long APL rdata"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.items.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Apl<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = self.iter();
+        if let Some(item) = items.next() {
+            item.fmt(f)?;
+        }
+        for item in items {
+            write!(f, " {}", item)?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Apl<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Apl(")?;
+        fmt::Display::fmt(self, f)?;
+        f.write_str(")")
+    }
+}
+
+//------------ AplItem -------------------------------------------------------
+
+/// A single address prefix item of APL record data.
+///
+/// The item consists of the address family, the length of the prefix in
+/// bits, the negation flag, and the address itself. In the wire format,
+/// trailing zero octets of the address are omitted.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AplItem<'a> {
+    family: AddressFamily,
+    prefix: u8,
+    negation: bool,
+    afdpart: &'a [u8],
+}
+
+impl<'a> AplItem<'a> {
+    /// Creates a new item from its components.
+    ///
+    /// The `afdpart` is the address with trailing zero octets omitted. The
+    /// function will fail if it is longer than 127 octets. For IPv4 and
+    /// IPv6, it also fails if the address or the prefix are too long for
+    /// the address family.
+    pub fn new(
+        family: AddressFamily,
+        prefix: u8,
+        negation: bool,
+        afdpart: &'a [u8],
+    ) -> Result<Self, AplError> {
+        let family = AddressFamily::from_int(family.to_int());
+        let max = match family {
+            AddressFamily::Ipv4 => Some((32, 4)),
+            AddressFamily::Ipv6 => Some((128, 16)),
+            _ => None,
+        };
+        if let Some((max_prefix, max_len)) = max {
+            if prefix > max_prefix {
+                return Err(AplError(AplErrorInner::LongPrefix));
+            }
+            if afdpart.len() > max_len {
+                return Err(AplError(AplErrorInner::LongAddress));
+            }
+        } else if afdpart.len() > 0x7F {
+            return Err(AplError(AplErrorInner::LongAddress));
+        }
+        Ok(AplItem {
+            family,
+            prefix,
+            negation,
+            afdpart,
+        })
+    }
+
+    /// Returns the address family of the item.
+    pub fn family(&self) -> AddressFamily {
+        self.family
+    }
+
+    /// Returns the length of the prefix in bits.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns whether the item is negated.
+    pub fn negation(&self) -> bool {
+        self.negation
+    }
+
+    /// Returns the address with trailing zero octets omitted.
+    pub fn afdpart(&self) -> &'a [u8] {
+        self.afdpart
+    }
+
+    /// Returns the address of the item if it is an IP address.
+    pub fn addr(&self) -> Option<IpAddr> {
+        match self.family {
+            AddressFamily::Ipv4 => {
+                let mut addr = [0u8; 4];
+                addr[..self.afdpart.len()].copy_from_slice(self.afdpart);
+                Some(IpAddr::from(addr))
+            }
+            AddressFamily::Ipv6 => {
+                let mut addr = [0u8; 16];
+                addr[..self.afdpart.len()].copy_from_slice(self.afdpart);
+                Some(IpAddr::from(addr))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether the prefix of the item contains the given address.
+    ///
+    /// The negation flag is not considered.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.family, addr) {
+            (AddressFamily::Ipv4, IpAddr::V4(addr)) => {
+                self.prefix_matches(&addr.octets())
+            }
+            (AddressFamily::Ipv6, IpAddr::V6(addr)) => {
+                self.prefix_matches(&addr.octets())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the first `prefix` bits of `addr` match.
+    fn prefix_matches(&self, addr: &[u8]) -> bool {
+        let mut bits = usize::from(self.prefix);
+        for (i, octet) in addr.iter().enumerate() {
+            if bits == 0 {
+                break;
+            }
+            let mask = if bits >= 8 {
+                0xFF
+            } else {
+                0xFFu8 << (8 - bits)
+            };
+            let own = self.afdpart.get(i).copied().unwrap_or(0);
+            if (octet ^ own) & mask != 0 {
+                return false;
+            }
+            bits = bits.saturating_sub(8);
+        }
+        true
+    }
+
+    /// Splits an item off the beginning of a slice.
+    ///
+    /// Returns the item and the remainder of the slice.
+    fn split_from(slice: &'a [u8]) -> Result<(Self, &'a [u8]), AplError> {
+        if slice.len() < 4 {
+            return Err(AplError(AplErrorInner::ShortInput));
+        }
+        let len = usize::from(slice[3] & 0x7F) + 4;
+        if slice.len() < len {
+            return Err(AplError(AplErrorInner::ShortInput));
+        }
+        let item = AplItem::new(
+            AddressFamily::from_int(u16::from_be_bytes([slice[0], slice[1]])),
+            slice[2],
+            slice[3] & 0x80 != 0,
+            &slice[4..len],
+        )?;
+        Ok((item, &slice[len..]))
+    }
+
+    /// Returns the length of the wire format of the item.
+    fn compose_len(&self) -> usize {
+        self.afdpart.len() + 4
+    }
+
+    /// Appends the wire format of the item to the target.
+    fn compose<Target: OctetsBuilder + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // The length is at most 127, so it always fits into the lower
+        // seven bits.
+        let mut len = self.afdpart.len() as u8;
+        if self.negation {
+            len |= 0x80;
+        }
+        self.family.compose(target)?;
+        target.append_slice(&[self.prefix, len])?;
+        target.append_slice(self.afdpart)
+    }
+}
+
+//--- Display
+
+impl<'a> fmt::Display for AplItem<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negation {
+            f.write_str("!")?;
+        }
+        match self.addr() {
+            Some(addr) => {
+                write!(f, "{}:{}/{}", self.family, addr, self.prefix)
+            }
+            None => {
+                // There is no presentation format for other families, so
+                // we display the address in hex. Apl::scan accepts this.
+                write!(f, "{}:", self.family)?;
+                base16::display(self.afdpart, f)?;
+                write!(f, "/{}", self.prefix)
+            }
+        }
+    }
+}
+
+//------------ AplIter -------------------------------------------------------
+
+/// An iterator over the items of APL record data.
+#[derive(Clone, Debug)]
+pub struct AplIter<'a>(&'a [u8]);
+
+impl<'a> Iterator for AplIter<'a> {
+    type Item = AplItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        // The data has been checked upon creation, so this can’t fail.
+        let (item, tail) = AplItem::split_from(self.0).ok()?;
+        self.0 = tail;
+        Some(item)
+    }
+}
+
+//------------ AplBuilder ----------------------------------------------------
+
+/// A builder for APL record data.
+#[derive(Clone, Debug)]
+pub struct AplBuilder<Builder> {
+    builder: Builder,
+}
+
+impl<Builder: OctetsBuilder + EmptyBuilder> AplBuilder<Builder> {
+    /// Creates a new, empty builder.
+    pub fn new() -> Self {
+        AplBuilder {
+            builder: Builder::empty(),
+        }
+    }
+}
+
+impl<Builder: OctetsBuilder + AsRef<[u8]>> AplBuilder<Builder> {
+    /// Appends an item.
+    pub fn push(&mut self, item: &AplItem) -> Result<(), AplError> {
+        LongRecordData::check_len(
+            self.builder.as_ref().len() + item.compose_len(),
+        )?;
+        item.compose(&mut self.builder)
+            .map_err(|_| AplError(AplErrorInner::ShortBuf))
+    }
+
+    /// Appends an item for an IP address prefix.
+    ///
+    /// The address is encoded with its trailing zero octets removed. The
+    /// function will fail if the prefix is too long for the address.
+    pub fn push_addr(
+        &mut self,
+        negation: bool,
+        addr: IpAddr,
+        prefix: u8,
+    ) -> Result<(), AplError> {
+        match addr {
+            IpAddr::V4(addr) => self.push_trimmed(
+                AddressFamily::Ipv4,
+                prefix,
+                negation,
+                &addr.octets(),
+            ),
+            IpAddr::V6(addr) => self.push_trimmed(
+                AddressFamily::Ipv6,
+                prefix,
+                negation,
+                &addr.octets(),
+            ),
+        }
+    }
+
+    /// Appends an item with trailing zero octets removed from `addr`.
+    fn push_trimmed(
+        &mut self,
+        family: AddressFamily,
+        prefix: u8,
+        negation: bool,
+        addr: &[u8],
+    ) -> Result<(), AplError> {
+        let len = addr
+            .iter()
+            .rposition(|&octet| octet != 0)
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        self.push(&AplItem::new(family, prefix, negation, &addr[..len])?)
+    }
+
+    /// Finishes the builder and returns the record data.
+    pub fn finish(self) -> Apl<Builder::Octets>
+    where
+        Builder: FreezeBuilder,
+    {
+        unsafe { Apl::from_octets_unchecked(self.builder.freeze()) }
+    }
+}
+
+impl<Builder: OctetsBuilder + EmptyBuilder> Default for AplBuilder<Builder> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// An item scanned from its presentation format.
+enum ScannedItem {
+    /// An IPv4 or IPv6 item with its negation flag, address, and prefix.
+    Addr(bool, IpAddr, u8),
+
+    /// An item of some other address family.
+    Other {
+        negation: bool,
+        family: AddressFamily,
+        prefix: u8,
+        afdpart: [u8; 0x7F],
+        len: usize,
+    },
+}
+
+/// Scans a single item in presentation format.
+///
+/// Returns `None` if the string isn’t a valid item.
+fn scan_item(s: &str) -> Option<ScannedItem> {
+    let (negation, s) = match s.strip_prefix('!') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let (family, s) = s.split_once(':')?;
+    let (addr, prefix) = s.rsplit_once('/')?;
+    let family = AddressFamily::from_int(scan_decimal(family)?);
+    let prefix = u8::try_from(scan_decimal(prefix)?).ok()?;
+    match family {
+        AddressFamily::Ipv4 => Some(ScannedItem::Addr(
+            negation,
+            IpAddr::V4(Ipv4Addr::from_str(addr).ok()?),
+            prefix,
+        )),
+        AddressFamily::Ipv6 => Some(ScannedItem::Addr(
+            negation,
+            IpAddr::V6(Ipv6Addr::from_str(addr).ok()?),
+            prefix,
+        )),
+        _ => {
+            // The address part is in hex as produced by AplItem’s Display.
+            let mut afdpart = [0u8; 0x7F];
+            let addr = addr.as_bytes();
+            if addr.len() % 2 != 0 || addr.len() / 2 > afdpart.len() {
+                return None;
+            }
+            for (octet, chunk) in afdpart.iter_mut().zip(addr.chunks(2)) {
+                *octet = hex_digit(chunk[0])? << 4 | hex_digit(chunk[1])?;
+            }
+            Some(ScannedItem::Other {
+                negation,
+                family,
+                prefix,
+                afdpart,
+                len: addr.len() / 2,
+            })
+        }
+    }
+}
+
+/// Scans a decimal number without a sign.
+fn scan_decimal(s: &str) -> Option<u16> {
+    if s.is_empty() || !s.bytes().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    u16::from_str(s).ok()
+}
+
+/// Returns the value of a hex digit.
+fn hex_digit(ch: u8) -> Option<u8> {
+    char::from(ch).to_digit(16).map(|value| value as u8)
+}
+
+//============ Error Types ===================================================
+
+//------------ AplError ------------------------------------------------------
+
+/// An octets sequence does not form valid APL record data.
+#[derive(Clone, Copy, Debug)]
+pub struct AplError(AplErrorInner);
+
+#[derive(Clone, Copy, Debug)]
+enum AplErrorInner {
+    Long(LongRecordData),
+    ShortInput,
+    LongPrefix,
+    LongAddress,
+    ShortBuf,
+}
+
+impl AplError {
+    pub fn as_str(self) -> &'static str {
+        match self.0 {
+            AplErrorInner::Long(err) => err.as_str(),
+            AplErrorInner::ShortInput => "short input",
+            AplErrorInner::LongPrefix => "prefix too long for family",
+            AplErrorInner::LongAddress => "address too long for family",
+            AplErrorInner::ShortBuf => "buffer size exceeded",
+        }
+    }
+}
+
+impl From<LongRecordData> for AplError {
+    fn from(err: LongRecordData) -> AplError {
+        AplError(AplErrorInner::Long(err))
+    }
+}
+
+impl From<AplError> for FormError {
+    fn from(err: AplError) -> FormError {
+        FormError::new(err.as_str())
+    }
+}
+
+impl fmt::Display for AplError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AplError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    fn example() -> Apl<Vec<u8>> {
+        let mut builder = AplBuilder::<Vec<u8>>::new();
+        builder
+            .push_addr(false, "192.168.32.0".parse().unwrap(), 21)
+            .unwrap();
+        builder
+            .push_addr(true, "192.168.38.0".parse().unwrap(), 28)
+            .unwrap();
+        builder.finish()
+    }
+
+    #[test]
+    fn apl_compose_parse_scan() {
+        let rdata = example();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Apl::parse(parser));
+        test_scan(
+            &["1:192.168.32.0/21", "!1:192.168.38.0/28"],
+            Apl::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), "1:192.168.32.0/21 !1:192.168.38.0/28");
+    }
+
+    #[test]
+    fn apl_trimmed_encoding() {
+        assert_eq!(
+            example().as_octets().as_slice(),
+            b"\x00\x01\x15\x03\xc0\xa8\x20\
+              \x00\x01\x1c\x83\xc0\xa8\x26"
+        );
+
+        let mut builder = AplBuilder::<Vec<u8>>::new();
+        builder.push_addr(false, "::".parse().unwrap(), 0).unwrap();
+        let rdata = builder.finish();
+        assert_eq!(rdata.as_octets().as_slice(), b"\x00\x02\x00\x00");
+        assert_eq!(rdata.to_string(), "2:::/0");
+
+        let mut builder = AplBuilder::<Vec<u8>>::new();
+        assert!(builder
+            .push_addr(false, "192.0.2.0".parse().unwrap(), 33)
+            .is_err());
+    }
+
+    #[test]
+    fn apl_other_family() {
+        let rdata = Apl::from_octets(Vec::from(
+            b"\x00\x05\x18\x83\xc0\xff\xee".as_ref(),
+        ))
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Apl::parse(parser));
+        assert_eq!(rdata.to_string(), "!5:C0FFEE/24");
+        test_scan(&["!5:C0FFEE/24"], Apl::scan, &rdata);
+        test_scan(&["!5:c0ffee/24"], Apl::scan, &rdata);
+        assert_eq!(rdata.iter().next().unwrap().addr(), None);
+    }
+
+    #[test]
+    fn apl_from_octets() {
+        assert!(Apl::from_octets(b"".as_ref()).unwrap().is_empty());
+        assert!(Apl::from_octets(b"\x00\x01\x15".as_ref()).is_err());
+        assert!(
+            Apl::from_octets(b"\x00\x01\x15\x03\xc0\xa8".as_ref()).is_err()
+        );
+        assert!(Apl::from_octets(b"\x00\x01\x21\x00".as_ref()).is_err());
+        assert!(Apl::from_octets(
+            b"\x00\x01\x20\x05\x01\x02\x03\x04\x05".as_ref()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn apl_permits() {
+        let rdata = example();
+        assert!(rdata.permits("192.168.32.1".parse().unwrap()));
+        assert!(rdata.permits("192.168.39.255".parse().unwrap()));
+        assert!(!rdata.permits("192.168.38.1".parse().unwrap()));
+        assert!(!rdata.permits("192.168.38.15".parse().unwrap()));
+        assert!(rdata.permits("192.168.38.16".parse().unwrap()));
+        assert!(!rdata.permits("192.168.40.0".parse().unwrap()));
+        assert!(!rdata.permits("::1".parse().unwrap()));
+        assert_eq!(
+            rdata
+                .longest_match("192.168.38.1".parse().unwrap())
+                .unwrap()
+                .prefix(),
+            28
+        );
+
+        let mut builder = AplBuilder::<Vec<u8>>::new();
+        builder.push_addr(true, "::".parse().unwrap(), 0).unwrap();
+        builder
+            .push_addr(false, "2001:db8::".parse().unwrap(), 32)
+            .unwrap();
+        builder
+            .push_addr(false, "2001:db8:1::".parse().unwrap(), 48)
+            .unwrap();
+        builder
+            .push_addr(true, "2001:db8:1::".parse().unwrap(), 48)
+            .unwrap();
+        let rdata = builder.finish();
+        assert!(rdata.permits("2001:db8::1".parse().unwrap()));
+        assert!(!rdata.permits("2001:db8:1::1".parse().unwrap()));
+        assert!(!rdata.permits("2001:db9::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn apl_scan_errors() {
+        assert!(scan_item("1:192.168.32.0/21").is_some());
+        assert!(scan_item("!2:2001:db8::/32").is_some());
+        assert!(scan_item("3:192.168.32.0/21").is_none());
+        assert!(scan_item("5:C0FFEE/24").is_some());
+        assert!(scan_item("5:C0FFE/24").is_none());
+        assert!(scan_item("+5:C0FFEE/24").is_none());
+        assert!(scan_item("1:192.168.32.0/256").is_none());
+        assert!(scan_item("1:192.168.32.0").is_none());
+        assert!(scan_item("1:2001:db8::/32").is_none());
+        assert!(scan_item("1:192.168.32.0/+21").is_none());
+        assert!(scan_item("192.168.32.0/21").is_none());
+    }
+}
@@ -0,0 +1,897 @@
+//! Record data from [RFC 3403]: NAPTR records.
+//!
+//! This RFC defines the NAPTR record type used by the Dynamic Delegation
+//! Discovery System (DDDS) defined in [RFC 3402]. It is used, for
+//! instance, by ENUM to map telephone numbers to URIs and by SIP to find
+//! the transport protocols supported by a domain.
+//!
+//! With the `regex` feature enabled, the module also provides the
+//! [`evaluate`] function that applies the rewrite rules of a set of NAPTR
+//! records to an input string.
+//!
+//! [RFC 3402]: https://tools.ietf.org/html/rfc3402
+//! [RFC 3403]: https://tools.ietf.org/html/rfc3403
+
+use crate::base::charstr::CharStr;
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{Dname, ParsedDname, PushError, ToDname};
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scan, Scanner};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::builder::{EmptyBuilder, FromBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+#[cfg(feature = "regex")]
+use std::string::String;
+#[cfg(feature = "regex")]
+use std::vec::Vec;
+
+//------------ Naptr ---------------------------------------------------------
+
+/// NAPTR record data.
+///
+/// NAPTR records contain a rule for rewriting a string as part of the
+/// DDDS. The order and preference fields determine the sequence in which
+/// the records of a set are processed. The flags field controls the
+/// interpretation of the result, the services field states the services
+/// and protocols available via the rewritten result. The rewrite rule
+/// itself is either the substitution expression in the regexp field or,
+/// if that is empty, the domain name in the replacement field.
+///
+/// The NAPTR record type is defined in [RFC 3403].
+///
+/// [RFC 3403]: https://tools.ietf.org/html/rfc3403
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: AsRef<[u8]> + octseq::serde::SerializeOctets,
+            Name: serde::Serialize,
+        ",
+        deserialize = "
+            Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+            <Octs as FromBuilder>::Builder: AsRef<[u8]> + EmptyBuilder,
+            Name: serde::Deserialize<'de>,
+        ",
+    ))
+)]
+pub struct Naptr<Octs, Name> {
+    order: u16,
+    preference: u16,
+    flags: CharStr<Octs>,
+    services: CharStr<Octs>,
+    regexp: CharStr<Octs>,
+    replacement: Name,
+}
+
+impl<Octs, Name> Naptr<Octs, Name> {
+    /// Creates new NAPTR record data from its components.
+    pub fn new(
+        order: u16,
+        preference: u16,
+        flags: CharStr<Octs>,
+        services: CharStr<Octs>,
+        regexp: CharStr<Octs>,
+        replacement: Name,
+    ) -> Self {
+        Naptr {
+            order,
+            preference,
+            flags,
+            services,
+            regexp,
+            replacement,
+        }
+    }
+
+    /// Returns the order in which records must be processed.
+    ///
+    /// Records with a lower order must be processed first.
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    /// Returns the preference among records with the same order.
+    ///
+    /// Records with a lower preference should be processed first.
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// Returns the flags.
+    pub fn flags(&self) -> &CharStr<Octs> {
+        &self.flags
+    }
+
+    /// Returns the services.
+    pub fn services(&self) -> &CharStr<Octs> {
+        &self.services
+    }
+
+    /// Returns the substitution expression.
+    pub fn regexp(&self) -> &CharStr<Octs> {
+        &self.regexp
+    }
+
+    /// Returns the replacement domain name.
+    pub fn replacement(&self) -> &Name {
+        &self.replacement
+    }
+
+    /// Returns whether the given flag is set.
+    ///
+    /// Flags are single characters compared ignoring ASCII case.
+    pub fn has_flag(&self, flag: u8) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.flags
+            .as_slice()
+            .iter()
+            .any(|ch| ch.eq_ignore_ascii_case(&flag))
+    }
+
+    /// Returns whether the record ends the DDDS loop.
+    ///
+    /// This is the case if one of the terminal flags ‘S’, ‘A’, or ‘U’
+    /// defined in RFC 3404 is set.
+    pub fn is_terminal(&self) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.has_flag(b'S') || self.has_flag(b'A') || self.has_flag(b'U')
+    }
+
+    pub(super) fn convert_octets<TOcts, TName>(
+        self,
+    ) -> Result<Naptr<TOcts, TName>, TOcts::Error>
+    where
+        TOcts: OctetsFrom<Octs>,
+        TName: OctetsFrom<Name, Error = TOcts::Error>,
+    {
+        Ok(Naptr::new(
+            self.order,
+            self.preference,
+            self.flags.try_octets_into()?,
+            self.services.try_octets_into()?,
+            self.regexp.try_octets_into()?,
+            self.replacement.try_octets_into()?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs, Dname = Name>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Self::new(
+            u16::scan(scanner)?,
+            u16::scan(scanner)?,
+            scanner.scan_charstr()?,
+            scanner.scan_charstr()?,
+            scanner.scan_charstr()?,
+            scanner.scan_dname()?,
+        ))
+    }
+}
+
+impl<Octs, NOcts> Naptr<Octs, ParsedDname<NOcts>> {
+    pub fn flatten_into<Target>(
+        self,
+    ) -> Result<Naptr<Target, Dname<Target>>, PushError>
+    where
+        NOcts: Octets,
+        Target: OctetsFrom<Octs>
+            + for<'a> OctetsFrom<NOcts::Range<'a>>
+            + FromBuilder,
+        <Target as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let Self {
+            order,
+            preference,
+            flags,
+            services,
+            regexp,
+            replacement,
+        } = self;
+        Ok(Naptr::new(
+            order,
+            preference,
+            flags.try_octets_into().map_err(Into::into)?,
+            services.try_octets_into().map_err(Into::into)?,
+            regexp.try_octets_into().map_err(Into::into)?,
+            replacement.flatten_into()?,
+        ))
+    }
+}
+
+impl<Octs> Naptr<Octs, ParsedDname<Octs>> {
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized + 'a>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            u16::parse(parser)?,
+            u16::parse(parser)?,
+            CharStr::parse(parser)?,
+            CharStr::parse(parser)?,
+            CharStr::parse(parser)?,
+            ParsedDname::parse(parser)?,
+        ))
+    }
+}
+
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+impl<Octs: AsRef<[u8]>, Name> Naptr<Octs, Name> {
+    /// Applies the substitution expression to a string.
+    ///
+    /// Returns `Ok(None)` if the regular expression doesn’t match the
+    /// string or if the regexp field is empty. In the latter case, the
+    /// replacement field provides the result.
+    ///
+    /// The expression is applied like the `s` command of sed: the first
+    /// match in the string is replaced with the expanded replacement. The
+    /// regular expression is interpreted using the syntax of the
+    /// [regex](https://docs.rs/regex) crate which is largely compatible
+    /// with POSIX extended regular expressions.
+    pub fn apply_regexp(
+        &self,
+        input: &str,
+    ) -> Result<Option<String>, RegexpError> {
+        if self.regexp.as_slice().is_empty() {
+            return Ok(None);
+        }
+        let expr = core::str::from_utf8(self.regexp.as_slice())
+            .map_err(|_| RegexpError::Utf8)?;
+        SubstExpr::parse(expr)?.apply(input)
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts, Name, SrcName> OctetsFrom<Naptr<SrcOcts, SrcName>>
+    for Naptr<Octs, Name>
+where
+    Octs: OctetsFrom<SrcOcts>,
+    Name: OctetsFrom<SrcName, Error = Octs::Error>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(
+        source: Naptr<SrcOcts, SrcName>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Naptr::new(
+            source.order,
+            source.preference,
+            CharStr::try_octets_from(source.flags)?,
+            CharStr::try_octets_from(source.services)?,
+            CharStr::try_octets_from(source.regexp)?,
+            Name::try_octets_from(source.replacement)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<O, OO, N, NN> PartialEq<Naptr<OO, NN>> for Naptr<O, N>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+    N: ToDname,
+    NN: ToDname,
+{
+    fn eq(&self, other: &Naptr<OO, NN>) -> bool {
+        self.order == other.order
+            && self.preference == other.preference
+            && self.flags.eq(&other.flags)
+            && self.services.eq(&other.services)
+            && self.regexp.eq(&other.regexp)
+            && self.replacement.name_eq(&other.replacement)
+    }
+}
+
+impl<O: AsRef<[u8]>, N: ToDname> Eq for Naptr<O, N> {}
+
+//--- PartialOrd, Ord, and CanonicalOrd
+
+impl<O, OO, N, NN> PartialOrd<Naptr<OO, NN>> for Naptr<O, N>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+    N: ToDname,
+    NN: ToDname,
+{
+    fn partial_cmp(&self, other: &Naptr<OO, NN>) -> Option<Ordering> {
+        match self.order.partial_cmp(&other.order) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.preference.partial_cmp(&other.preference) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.flags.partial_cmp(&other.flags) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.services.partial_cmp(&other.services) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        match self.regexp.partial_cmp(&other.regexp) {
+            Some(Ordering::Equal) => {}
+            other => return other,
+        }
+        Some(self.replacement.name_cmp(&other.replacement))
+    }
+}
+
+impl<O: AsRef<[u8]>, N: ToDname> Ord for Naptr<O, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.order.cmp(&other.order) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.flags.cmp(&other.flags) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.services.cmp(&other.services) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.regexp.cmp(&other.regexp) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.replacement.name_cmp(&other.replacement)
+    }
+}
+
+impl<O, OO, N, NN> CanonicalOrd<Naptr<OO, NN>> for Naptr<O, N>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+    N: ToDname,
+    NN: ToDname,
+{
+    fn canonical_cmp(&self, other: &Naptr<OO, NN>) -> Ordering {
+        match self.order.cmp(&other.order) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.preference.cmp(&other.preference) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.flags.canonical_cmp(&other.flags) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.services.canonical_cmp(&other.services) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.regexp.canonical_cmp(&other.regexp) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.replacement.lowercase_composed_cmp(&other.replacement)
+    }
+}
+
+//--- Hash
+
+impl<O: AsRef<[u8]>, N: hash::Hash> hash::Hash for Naptr<O, N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.order.hash(state);
+        self.preference.hash(state);
+        self.flags.hash(state);
+        self.services.hash(state);
+        self.regexp.hash(state);
+        self.replacement.hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs, Name> RecordData for Naptr<Octs, Name> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Naptr
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> ParseRecordData<'a, Octs>
+    for Naptr<Octs::Range<'a>, ParsedDname<Octs::Range<'a>>>
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Naptr {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs, Name> ComposeRecordData for Naptr<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: ToDname,
+{
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        // NAPTR records are not compressed.
+        Some(
+            u16::COMPOSE_LEN
+                + u16::COMPOSE_LEN
+                + self.flags.compose_len()
+                + self.services.compose_len()
+                + self.regexp.compose_len()
+                + self.replacement.compose_len(),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_head(target)?;
+        self.replacement.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_head(target)?;
+        self.replacement.compose_canonical(target) // ... but are lowercased.
+    }
+}
+
+impl<Octs: AsRef<[u8]>, Name: ToDname> Naptr<Octs, Name> {
+    fn compose_head<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.order.compose(target)?;
+        self.preference.compose(target)?;
+        self.flags.compose(target)?;
+        self.services.compose(target)?;
+        self.regexp.compose(target)
+    }
+}
+
+//--- Display
+
+impl<Octs, Name> fmt::Display for Naptr<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} \"{}\" \"{}\" \"{}\" {}",
+            self.order,
+            self.preference,
+            self.flags,
+            self.services,
+            self.regexp,
+            self.replacement
+        )
+    }
+}
+
+//--- Debug
+
+impl<Octs, Name> fmt::Debug for Naptr<Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Naptr")
+            .field("order", &self.order)
+            .field("preference", &self.preference)
+            .field("flags", &self.flags)
+            .field("services", &self.services)
+            .field("regexp", &self.regexp)
+            .field("replacement", &self.replacement)
+            .finish()
+    }
+}
+
+//------------ evaluate ------------------------------------------------------
+
+/// Applies the rewrite rules of a set of NAPTR records to a string.
+///
+/// The records are processed in the order given by their order and
+/// preference fields as described in section 3.3 of [RFC 3402]. Each
+/// record with a non-empty regexp field is applied to `input`. Records
+/// with an empty regexp field always match and produce their replacement
+/// domain name. Once a record has matched, records with a higher order
+/// are not considered anymore.
+///
+/// Records whose substitution expression is invalid are skipped. The
+/// returned matches are in processing order. It is up to the caller to
+/// pick the first match with suitable flags and services.
+///
+/// [RFC 3402]: https://tools.ietf.org/html/rfc3402
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+pub fn evaluate<'a, Octs, Name, Iter>(
+    records: Iter,
+    input: &str,
+) -> Vec<NaptrMatch<'a, Octs, Name>>
+where
+    Octs: AsRef<[u8]> + 'a,
+    Name: 'a,
+    Iter: IntoIterator<Item = &'a Naptr<Octs, Name>>,
+{
+    let mut records: Vec<_> = records.into_iter().collect();
+    records.sort_by_key(|record| (record.order, record.preference));
+
+    let mut res = Vec::new();
+    let mut matched_order = None;
+    for record in records {
+        if let Some(order) = matched_order {
+            if record.order != order {
+                break;
+            }
+        }
+        let rewrite = if record.regexp.as_slice().is_empty() {
+            Rewrite::Replacement(&record.replacement)
+        } else {
+            match record.apply_regexp(input) {
+                Ok(Some(output)) => Rewrite::Output(output),
+                _ => continue,
+            }
+        };
+        matched_order = Some(record.order);
+        res.push(NaptrMatch { record, rewrite });
+    }
+    res
+}
+
+//------------ NaptrMatch ----------------------------------------------------
+
+/// A NAPTR record that matched an input string.
+///
+/// This type is returned by the [`evaluate`] function.
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Clone)]
+pub struct NaptrMatch<'a, Octs, Name> {
+    /// The matching record.
+    record: &'a Naptr<Octs, Name>,
+
+    /// The result of the rewrite.
+    rewrite: Rewrite<'a, Name>,
+}
+
+#[cfg(feature = "regex")]
+impl<'a, Octs, Name> NaptrMatch<'a, Octs, Name> {
+    /// Returns the record that matched.
+    pub fn record(&self) -> &'a Naptr<Octs, Name> {
+        self.record
+    }
+
+    /// Returns the result of the rewrite.
+    pub fn rewrite(&self) -> &Rewrite<'a, Name> {
+        &self.rewrite
+    }
+}
+
+#[cfg(feature = "regex")]
+impl<'a, Octs, Name> fmt::Debug for NaptrMatch<'a, Octs, Name>
+where
+    Octs: AsRef<[u8]>,
+    Name: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NaptrMatch")
+            .field("record", &self.record)
+            .field("rewrite", &self.rewrite)
+            .finish()
+    }
+}
+
+//------------ Rewrite -------------------------------------------------------
+
+/// The result of applying a NAPTR record’s rewrite rule.
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rewrite<'a, Name> {
+    /// The substitution expression produced this string.
+    Output(String),
+
+    /// The record provided this replacement domain name.
+    Replacement(&'a Name),
+}
+
+//------------ SubstExpr -----------------------------------------------------
+
+/// A parsed substitution expression.
+///
+/// The syntax is defined in section 3.2 of RFC 3402:
+///
+/// ```text
+/// subst-expr   = delim-char  ere  delim-char  repl  delim-char  *flags
+/// ```
+#[cfg(feature = "regex")]
+struct SubstExpr<'a> {
+    /// The compiled regular expression.
+    regex: regex::Regex,
+
+    /// The replacement.
+    repl: &'a str,
+
+    /// The delimiter.
+    delim: char,
+}
+
+#[cfg(feature = "regex")]
+impl<'a> SubstExpr<'a> {
+    /// Parses a substitution expression.
+    fn parse(expr: &'a str) -> Result<Self, RegexpError> {
+        let delim = match expr.chars().next() {
+            Some(ch)
+                if ch != 'i' && ch != '\\' && !matches!(ch, '1'..='9') =>
+            {
+                ch
+            }
+            _ => return Err(RegexpError::Syntax),
+        };
+        let (ere, rest) =
+            Self::split_at_delim(&expr[delim.len_utf8()..], delim)?;
+        let (repl, flags) = Self::split_at_delim(rest, delim)?;
+        let case_insensitive = match flags {
+            "" => false,
+            "i" => true,
+            _ => return Err(RegexpError::Syntax),
+        };
+
+        // Replace escaped delimiters in the ERE with the plain delimiter,
+        // escaped for the regex syntax if necessary.
+        let mut pattern = String::with_capacity(ere.len());
+        let mut chars = ere.chars();
+        while let Some(ch) = chars.next() {
+            if ch == '\\' {
+                match chars.next() {
+                    Some(next) if next == delim => {
+                        pattern.push_str(&regex::escape(
+                            delim.encode_utf8(&mut [0; 4]),
+                        ));
+                    }
+                    Some(next) => {
+                        pattern.push(ch);
+                        pattern.push(next);
+                    }
+                    None => return Err(RegexpError::Syntax),
+                }
+            } else {
+                pattern.push(ch)
+            }
+        }
+        let regex = regex::RegexBuilder::new(&pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|_| RegexpError::Regex)?;
+        Ok(SubstExpr { regex, repl, delim })
+    }
+
+    /// Splits a string at the first unescaped delimiter.
+    fn split_at_delim(
+        s: &str,
+        delim: char,
+    ) -> Result<(&str, &str), RegexpError> {
+        let mut escaped = false;
+        for (idx, ch) in s.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == delim {
+                return Ok((&s[..idx], &s[idx + ch.len_utf8()..]));
+            }
+        }
+        Err(RegexpError::Syntax)
+    }
+
+    /// Applies the expression to an input string.
+    fn apply(&self, input: &str) -> Result<Option<String>, RegexpError> {
+        let captures = match self.regex.captures(input) {
+            Some(captures) => captures,
+            None => return Ok(None),
+        };
+        let whole = captures.get(0).expect("missing whole match");
+        let mut res = String::with_capacity(input.len() + self.repl.len());
+        res.push_str(&input[..whole.start()]);
+        let mut chars = self.repl.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                res.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some(next @ '1'..='9') => {
+                    let idx = usize::from(next as u8 - b'0');
+                    if let Some(group) = captures.get(idx) {
+                        res.push_str(group.as_str())
+                    }
+                }
+                Some(next) if next == self.delim || next == '\\' => {
+                    res.push(next)
+                }
+                _ => return Err(RegexpError::Syntax),
+            }
+        }
+        res.push_str(&input[whole.end()..]);
+        Ok(Some(res))
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ RegexpError ---------------------------------------------------
+
+/// The substitution expression of a NAPTR record is invalid.
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegexpError {
+    /// The expression is not valid UTF-8.
+    Utf8,
+
+    /// The expression does not follow the substitution expression syntax.
+    Syntax,
+
+    /// The regular expression is invalid.
+    Regex,
+}
+
+#[cfg(feature = "regex")]
+impl fmt::Display for RegexpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            RegexpError::Utf8 => "invalid UTF-8 in substitution expression",
+            RegexpError::Syntax => "invalid substitution expression",
+            RegexpError::Regex => "invalid regular expression",
+        })
+    }
+}
+
+#[cfg(feature = "regex")]
+impl std::error::Error for RegexpError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    fn naptr(
+        order: u16,
+        preference: u16,
+        flags: &str,
+        services: &str,
+        regexp: &str,
+        replacement: &str,
+    ) -> Naptr<Vec<u8>, Dname<Vec<u8>>> {
+        Naptr::new(
+            order,
+            preference,
+            CharStr::from_str(flags).unwrap(),
+            CharStr::from_str(services).unwrap(),
+            CharStr::from_str(regexp).unwrap(),
+            Dname::from_str(replacement).unwrap(),
+        )
+    }
+
+    #[test]
+    fn naptr_compose_parse_scan() {
+        let rdata =
+            naptr(100, 10, "S", "SIP+D2U", "", "_sip._udp.example.com");
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Naptr::parse(parser));
+        test_scan(
+            &["100", "10", "S", "SIP+D2U", "", "_sip._udp.example.com"],
+            Naptr::scan,
+            &rdata,
+        );
+        assert!(rdata.is_terminal());
+        assert!(rdata.has_flag(b's'));
+        assert!(!rdata.has_flag(b'U'));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn apply_regexp() {
+        let rdata = naptr(
+            100,
+            10,
+            "u",
+            "E2U+sip",
+            "!^\\\\+44(.*)$!sip:\\\\1@example.com!",
+            ".",
+        );
+        assert_eq!(
+            rdata.regexp().as_slice(),
+            b"!^\\+44(.*)$!sip:\\1@example.com!"
+        );
+        assert_eq!(
+            rdata.apply_regexp("+442079460148").unwrap().as_deref(),
+            Some("sip:2079460148@example.com")
+        );
+        assert_eq!(rdata.apply_regexp("+4930123456").unwrap(), None);
+
+        // Sed-like replacement of the first match only and flags.
+        let rdata = naptr(0, 0, "", "", "/A/x/i", ".");
+        assert_eq!(
+            rdata.apply_regexp("banana").unwrap().as_deref(),
+            Some("bxnana")
+        );
+
+        // Escaped delimiters.
+        let rdata = naptr(0, 0, "", "", "/a\\\\/b/c\\\\/d/", ".");
+        assert_eq!(
+            rdata.apply_regexp("xa/by").unwrap().as_deref(),
+            Some("xc/dy")
+        );
+
+        for bad in ["1abc1d1", "/abc/d", "/abc/d/x", "/(/d/", "/a/\\\\x/"] {
+            assert!(naptr(0, 0, "", "", bad, ".")
+                .apply_regexp("abc")
+                .is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn evaluate() {
+        let records = [
+            naptr(200, 10, "u", "E2U+sip", "!^.*$!sip:b@example.com!", "."),
+            naptr(100, 20, "u", "E2U+sip", "!^.*$!sip:a@example.com!", "."),
+            naptr(
+                100,
+                10,
+                "u",
+                "E2U+sip",
+                "!^\\\\+1!sip:x@example.com!",
+                ".",
+            ),
+            naptr(100, 30, "", "E2U+sip", "", "next.example.com"),
+        ];
+        let res = super::evaluate(&records, "+44123");
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].record().preference(), 20);
+        assert_eq!(
+            res[0].rewrite(),
+            &Rewrite::Output("sip:a@example.com".into())
+        );
+        assert_eq!(
+            res[1].rewrite(),
+            &Rewrite::Replacement(&records[3].replacement)
+        );
+
+        let res = super::evaluate(&records[..1], "+44123");
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].record().order(), 200);
+    }
+}
@@ -0,0 +1,617 @@
+//! Record data from [RFC 4255]: SSHFP records.
+//!
+//! This RFC defines the SSHFP record type used to publish fingerprints of
+//! SSH host keys. The record has been extended for ECDSA keys and the
+//! SHA-256 fingerprint type by [RFC 6594] and for Ed25519 keys by
+//! [RFC 7479].
+//!
+//! With the `ring` feature enabled, the module also provides functions to
+//! create SSHFP record data for an SSH public key and to check a key
+//! against a set of SSHFP records.
+//!
+//! [RFC 4255]: https://tools.ietf.org/html/rfc4255
+//! [RFC 6594]: https://tools.ietf.org/html/rfc6594
+//! [RFC 7479]: https://tools.ietf.org/html/rfc7479
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Rtype, SshfpAlg, SshfpType};
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "ring")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+#[cfg(feature = "ring")]
+use ring::digest;
+
+//------------ Sshfp ---------------------------------------------------------
+
+/// SSHFP record data.
+///
+/// SSHFP records publish the fingerprint of an SSH host key for the host
+/// at the domain name of the record. The algorithm field states the type of
+/// the key and the fingerprint type field the hash function used to create
+/// the fingerprint.
+///
+/// The SSHFP record type is defined in [RFC 4255].
+///
+/// [RFC 4255]: https://tools.ietf.org/html/rfc4255
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Sshfp<Octs> {
+    algorithm: SshfpAlg,
+    fp_type: SshfpType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    fingerprint: Octs,
+}
+
+impl<Octs> Sshfp<Octs> {
+    /// Creates new SSHFP record data from its components.
+    ///
+    /// The function will fail if the fingerprint is longer than 65,533
+    /// octets.
+    pub fn new(
+        algorithm: SshfpAlg,
+        fp_type: SshfpType,
+        fingerprint: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(SshfpAlg::COMPOSE_LEN + SshfpType::COMPOSE_LEN)
+                .checked_add(fingerprint.as_ref().len())
+                .expect("long fingerprint"),
+        )?;
+        Ok(unsafe { Sshfp::new_unchecked(algorithm, fp_type, fingerprint) })
+    }
+
+    /// Creates new SSHFP record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        algorithm: SshfpAlg,
+        fp_type: SshfpType,
+        fingerprint: Octs,
+    ) -> Self {
+        Sshfp {
+            algorithm,
+            fp_type,
+            fingerprint,
+        }
+    }
+
+    /// Returns the algorithm of the key.
+    pub fn algorithm(&self) -> SshfpAlg {
+        self.algorithm
+    }
+
+    /// Returns the fingerprint type.
+    pub fn fp_type(&self) -> SshfpType {
+        self.fp_type
+    }
+
+    /// Returns the fingerprint.
+    pub fn fingerprint(&self) -> &Octs {
+        &self.fingerprint
+    }
+
+    /// Converts the record data into the fingerprint.
+    pub fn into_fingerprint(self) -> Octs {
+        self.fingerprint
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Sshfp<Target>, Target::Error> {
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                self.algorithm,
+                self.fp_type,
+                self.fingerprint.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(2) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                SshfpAlg::parse(parser)?,
+                SshfpType::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            SshfpAlg::scan(scanner)?,
+            SshfpType::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+impl<Octs> Sshfp<Octs> {
+    /// Creates SSHFP record data for an SSH public key.
+    ///
+    /// The key must be given in the binary format used by the SSH protocol,
+    /// i.e., the base64-decoded second field of an OpenSSH public key
+    /// file. The algorithm is taken from the key and the fingerprint is
+    /// calculated using the given fingerprint type.
+    pub fn from_public_key(
+        key: &[u8],
+        fp_type: SshfpType,
+    ) -> Result<Self, SshfpError>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let algorithm = key_algorithm(key)?;
+        let digest = fingerprint(key, fp_type)?;
+        let mut builder = <Octs as FromBuilder>::Builder::with_capacity(
+            digest.as_ref().len(),
+        );
+        builder
+            .append_slice(digest.as_ref())
+            .map_err(|_| SshfpError::ShortBuf)?;
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                algorithm,
+                fp_type,
+                Octs::from_builder(builder),
+            )
+        })
+    }
+
+    /// Returns whether the record matches an SSH public key.
+    ///
+    /// The key must be given in the binary format used by the SSH protocol.
+    /// The record matches if it is for the algorithm of the key and its
+    /// fingerprint is that of the key.
+    ///
+    /// The method fails if the key is malformed or the fingerprint type of
+    /// the record is not supported.
+    pub fn matches_key(&self, key: &[u8]) -> Result<bool, SshfpError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        if key_algorithm(key)? != self.algorithm {
+            return Ok(false);
+        }
+        Ok(fingerprint(key, self.fp_type)?.as_ref()
+            == self.fingerprint.as_ref())
+    }
+}
+
+impl<SrcOcts> Sshfp<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Sshfp<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            algorithm,
+            fp_type,
+            fingerprint,
+        } = self;
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                algorithm,
+                fp_type,
+                fingerprint.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Sshfp<SrcOcts>> for Sshfp<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Sshfp<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Sshfp::new_unchecked(
+                source.algorithm,
+                source.fp_type,
+                Octs::try_octets_from(source.fingerprint)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Sshfp<Other>> for Sshfp<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Sshfp<Other>) -> bool {
+        self.algorithm == other.algorithm
+            && self.fp_type == other.fp_type
+            && self.fingerprint.as_ref() == other.fingerprint.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Sshfp<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Sshfp<Other>> for Sshfp<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Sshfp<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Sshfp<Other>> for Sshfp<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Sshfp<Other>) -> Ordering {
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.fp_type.cmp(&other.fp_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.fingerprint.as_ref().cmp(other.fingerprint.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Sshfp<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Sshfp<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.algorithm.hash(state);
+        self.fp_type.hash(state);
+        self.fingerprint.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Sshfp<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Sshfp
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Sshfp<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Sshfp {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Sshfp<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.fingerprint.as_ref().len())
+                .expect("long fingerprint")
+                .checked_add(SshfpAlg::COMPOSE_LEN + SshfpType::COMPOSE_LEN)
+                .expect("long fingerprint"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.algorithm.compose(target)?;
+        self.fp_type.compose(target)?;
+        target.append_slice(self.fingerprint.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Sshfp<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} ", self.algorithm, self.fp_type)?;
+        base16::display(&self.fingerprint, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Sshfp<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sshfp")
+            .field("algorithm", &self.algorithm)
+            .field("fp_type", &self.fp_type)
+            .field("fingerprint", &self.fingerprint.as_ref())
+            .finish()
+    }
+}
+
+//------------ Key Fingerprints ----------------------------------------------
+
+/// Returns the SSHFP algorithm of an SSH public key.
+///
+/// The key must be given in the binary format used by the SSH protocol. It
+/// starts with the name of the key type which is translated into the
+/// algorithm.
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+pub fn key_algorithm(key: &[u8]) -> Result<SshfpAlg, SshfpError> {
+    if key.len() < 4 {
+        return Err(SshfpError::MalformedKey);
+    }
+    let (len, key) = key.split_at(4);
+    let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]);
+    let name = match usize::try_from(len) {
+        Ok(len) if len <= key.len() => &key[..len],
+        _ => return Err(SshfpError::MalformedKey),
+    };
+    match name {
+        b"ssh-rsa" => Ok(SshfpAlg::Rsa),
+        b"ssh-dss" => Ok(SshfpAlg::Dsa),
+        b"ecdsa-sha2-nistp256"
+        | b"ecdsa-sha2-nistp384"
+        | b"ecdsa-sha2-nistp521" => Ok(SshfpAlg::Ecdsa),
+        b"ssh-ed25519" => Ok(SshfpAlg::Ed25519),
+        b"ssh-ed448" => Ok(SshfpAlg::Ed448),
+        _ => Err(SshfpError::UnsupportedAlgorithm),
+    }
+}
+
+/// Calculates the SSHFP fingerprint of an SSH public key.
+///
+/// The key must be given in the binary format used by the SSH protocol.
+/// The function fails if the fingerprint type is not supported.
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+pub fn fingerprint(
+    key: &[u8],
+    fp_type: SshfpType,
+) -> Result<digest::Digest, SshfpError> {
+    let alg = match fp_type {
+        SshfpType::Sha1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        SshfpType::Sha256 => &digest::SHA256,
+        _ => return Err(SshfpError::UnsupportedFingerprintType),
+    };
+    Ok(digest::digest(alg, key))
+}
+
+/// Checks an SSH public key against a set of SSHFP records.
+///
+/// The key must be given in the binary format used by the SSH protocol.
+/// Returns whether any of the records matches the key. Records with a
+/// fingerprint type that isn’t supported are ignored. The function fails
+/// if the key is malformed or of an unknown type.
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+pub fn verify_key<'a, Octs, Iter>(
+    key: &[u8],
+    records: Iter,
+) -> Result<bool, SshfpError>
+where
+    Octs: AsRef<[u8]> + 'a,
+    Iter: IntoIterator<Item = &'a Sshfp<Octs>>,
+{
+    let algorithm = key_algorithm(key)?;
+    let mut sha1 = None;
+    let mut sha256 = None;
+    for record in records {
+        if record.algorithm != algorithm {
+            continue;
+        }
+        let digest = match record.fp_type {
+            SshfpType::Sha1 => &mut sha1,
+            SshfpType::Sha256 => &mut sha256,
+            _ => continue,
+        };
+        if digest.is_none() {
+            *digest = Some(fingerprint(key, record.fp_type)?);
+        }
+        if let Some(digest) = digest {
+            if digest.as_ref() == record.fingerprint.as_ref() {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+//============ Error Types ===================================================
+
+//------------ SshfpError ----------------------------------------------------
+
+/// An SSH public key could not be fingerprinted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SshfpError {
+    /// The key is not in the binary format used by SSH.
+    MalformedKey,
+
+    /// The key is of a type that has no SSHFP algorithm.
+    UnsupportedAlgorithm,
+
+    /// The fingerprint type isn’t supported.
+    UnsupportedFingerprintType,
+
+    /// The fingerprint didn’t fit into the octets sequence.
+    ShortBuf,
+}
+
+impl fmt::Display for SshfpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            SshfpError::MalformedKey => "malformed key",
+            SshfpError::UnsupportedAlgorithm => "unsupported key algorithm",
+            SshfpError::UnsupportedFingerprintType => {
+                "unsupported fingerprint type"
+            }
+            SshfpError::ShortBuf => "buffer size exceeded",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SshfpError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn sshfp_compose_parse_scan() {
+        let rdata =
+            Sshfp::new(SshfpAlg::Ed25519, SshfpType::Sha256, b"key").unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Sshfp::parse(parser));
+        test_scan(&["4", "2", "6b6579"], Sshfp::scan, &rdata);
+        assert_eq!(rdata.to_string(), "4 2 6B6579");
+    }
+
+    /// The binary form of an Ed25519 public key.
+    #[cfg(feature = "ring")]
+    const ED25519_KEY: &[u8] = b"\x00\x00\x00\x0bssh-ed25519\
+        \x00\x00\x00\x20\
+        \x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\
+        \x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b\x1c\x1d\x1e\x1f\x20";
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn key_algorithm() {
+        assert_eq!(super::key_algorithm(ED25519_KEY), Ok(SshfpAlg::Ed25519));
+        assert_eq!(
+            super::key_algorithm(b"\x00\x00\x00\x07ssh-rsa"),
+            Ok(SshfpAlg::Rsa)
+        );
+        assert_eq!(
+            super::key_algorithm(b"\x00\x00\x00\x07ssh-foo"),
+            Err(SshfpError::UnsupportedAlgorithm)
+        );
+        assert_eq!(
+            super::key_algorithm(b"\x00\x00\x00\x08ssh-rsa"),
+            Err(SshfpError::MalformedKey)
+        );
+        assert_eq!(
+            super::key_algorithm(b"\x00\x00"),
+            Err(SshfpError::MalformedKey)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn from_public_key_and_verify() {
+        let sha256: Sshfp<std::vec::Vec<u8>> =
+            Sshfp::from_public_key(ED25519_KEY, SshfpType::Sha256).unwrap();
+        assert_eq!(sha256.algorithm(), SshfpAlg::Ed25519);
+        assert_eq!(sha256.fingerprint().len(), 32);
+        assert_eq!(sha256.matches_key(ED25519_KEY), Ok(true));
+        assert_eq!(sha256.matches_key(b"\x00\x00\x00\x07ssh-rsa"), Ok(false));
+
+        let sha1: Sshfp<std::vec::Vec<u8>> =
+            Sshfp::from_public_key(ED25519_KEY, SshfpType::Sha1).unwrap();
+        assert_eq!(sha1.fingerprint().len(), 20);
+
+        let other = Sshfp::new(
+            SshfpAlg::Ed25519,
+            SshfpType::Sha256,
+            std::vec![0u8; 32],
+        )
+        .unwrap();
+        let unknown = Sshfp::new(
+            SshfpAlg::Ed25519,
+            SshfpType::Int(3),
+            std::vec![0u8; 32],
+        )
+        .unwrap();
+        assert_eq!(verify_key(ED25519_KEY, [&other, &unknown]), Ok(false));
+        assert_eq!(
+            verify_key(ED25519_KEY, [&other, &unknown, &sha1]),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_key(ED25519_KEY, &[sha256.clone(), other.clone()]),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_key(b"\x00", [&sha256]),
+            Err(SshfpError::MalformedKey)
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! Record data from [RFC 4398]: CERT records.
+//!
+//! This RFC defines the CERT record type used to store certificates and
+//! certificate revocation lists in the DNS.
+//!
+//! [RFC 4398]: https://tools.ietf.org/html/rfc4398
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{CertType, Rtype, SecAlg};
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Cert ----------------------------------------------------------
+
+/// CERT record data.
+///
+/// CERT records contain a certificate or a certificate revocation list.
+/// The certificate type determines its format. The key tag and algorithm
+/// fields can be used to identify a DNSKEY the certificate refers to; they
+/// are zero if there is no such key.
+///
+/// In the presentation format, the certificate type and algorithm can be
+/// given as mnemonics or decimal numbers and the certificate is given in
+/// Base 64.
+///
+/// The CERT record type is defined in [RFC 4398].
+///
+/// [RFC 4398]: https://tools.ietf.org/html/rfc4398
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Cert<Octs> {
+    cert_type: CertType,
+    key_tag: u16,
+    algorithm: SecAlg,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    certificate: Octs,
+}
+
+impl<Octs> Cert<Octs> {
+    /// Creates new CERT record data from its components.
+    ///
+    /// The function will fail if the certificate is longer than 65,530
+    /// octets.
+    pub fn new(
+        cert_type: CertType,
+        key_tag: u16,
+        algorithm: SecAlg,
+        certificate: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(
+                CertType::COMPOSE_LEN
+                    + u16::COMPOSE_LEN
+                    + SecAlg::COMPOSE_LEN,
+            )
+            .checked_add(certificate.as_ref().len())
+            .expect("long certificate"),
+        )?;
+        Ok(unsafe {
+            Cert::new_unchecked(cert_type, key_tag, algorithm, certificate)
+        })
+    }
+
+    /// Creates new CERT record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        cert_type: CertType,
+        key_tag: u16,
+        algorithm: SecAlg,
+        certificate: Octs,
+    ) -> Self {
+        Cert {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate,
+        }
+    }
+
+    /// Returns the certificate type.
+    pub fn cert_type(&self) -> CertType {
+        self.cert_type
+    }
+
+    /// Returns the key tag of the key the certificate refers to.
+    pub fn key_tag(&self) -> u16 {
+        self.key_tag
+    }
+
+    /// Returns the algorithm of the key the certificate refers to.
+    pub fn algorithm(&self) -> SecAlg {
+        self.algorithm
+    }
+
+    /// Returns the certificate or certificate revocation list.
+    pub fn certificate(&self) -> &Octs {
+        &self.certificate
+    }
+
+    /// Converts the record data into the certificate.
+    pub fn into_certificate(self) -> Octs {
+        self.certificate
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Cert<Target>, Target::Error> {
+        Ok(unsafe {
+            Cert::new_unchecked(
+                self.cert_type,
+                self.key_tag,
+                self.algorithm,
+                self.certificate.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(5) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                CertType::parse(parser)?,
+                u16::parse(parser)?,
+                SecAlg::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            CertType::scan(scanner)?,
+            u16::scan(scanner)?,
+            SecAlg::scan(scanner)?,
+            scanner.convert_entry(base64::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Cert<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Cert<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate,
+        } = self;
+        Ok(unsafe {
+            Cert::new_unchecked(
+                cert_type,
+                key_tag,
+                algorithm,
+                certificate.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Cert<SrcOcts>> for Cert<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Cert<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Cert::new_unchecked(
+                source.cert_type,
+                source.key_tag,
+                source.algorithm,
+                Octs::try_octets_from(source.certificate)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Cert<Other>> for Cert<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Cert<Other>) -> bool {
+        self.cert_type == other.cert_type
+            && self.key_tag == other.key_tag
+            && self.algorithm == other.algorithm
+            && self.certificate.as_ref() == other.certificate.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Cert<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Cert<Other>> for Cert<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Cert<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Cert<Other>> for Cert<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Cert<Other>) -> Ordering {
+        match self.cert_type.cmp(&other.cert_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.key_tag.cmp(&other.key_tag) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.certificate.as_ref().cmp(other.certificate.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Cert<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Cert<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.cert_type.hash(state);
+        self.key_tag.hash(state);
+        self.algorithm.hash(state);
+        self.certificate.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Cert<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Cert
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Cert<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Cert {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Cert<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.certificate.as_ref().len())
+                .expect("long certificate")
+                .checked_add(
+                    CertType::COMPOSE_LEN
+                        + u16::COMPOSE_LEN
+                        + SecAlg::COMPOSE_LEN,
+                )
+                .expect("long certificate"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.cert_type.compose(target)?;
+        self.key_tag.compose(target)?;
+        self.algorithm.compose(target)?;
+        target.append_slice(self.certificate.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Cert<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} ", self.cert_type, self.key_tag, self.algorithm)?;
+        base64::display(&self.certificate, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Cert<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cert")
+            .field("cert_type", &self.cert_type)
+            .field("key_tag", &self.key_tag)
+            .field("algorithm", &self.algorithm)
+            .field("certificate", &self.certificate.as_ref())
+            .finish()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn cert_compose_parse_scan() {
+        let rdata = Cert::new(
+            CertType::Pgp,
+            12345,
+            SecAlg::RsaSha256,
+            Vec::from(b"certificate".as_ref()),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Cert::parse(parser));
+        test_scan(
+            &["PGP", "12345", "RSASHA256", "Y2VydGlmaWNhdGU="],
+            Cert::scan,
+            &rdata,
+        );
+        test_scan(
+            &["3", "12345", "8", "Y2VydGlm", "aWNhdGU="],
+            Cert::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), "PGP 12345 RSASHA256 Y2VydGlmaWNhdGU=");
+    }
+}
@@ -0,0 +1,447 @@
+//! Record data from [RFC 4701]: DHCID records.
+//!
+//! This RFC defines the DHCID record type which is used by DHCP servers
+//! and clients to record which client a name in a dynamic DNS update
+//! belongs to.
+//!
+//! With the `ring` feature enabled, the module also provides functions to
+//! create DHCID record data for a client and to check whether a record
+//! belongs to a client.
+//!
+//! [RFC 4701]: https://tools.ietf.org/html/rfc4701
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{DhcidDigestType, DhcidIdType, Rtype};
+use crate::base::name::PushError;
+#[cfg(feature = "ring")]
+use crate::base::name::ToDname;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "ring")]
+use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder, ShortBuf};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+#[cfg(feature = "ring")]
+use ring::digest;
+
+//------------ Dhcid ---------------------------------------------------------
+
+/// DHCID record data.
+///
+/// DHCID records associate a domain name with the DHCP client it has been
+/// assigned to. The record data consists of a two octet identifier type,
+/// a one octet digest type, and a digest over the client identifier and
+/// the domain name. It is treated as an opaque value by the DNS and is
+/// given in Base 64 in the presentation format.
+///
+/// The DHCID record type is defined in [RFC 4701].
+///
+/// [RFC 4701]: https://tools.ietf.org/html/rfc4701
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Dhcid<Octs> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    data: Octs,
+}
+
+impl<Octs> Dhcid<Octs> {
+    /// Creates new DHCID record data from the raw data.
+    ///
+    /// The function will fail if the data is longer than 65,535 octets.
+    pub fn new(data: Octs) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(data.as_ref().len())?;
+        Ok(unsafe { Dhcid::new_unchecked(data) })
+    }
+
+    /// Creates new DHCID record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the data is at most 65,535 octets
+    /// long.
+    pub unsafe fn new_unchecked(data: Octs) -> Self {
+        Dhcid { data }
+    }
+
+    /// Returns the raw data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Converts the record data into the raw data.
+    pub fn into_data(self) -> Octs {
+        self.data
+    }
+
+    /// Returns the identifier type.
+    ///
+    /// Returns `None` if the data is too short.
+    pub fn id_type(&self) -> Option<DhcidIdType>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        match self.data.as_ref() {
+            [first, second, ..] => {
+                Some(DhcidIdType::from_int(u16::from_be_bytes([
+                    *first, *second,
+                ])))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the digest type.
+    ///
+    /// Returns `None` if the data is too short.
+    pub fn digest_type(&self) -> Option<DhcidDigestType>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data
+            .as_ref()
+            .get(2)
+            .map(|&digest_type| DhcidDigestType::from_int(digest_type))
+    }
+
+    /// Returns the digest.
+    ///
+    /// Returns `None` if the data is too short.
+    pub fn digest(&self) -> Option<&[u8]>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.data.as_ref().get(3..)
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Dhcid<Target>, Target::Error> {
+        Ok(unsafe { Dhcid::new_unchecked(self.data.try_octets_into()?) })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = parser.remaining();
+        Ok(unsafe { Self::new_unchecked(parser.parse_octets(len)?) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(scanner.convert_entry(base64::SymbolConverter::new())?)
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+impl<Octs> Dhcid<Octs> {
+    /// Creates DHCID record data for a client and domain name.
+    ///
+    /// The identifier type states which DHCP client identifier is given in
+    /// `identifier`. For [`DhcidIdType::Chaddr`], this is the hardware
+    /// type followed by the hardware address, for
+    /// [`DhcidIdType::ClientId`] the data of the DHCPv4 client identifier
+    /// option, and for [`DhcidIdType::Duid`] the DHCPv6 DUID. The digest
+    /// is calculated using SHA-256.
+    pub fn from_identifier<N: ToDname + ?Sized>(
+        id_type: DhcidIdType,
+        identifier: &[u8],
+        fqdn: &N,
+    ) -> Result<Self, ShortBuf>
+    where
+        Octs: FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+    {
+        let digest = identifier_digest(identifier, fqdn);
+        let mut builder = <Octs as FromBuilder>::Builder::with_capacity(
+            usize::from(
+                DhcidIdType::COMPOSE_LEN + DhcidDigestType::COMPOSE_LEN,
+            ) + digest.as_ref().len(),
+        );
+        id_type.compose(&mut builder).map_err(Into::into)?;
+        DhcidDigestType::Sha256
+            .compose(&mut builder)
+            .map_err(Into::into)?;
+        builder.append_slice(digest.as_ref()).map_err(Into::into)?;
+        Ok(unsafe { Dhcid::new_unchecked(Octs::from_builder(builder)) })
+    }
+
+    /// Returns whether the record belongs to a client and domain name.
+    ///
+    /// The identifier type and client identifier are interpreted as
+    /// described for [`from_identifier`][Self::from_identifier]. This can
+    /// be used to determine whether a domain name may be updated on behalf
+    /// of a client. Records with a digest type other than SHA-256 never
+    /// match.
+    pub fn matches<N: ToDname + ?Sized>(
+        &self,
+        id_type: DhcidIdType,
+        identifier: &[u8],
+        fqdn: &N,
+    ) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        if self.id_type() != Some(id_type)
+            || self.digest_type() != Some(DhcidDigestType::Sha256)
+        {
+            return false;
+        }
+        match self.digest() {
+            Some(digest) => {
+                digest == identifier_digest(identifier, fqdn).as_ref()
+            }
+            None => false,
+        }
+    }
+}
+
+impl<SrcOcts> Dhcid<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Dhcid<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        Ok(unsafe {
+            Dhcid::new_unchecked(
+                self.data.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Dhcid<SrcOcts>> for Dhcid<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Dhcid<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Dhcid::new_unchecked(Octs::try_octets_from(source.data)?)
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Dhcid<Other>> for Dhcid<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Dhcid<Other>) -> bool {
+        self.data.as_ref() == other.data.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Dhcid<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Dhcid<Other>> for Dhcid<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Dhcid<Other>) -> Option<Ordering> {
+        self.data.as_ref().partial_cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Dhcid<Other>> for Dhcid<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Dhcid<Other>) -> Ordering {
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Dhcid<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Dhcid<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.data.as_ref().hash(state)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Dhcid<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Dhcid
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Dhcid<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Dhcid {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Dhcid<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(u16::try_from(self.data.as_ref().len()).expect("long DHCID"))
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Dhcid<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        base64::display(&self.data, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Dhcid<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Dhcid").field(&self.data.as_ref()).finish()
+    }
+}
+
+//------------ Identifier Digests --------------------------------------------
+
+/// Calculates the DHCID digest of a client identifier and domain name.
+///
+/// The digest is the SHA-256 hash over the client identifier followed by
+/// the domain name in canonical wire format, i.e., with all ASCII letters
+/// converted to lowercase.
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+pub fn identifier_digest<N: ToDname + ?Sized>(
+    identifier: &[u8],
+    fqdn: &N,
+) -> digest::Digest {
+    let mut ctx = digest::Context::new(&digest::SHA256);
+    ctx.update(identifier);
+    for label in fqdn.iter_labels() {
+        ctx.update(label.to_canonical().as_wire_slice());
+    }
+    ctx.finish()
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    // The example from RFC 4701, section 3.6.1.
+    const DUID: &[u8] =
+        b"\x00\x01\x00\x06\x41\x2d\xf1\x66\x01\x02\x03\x04\x05\x06";
+    const DHCID: &str = "AAIBY2/AuCccgoJbsaxcQc9TUapptP69lOjxfNuVAA2kjEA=";
+
+    #[test]
+    fn dhcid_compose_parse_scan() {
+        let rdata =
+            Dhcid::new(base64::decode::<Vec<u8>>(DHCID).unwrap()).unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Dhcid::parse(parser));
+        test_scan(&[DHCID], Dhcid::scan, &rdata);
+        test_scan(&[&DHCID[..20], &DHCID[20..]], Dhcid::scan, &rdata);
+        assert_eq!(rdata.to_string(), DHCID);
+        assert_eq!(rdata.id_type(), Some(DhcidIdType::Duid));
+        assert_eq!(rdata.digest_type(), Some(DhcidDigestType::Sha256));
+        assert_eq!(rdata.digest().map(<[u8]>::len), Some(32));
+    }
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn dhcid_from_identifier() {
+        use crate::base::name::Dname;
+        use core::str::FromStr;
+
+        let fqdn = Dname::<Vec<u8>>::from_str("chi6.example.com").unwrap();
+        let rdata =
+            Dhcid::<Vec<u8>>::from_identifier(DhcidIdType::Duid, DUID, &fqdn)
+                .unwrap();
+        assert_eq!(rdata.to_string(), DHCID);
+        assert!(rdata.matches(DhcidIdType::Duid, DUID, &fqdn));
+        assert!(rdata.matches(
+            DhcidIdType::Duid,
+            DUID,
+            &Dname::<Vec<u8>>::from_str("CHI6.Example.COM").unwrap()
+        ));
+        assert!(!rdata.matches(
+            DhcidIdType::Duid,
+            DUID,
+            &Dname::<Vec<u8>>::from_str("chi.example.com").unwrap()
+        ));
+        assert!(!rdata.matches(DhcidIdType::Duid, &DUID[1..], &fqdn));
+        assert!(!rdata.matches(DhcidIdType::ClientId, DUID, &fqdn));
+    }
+}
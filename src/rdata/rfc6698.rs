@@ -0,0 +1,684 @@
+//! Record data from [RFC 6698]: TLSA records.
+//!
+//! This RFC defines the TLSA record type used by DNS-Based Authentication
+//! of Named Entities (DANE) to associate a TLS server certificate or public
+//! key with the domain name where the record is found.
+//!
+//! With the `ring` feature enabled, the module also provides a means to
+//! check whether a certificate matches a TLSA record.
+//!
+//! [RFC 6698]: https://tools.ietf.org/html/rfc6698
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{
+    Rtype, TlsaCertUsage, TlsaMatchingType, TlsaSelector,
+};
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Tlsa ----------------------------------------------------------
+
+/// TLSA record data.
+///
+/// TLSA records associate a certificate or public key with the TLS server
+/// found at the domain name of the record. The certificate usage field
+/// determines how the association is to be used during verification, the
+/// selector determines which part of the certificate is to be matched, and
+/// the matching type determines whether the association data contains the
+/// selected part verbatim or a hash of it.
+///
+/// The TLSA record type is defined in [RFC 6698].
+///
+/// [RFC 6698]: https://tools.ietf.org/html/rfc6698
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Tlsa<Octs> {
+    cert_usage: TlsaCertUsage,
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    data: Octs,
+}
+
+impl<Octs> Tlsa<Octs> {
+    /// Creates new TLSA record data from its components.
+    ///
+    /// The function will fail if the association data is longer than
+    /// 65,532 octets.
+    pub fn new(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            3usize.checked_add(data.as_ref().len()).expect("long data"),
+        )?;
+        Ok(unsafe {
+            Tlsa::new_unchecked(cert_usage, selector, matching_type, data)
+        })
+    }
+
+    /// Creates new TLSA record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Self {
+        Tlsa {
+            cert_usage,
+            selector,
+            matching_type,
+            data,
+        }
+    }
+
+    /// Returns the certificate usage.
+    pub fn cert_usage(&self) -> TlsaCertUsage {
+        self.cert_usage
+    }
+
+    /// Returns the selector.
+    pub fn selector(&self) -> TlsaSelector {
+        self.selector
+    }
+
+    /// Returns the matching type.
+    pub fn matching_type(&self) -> TlsaMatchingType {
+        self.matching_type
+    }
+
+    /// Returns the certificate association data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Converts the record data into the certificate association data.
+    pub fn into_data(self) -> Octs {
+        self.data
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Tlsa<Target>, Target::Error> {
+        Ok(unsafe {
+            Tlsa::new_unchecked(
+                self.cert_usage,
+                self.selector,
+                self.matching_type,
+                self.data.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(3) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                TlsaCertUsage::parse(parser)?,
+                TlsaSelector::parse(parser)?,
+                TlsaMatchingType::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            TlsaCertUsage::scan(scanner)?,
+            TlsaSelector::scan(scanner)?,
+            TlsaMatchingType::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+impl<Octs: AsRef<[u8]>> Tlsa<Octs> {
+    /// Returns whether a certificate matches the record.
+    ///
+    /// The certificate must be given in its DER encoding. Depending on the
+    /// selector of the record, either the full certificate or its
+    /// SubjectPublicKeyInfo is matched against the association data.
+    ///
+    /// The method only considers the selector, matching type, and
+    /// association data. Checking the certificate usage is left to the
+    /// caller.
+    pub fn matches_certificate(
+        &self,
+        cert: &[u8],
+    ) -> Result<bool, DaneError> {
+        match_certificate(
+            self.selector,
+            self.matching_type,
+            self.data.as_ref(),
+            cert,
+        )
+    }
+
+    /// Returns whether a public key matches the record.
+    ///
+    /// The key must be given as a DER-encoded SubjectPublicKeyInfo. If the
+    /// selector of the record requires the full certificate, the key never
+    /// matches.
+    pub fn matches_spki(&self, spki: &[u8]) -> Result<bool, DaneError> {
+        match_spki(
+            self.selector,
+            self.matching_type,
+            self.data.as_ref(),
+            spki,
+        )
+    }
+}
+
+impl<SrcOcts> Tlsa<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Tlsa<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            cert_usage,
+            selector,
+            matching_type,
+            data,
+        } = self;
+        Ok(unsafe {
+            Tlsa::new_unchecked(
+                cert_usage,
+                selector,
+                matching_type,
+                data.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Tlsa<SrcOcts>> for Tlsa<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Tlsa<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Tlsa::new_unchecked(
+                source.cert_usage,
+                source.selector,
+                source.matching_type,
+                Octs::try_octets_from(source.data)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Tlsa<Other>> for Tlsa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Tlsa<Other>) -> bool {
+        self.cert_usage == other.cert_usage
+            && self.selector == other.selector
+            && self.matching_type == other.matching_type
+            && self.data.as_ref() == other.data.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Tlsa<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Tlsa<Other>> for Tlsa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Tlsa<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Tlsa<Other>> for Tlsa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Tlsa<Other>) -> Ordering {
+        match self.cert_usage.cmp(&other.cert_usage) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.selector.cmp(&other.selector) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.matching_type.cmp(&other.matching_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Tlsa<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Tlsa<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.cert_usage.hash(state);
+        self.selector.hash(state);
+        self.matching_type.hash(state);
+        self.data.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Tlsa<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Tlsa
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Tlsa<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Tlsa {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Tlsa<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.data.as_ref().len())
+                .expect("long data")
+                .checked_add(
+                    TlsaCertUsage::COMPOSE_LEN
+                        + TlsaSelector::COMPOSE_LEN
+                        + TlsaMatchingType::COMPOSE_LEN,
+                )
+                .expect("long data"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.cert_usage.compose(target)?;
+        self.selector.compose(target)?;
+        self.matching_type.compose(target)?;
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Tlsa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.cert_usage, self.selector, self.matching_type
+        )?;
+        base16::display(&self.data, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Tlsa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tlsa")
+            .field("cert_usage", &self.cert_usage)
+            .field("selector", &self.selector)
+            .field("matching_type", &self.matching_type)
+            .field("data", &self.data.as_ref())
+            .finish()
+    }
+}
+
+//------------ Certificate Matching ------------------------------------------
+
+/// Matches a DER-encoded certificate against association data.
+#[cfg(feature = "ring")]
+pub(super) fn match_certificate(
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    data: &[u8],
+    cert: &[u8],
+) -> Result<bool, DaneError> {
+    match selector {
+        TlsaSelector::Cert => match_content(matching_type, data, cert),
+        TlsaSelector::Spki => match_content(
+            matching_type,
+            data,
+            cert_spki(cert).ok_or(DaneError::MalformedCertificate)?,
+        ),
+        _ => Err(DaneError::UnsupportedSelector),
+    }
+}
+
+/// Matches a DER-encoded SubjectPublicKeyInfo against association data.
+#[cfg(feature = "ring")]
+pub(super) fn match_spki(
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    data: &[u8],
+    spki: &[u8],
+) -> Result<bool, DaneError> {
+    match selector {
+        TlsaSelector::Cert => Ok(false),
+        TlsaSelector::Spki => match_content(matching_type, data, spki),
+        _ => Err(DaneError::UnsupportedSelector),
+    }
+}
+
+/// Matches the selected content against association data.
+#[cfg(feature = "ring")]
+fn match_content(
+    matching_type: TlsaMatchingType,
+    data: &[u8],
+    content: &[u8],
+) -> Result<bool, DaneError> {
+    use ring::digest;
+
+    let alg = match matching_type {
+        TlsaMatchingType::Full => return Ok(data == content),
+        TlsaMatchingType::Sha256 => &digest::SHA256,
+        TlsaMatchingType::Sha512 => &digest::SHA512,
+        _ => return Err(DaneError::UnsupportedMatchingType),
+    };
+    Ok(digest::digest(alg, content).as_ref() == data)
+}
+
+/// Returns the DER-encoded SubjectPublicKeyInfo of a certificate.
+///
+/// This walks the DER encoding of the certificate just far enough to find
+/// the SubjectPublicKeyInfo. Returns `None` if the certificate is
+/// malformed.
+#[cfg(feature = "ring")]
+fn cert_spki(cert: &[u8]) -> Option<&[u8]> {
+    // Certificate ::= SEQUENCE {
+    //     tbsCertificate       TBSCertificate,
+    //     ... }
+    let (tag, _, cert, tail) = der_split(cert)?;
+    if tag != 0x30 || !tail.is_empty() {
+        return None;
+    }
+    let (tag, _, mut tbs, _) = der_split(cert)?;
+    if tag != 0x30 {
+        return None;
+    }
+
+    // TBSCertificate ::= SEQUENCE {
+    //     version         [0]  EXPLICIT Version DEFAULT v1,
+    //     serialNumber         CertificateSerialNumber,
+    //     signature            AlgorithmIdentifier,
+    //     issuer               Name,
+    //     validity             Validity,
+    //     subject              Name,
+    //     subjectPublicKeyInfo SubjectPublicKeyInfo,
+    //     ... }
+    let mut skip: &[u8] = &[0x02, 0x30, 0x30, 0x30, 0x30];
+    if tbs.first() == Some(&0xA0) {
+        tbs = der_split(tbs)?.3;
+    }
+    loop {
+        let (tag, tlv, _, tail) = der_split(tbs)?;
+        match skip.split_first() {
+            Some((&expected, rest)) => {
+                if tag != expected {
+                    return None;
+                }
+                skip = rest;
+                tbs = tail;
+            }
+            None => return if tag == 0x30 { Some(tlv) } else { None },
+        }
+    }
+}
+
+/// Splits a DER-encoded value off the beginning of `data`.
+///
+/// Returns the tag, the complete encoding of the value, its content, and
+/// the remaining data. Only single octet tags and definite lengths of up to
+/// four octets are supported.
+#[cfg(feature = "ring")]
+fn der_split(data: &[u8]) -> Option<(u8, &[u8], &[u8], &[u8])> {
+    let (&tag, rest) = data.split_first()?;
+    if tag & 0x1F == 0x1F {
+        return None;
+    }
+    let (&first, mut rest) = rest.split_first()?;
+    let len = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let count = usize::from(first & 0x7F);
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let (octets, tail) = rest.split_at(count);
+        rest = tail;
+        octets
+            .iter()
+            .fold(0usize, |len, &octet| (len << 8) | usize::from(octet))
+    };
+    if rest.len() < len {
+        return None;
+    }
+    let header_len = data.len() - rest.len();
+    let (content, tail) = rest.split_at(len);
+    Some((tag, &data[..header_len + len], content, tail))
+}
+
+//============ Error Types ===================================================
+
+//------------ DaneError -----------------------------------------------------
+
+/// A certificate could not be matched against a TLSA or SMIMEA record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DaneError {
+    /// The record uses a selector that isn’t supported.
+    UnsupportedSelector,
+
+    /// The record uses a matching type that isn’t supported.
+    UnsupportedMatchingType,
+
+    /// The certificate is not a valid DER-encoded certificate.
+    MalformedCertificate,
+}
+
+impl fmt::Display for DaneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            DaneError::UnsupportedSelector => "unsupported selector",
+            DaneError::UnsupportedMatchingType => "unsupported matching type",
+            DaneError::MalformedCertificate => "malformed certificate",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DaneError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn tlsa_compose_parse_scan() {
+        let rdata = Tlsa::new(
+            TlsaCertUsage::DaneEe,
+            TlsaSelector::Spki,
+            TlsaMatchingType::Sha256,
+            b"key",
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Tlsa::parse(parser));
+        test_scan(&["3", "1", "1", "6b", "6579"], Tlsa::scan, &rdata);
+        assert_eq!(rdata.to_string(), "3 1 1 6B6579");
+    }
+
+    /// A minimal DER-encoded certificate.
+    ///
+    /// Only the structure up to the SubjectPublicKeyInfo is realistic.
+    #[cfg(feature = "ring")]
+    const CERT: &[u8] = &[
+        0x30, 0x1D, // Certificate
+        0x30, 0x16, // TBSCertificate
+        0xA0, 0x03, 0x02, 0x01, 0x02, // version
+        0x02, 0x01, 0x01, // serialNumber
+        0x30, 0x00, // signature
+        0x30, 0x00, // issuer
+        0x30, 0x00, // validity
+        0x30, 0x00, // subject
+        0x30, 0x04, 0x30, 0x00, 0x03, 0x00, // subjectPublicKeyInfo
+        0x30, 0x00, // signatureAlgorithm
+        0x03, 0x01, 0x00, // signatureValue
+    ];
+
+    #[cfg(feature = "ring")]
+    const SPKI: &[u8] = &[0x30, 0x04, 0x30, 0x00, 0x03, 0x00];
+
+    #[cfg(feature = "ring")]
+    fn tlsa(
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: &[u8],
+    ) -> Tlsa<&[u8]> {
+        Tlsa::new(TlsaCertUsage::DaneEe, selector, matching_type, data)
+            .unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn cert_spki() {
+        assert_eq!(super::cert_spki(CERT), Some(SPKI));
+        assert_eq!(super::cert_spki(&CERT[..CERT.len() - 1]), None);
+        assert_eq!(super::cert_spki(SPKI), None);
+    }
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn matches_certificate() {
+        use ring::digest::{digest, SHA256, SHA512};
+
+        let sha256 = digest(&SHA256, SPKI);
+        let sha512 = digest(&SHA512, CERT);
+
+        let rdata = tlsa(TlsaSelector::Cert, TlsaMatchingType::Full, CERT);
+        assert_eq!(rdata.matches_certificate(CERT), Ok(true));
+        assert_eq!(rdata.matches_spki(SPKI), Ok(false));
+
+        let rdata = tlsa(
+            TlsaSelector::Spki,
+            TlsaMatchingType::Sha256,
+            sha256.as_ref(),
+        );
+        assert_eq!(rdata.matches_certificate(CERT), Ok(true));
+        assert_eq!(rdata.matches_spki(SPKI), Ok(true));
+        assert_eq!(rdata.matches_spki(&SPKI[..4]), Ok(false));
+
+        let rdata = tlsa(
+            TlsaSelector::Cert,
+            TlsaMatchingType::Sha512,
+            sha512.as_ref(),
+        );
+        assert_eq!(rdata.matches_certificate(CERT), Ok(true));
+
+        let rdata = tlsa(TlsaSelector::Spki, TlsaMatchingType::Full, SPKI);
+        assert_eq!(
+            rdata.matches_certificate(&CERT[1..]),
+            Err(DaneError::MalformedCertificate)
+        );
+
+        let rdata =
+            tlsa(TlsaSelector::Spki, TlsaMatchingType::PrivMatch, SPKI);
+        assert_eq!(
+            rdata.matches_spki(SPKI),
+            Err(DaneError::UnsupportedMatchingType)
+        );
+    }
+}
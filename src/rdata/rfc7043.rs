@@ -0,0 +1,364 @@
+//! Record data from [RFC 7043]: EUI48 and EUI64 records.
+//!
+//! This RFC defines record types for 48 bit and 64 bit Extended Unique
+//! Identifiers, i.e., MAC addresses and similar identifiers.
+//!
+//! [RFC 7043]: https://tools.ietf.org/html/rfc7043
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::PushError;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::fmt;
+use octseq::octets::OctetsFrom;
+use octseq::parse::Parser;
+
+//------------ Eui48 ---------------------------------------------------------
+
+/// EUI48 record data.
+///
+/// EUI48 records convey a 48 bit Extended Unique Identifier. The
+/// presentation format is six two-digit hexadecimal numbers separated by
+/// hyphens, e.g., `00-00-5e-00-53-2a`.
+///
+/// The EUI48 record type is defined in [RFC 7043, section 3].
+///
+/// [RFC 7043, section 3]: https://tools.ietf.org/html/rfc7043#section-3
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eui48 {
+    addr: [u8; 6],
+}
+
+impl Eui48 {
+    /// Creates new EUI48 record data from the address octets.
+    pub fn new(addr: [u8; 6]) -> Self {
+        Eui48 { addr }
+    }
+
+    /// Returns the address octets.
+    pub fn addr(&self) -> [u8; 6] {
+        self.addr
+    }
+
+    pub fn flatten_into(self) -> Result<Self, PushError> {
+        Ok(self)
+    }
+
+    pub(super) fn convert_octets<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+        parser: &mut Parser<Octs>,
+    ) -> Result<Self, ParseError> {
+        let mut addr = [0; 6];
+        parser.parse_buf(&mut addr)?;
+        Ok(Self::new(addr))
+    }
+
+    pub fn scan<S: Scanner>(scanner: &mut S) -> Result<Self, S::Error> {
+        let mut addr = [0; 6];
+        scanner.scan_ascii_str(|s| {
+            scan_hyphenated(s, &mut addr)
+                .ok_or_else(|| S::Error::custom("expected EUI48 address"))
+        })?;
+        Ok(Self::new(addr))
+    }
+}
+
+//--- OctetsFrom
+
+impl OctetsFrom<Eui48> for Eui48 {
+    type Error = Infallible;
+
+    fn try_octets_from(source: Eui48) -> Result<Self, Self::Error> {
+        Ok(source)
+    }
+}
+
+//--- From
+
+impl From<[u8; 6]> for Eui48 {
+    fn from(addr: [u8; 6]) -> Self {
+        Self::new(addr)
+    }
+}
+
+impl From<Eui48> for [u8; 6] {
+    fn from(eui: Eui48) -> Self {
+        eui.addr
+    }
+}
+
+//--- CanonicalOrd
+
+impl CanonicalOrd for Eui48 {
+    fn canonical_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl RecordData for Eui48 {
+    fn rtype(&self) -> Rtype {
+        Rtype::Eui48
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseRecordData<'a, Octs> for Eui48 {
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Eui48 {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ComposeRecordData for Eui48 {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(6)
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(&self.addr)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for Eui48 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_hyphenated(&self.addr, f)
+    }
+}
+
+//------------ Eui64 ---------------------------------------------------------
+
+/// EUI64 record data.
+///
+/// EUI64 records convey a 64 bit Extended Unique Identifier. The
+/// presentation format is eight two-digit hexadecimal numbers separated by
+/// hyphens, e.g., `00-00-5e-ef-10-00-00-2a`.
+///
+/// The EUI64 record type is defined in [RFC 7043, section 4].
+///
+/// [RFC 7043, section 4]: https://tools.ietf.org/html/rfc7043#section-4
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eui64 {
+    addr: [u8; 8],
+}
+
+impl Eui64 {
+    /// Creates new EUI64 record data from the address octets.
+    pub fn new(addr: [u8; 8]) -> Self {
+        Eui64 { addr }
+    }
+
+    /// Returns the address octets.
+    pub fn addr(&self) -> [u8; 8] {
+        self.addr
+    }
+
+    pub fn flatten_into(self) -> Result<Self, PushError> {
+        Ok(self)
+    }
+
+    pub(super) fn convert_octets<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
+        parser: &mut Parser<Octs>,
+    ) -> Result<Self, ParseError> {
+        let mut addr = [0; 8];
+        parser.parse_buf(&mut addr)?;
+        Ok(Self::new(addr))
+    }
+
+    pub fn scan<S: Scanner>(scanner: &mut S) -> Result<Self, S::Error> {
+        let mut addr = [0; 8];
+        scanner.scan_ascii_str(|s| {
+            scan_hyphenated(s, &mut addr)
+                .ok_or_else(|| S::Error::custom("expected EUI64 address"))
+        })?;
+        Ok(Self::new(addr))
+    }
+}
+
+//--- OctetsFrom
+
+impl OctetsFrom<Eui64> for Eui64 {
+    type Error = Infallible;
+
+    fn try_octets_from(source: Eui64) -> Result<Self, Self::Error> {
+        Ok(source)
+    }
+}
+
+//--- From
+
+impl From<[u8; 8]> for Eui64 {
+    fn from(addr: [u8; 8]) -> Self {
+        Self::new(addr)
+    }
+}
+
+impl From<Eui64> for [u8; 8] {
+    fn from(eui: Eui64) -> Self {
+        eui.addr
+    }
+}
+
+//--- CanonicalOrd
+
+impl CanonicalOrd for Eui64 {
+    fn canonical_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl RecordData for Eui64 {
+    fn rtype(&self) -> Rtype {
+        Rtype::Eui64
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParseRecordData<'a, Octs> for Eui64 {
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Eui64 {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ComposeRecordData for Eui64 {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(8)
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(&self.addr)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for Eui64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_hyphenated(&self.addr, f)
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Scans the hyphenated presentation format into `addr`.
+///
+/// The string must consist of exactly as many two-digit hexadecimal
+/// numbers as `addr` is long, separated by single hyphens. Returns `None`
+/// otherwise.
+fn scan_hyphenated(s: &str, addr: &mut [u8]) -> Option<()> {
+    let mut parts = s.split('-');
+    for octet in addr.iter_mut() {
+        let part = parts.next()?;
+        if part.len() != 2 || !part.bytes().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        *octet = u8::from_str_radix(part, 16).ok()?;
+    }
+    match parts.next() {
+        Some(_) => None,
+        None => Some(()),
+    }
+}
+
+/// Formats the address in the hyphenated presentation format.
+fn fmt_hyphenated(addr: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    let mut addr = addr.iter();
+    if let Some(octet) = addr.next() {
+        write!(f, "{:02x}", octet)?;
+    }
+    for octet in addr {
+        write!(f, "-{:02x}", octet)?;
+    }
+    Ok(())
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn eui48_compose_parse_scan() {
+        let rdata = Eui48::new([0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a]);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, Eui48::parse);
+        test_scan(&["00-00-5e-00-53-2a"], Eui48::scan, &rdata);
+        test_scan(&["00-00-5E-00-53-2A"], Eui48::scan, &rdata);
+        assert_eq!(rdata.to_string(), "00-00-5e-00-53-2a");
+    }
+
+    #[test]
+    fn eui64_compose_parse_scan() {
+        let rdata =
+            Eui64::new([0x00, 0x00, 0x5e, 0xef, 0x10, 0x00, 0x00, 0x2a]);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, Eui64::parse);
+        test_scan(&["00-00-5e-ef-10-00-00-2a"], Eui64::scan, &rdata);
+        assert_eq!(rdata.to_string(), "00-00-5e-ef-10-00-00-2a");
+    }
+
+    #[test]
+    fn scan_hyphenated_errors() {
+        let mut addr = [0; 6];
+        assert!(scan_hyphenated("00-00-5e-00-53", &mut addr).is_none());
+        assert!(scan_hyphenated("00-00-5e-00-53-2a-00", &mut addr).is_none());
+        assert!(scan_hyphenated("00:00:5e:00:53:2a", &mut addr).is_none());
+        assert!(scan_hyphenated("0-00-5e-00-53-2a0", &mut addr).is_none());
+        assert!(scan_hyphenated("+0-00-5e-00-53-2a", &mut addr).is_none());
+    }
+}
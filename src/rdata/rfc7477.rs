@@ -0,0 +1,786 @@
+//! Record data from [RFC 7477]: CSYNC records.
+//!
+//! This RFC defines the CSYNC record type through which a child zone can
+//! ask its parent to synchronize the NS records and glue of the delegation
+//! with the data published in the child zone.
+//!
+//! With the `std` feature enabled, the module also provides the
+//! [`CsyncProcessor`] that implements the parent side of the procedure.
+//!
+//! [RFC 7477]: https://tools.ietf.org/html/rfc7477
+
+use super::rfc4034::RtypeBitmap;
+#[cfg(feature = "std")]
+use super::{Aaaa, Ns, Soa, A};
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::PushError;
+#[cfg(feature = "std")]
+use crate::base::name::ToDname;
+use crate::base::rdata::{ComposeRecordData, ParseRecordData, RecordData};
+#[cfg(feature = "std")]
+use crate::base::rrset::Rrset;
+use crate::base::scan::{Scan, Scanner};
+use crate::base::serial::Serial;
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+//------------ Csync ---------------------------------------------------------
+
+/// CSYNC record data.
+///
+/// CSYNC records are placed at the apex of a child zone. They list the
+/// record types for which the parent should copy the records from the
+/// child into the delegation. Currently, these are the NS records as well
+/// as the A and AAAA glue records of in-bailiwick name servers.
+///
+/// The serial field contains a SOA serial of the child zone. If the
+/// soaminimum flag is set, the parent must not use data from a version of
+/// the child zone with a smaller serial. If the immediate flag is not set,
+/// the parent must wait for an approval by other means before processing
+/// the record.
+///
+/// The CSYNC record type is defined in [RFC 7477].
+///
+/// [RFC 7477]: https://tools.ietf.org/html/rfc7477
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder
+                + octseq::builder::Truncate
+                + AsRef<[u8]> + AsMut<[u8]>,
+        ",
+    ))
+)]
+pub struct Csync<Octs> {
+    serial: Serial,
+    flags: u16,
+    types: RtypeBitmap<Octs>,
+}
+
+impl<Octs> Csync<Octs> {
+    /// Creates new CSYNC record data from its components.
+    pub fn new(serial: Serial, flags: u16, types: RtypeBitmap<Octs>) -> Self {
+        Csync {
+            serial,
+            flags,
+            types,
+        }
+    }
+
+    /// Returns the SOA serial of the child zone.
+    pub fn serial(&self) -> Serial {
+        self.serial
+    }
+
+    /// Returns the flags.
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// Returns whether the immediate flag is set.
+    ///
+    /// If the flag is set, the parent may process the record right away.
+    /// Otherwise, it must wait until the change has been approved through
+    /// some other channel. See [RFC 7477, Section 2.1.1.2].
+    ///
+    /// [RFC 7477, Section 2.1.1.2]: https://tools.ietf.org/html/rfc7477#section-2.1.1.2
+    pub fn is_immediate(&self) -> bool {
+        self.flags & 0b0000_0000_0000_0001 != 0
+    }
+
+    /// Returns whether the soaminimum flag is set.
+    ///
+    /// If the flag is set, the parent must not use data from a version of
+    /// the child zone with a SOA serial smaller than the serial of the
+    /// record. See [RFC 7477, Section 2.1.1.2].
+    ///
+    /// [RFC 7477, Section 2.1.1.2]: https://tools.ietf.org/html/rfc7477#section-2.1.1.2
+    pub fn is_soa_minimum(&self) -> bool {
+        self.flags & 0b0000_0000_0000_0010 != 0
+    }
+
+    /// Returns the record types to be synchronized.
+    pub fn types(&self) -> &RtypeBitmap<Octs> {
+        &self.types
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Csync<Target>, Target::Error> {
+        Ok(Csync::new(
+            self.serial,
+            self.flags,
+            self.types.convert_octets()?,
+        ))
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Ok(Csync::new(
+            Serial::parse(parser)?,
+            u16::parse(parser)?,
+            RtypeBitmap::parse(parser)?,
+        ))
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error> {
+        Ok(Csync::new(
+            Serial::scan(scanner)?,
+            u16::scan(scanner)?,
+            RtypeBitmap::scan(scanner)?,
+        ))
+    }
+}
+
+impl<SrcOcts> Csync<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Csync<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            serial,
+            flags,
+            types,
+        } = self;
+        Ok(Csync::new(
+            serial,
+            flags,
+            types.try_octets_into().map_err(Into::into)?,
+        ))
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Csync<SrcOcts>> for Csync<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Csync<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(Csync::new(
+            source.serial,
+            source.flags,
+            RtypeBitmap::try_octets_from(source.types)?,
+        ))
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Csync<Other>> for Csync<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Csync<Other>) -> bool {
+        self.serial == other.serial
+            && self.flags == other.flags
+            && self.types == other.types
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Csync<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Csync<Other>> for Csync<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Csync<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Csync<Other>> for Csync<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Csync<Other>) -> Ordering {
+        match self.serial.canonical_cmp(&other.serial) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.flags.cmp(&other.flags) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.types.canonical_cmp(&other.types)
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Csync<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Csync<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.serial.hash(state);
+        self.flags.hash(state);
+        self.types.hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Csync<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Csync
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Csync<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Csync {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Csync<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            self.types
+                .compose_len()
+                .checked_add(u32::COMPOSE_LEN + u16::COMPOSE_LEN)
+                .expect("long type bitmap"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.serial.compose(target)?;
+        self.flags.compose(target)?;
+        self.types.compose(target)
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Csync<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.serial, self.flags, self.types)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Csync<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Csync")
+            .field("serial", &self.serial)
+            .field("flags", &self.flags)
+            .field("types", &self.types)
+            .finish()
+    }
+}
+
+//------------ CsyncProcessor ------------------------------------------------
+
+/// The parent side of CSYNC processing.
+///
+/// A processor is created for the apex name of a child zone. Its
+/// [`process`][Self::process] method takes the CSYNC and SOA record data
+/// as well as the NS, A, and AAAA record sets retrieved from the child
+/// zone and determines which parts of the delegation in the parent zone
+/// should be replaced, following the rules of section 3 of [RFC 7477].
+///
+/// The processor doesn’t retrieve any data itself. It is the caller’s
+/// responsibility to query the child’s authoritative servers for all the
+/// data and to DNSSEC-validate it before handing it to the processor.
+///
+/// Because the processor only looks at the data it is given, a few
+/// aspects of the procedure have to be provided by the caller. If the
+/// CSYNC record doesn’t have the immediate flag set, the caller needs to
+/// declare through [`approve`][Self::approve] that the change has been
+/// approved. In order to guard against replayed data, the child’s SOA
+/// serial of the last successful processing can be given via
+/// [`last_serial`][Self::last_serial].
+///
+/// [RFC 7477]: https://tools.ietf.org/html/rfc7477
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct CsyncProcessor<Name> {
+    /// The apex name of the child zone.
+    apex: Name,
+
+    /// The child’s SOA serial when the last CSYNC record was processed.
+    last_serial: Option<Serial>,
+
+    /// Has the update been approved out of band?
+    approved: bool,
+}
+
+#[cfg(feature = "std")]
+impl<Name: ToDname> CsyncProcessor<Name> {
+    /// Creates a new processor for the child zone with the given apex.
+    pub fn new(apex: Name) -> Self {
+        CsyncProcessor {
+            apex,
+            last_serial: None,
+            approved: false,
+        }
+    }
+
+    /// Sets the child’s SOA serial of the last successful processing.
+    ///
+    /// If set, data from a version of the child zone with an older serial
+    /// will be rejected.
+    pub fn last_serial(mut self, serial: Serial) -> Self {
+        self.last_serial = Some(serial);
+        self
+    }
+
+    /// Sets whether the update has been approved out of band.
+    ///
+    /// An approval is necessary for processing CSYNC records that don’t
+    /// have the immediate flag set.
+    pub fn approve(mut self, approved: bool) -> Self {
+        self.approved = approved;
+        self
+    }
+
+    /// Returns the apex name of the child zone.
+    pub fn apex(&self) -> &Name {
+        &self.apex
+    }
+
+    /// Determines the delegation records to be updated.
+    ///
+    /// The `csync` and `soa` arguments are the record data of the CSYNC and
+    /// SOA records at the apex of the child zone. The `ns` argument is the
+    /// child’s apex NS record set and `a` and `aaaa` are the address
+    /// record sets the child has for its name servers. Address record sets
+    /// for other names are ignored. Both kinds of address record sets
+    /// should always be given since they are used to check that all
+    /// in-bailiwick name servers have addresses even if only one kind is
+    /// to be synchronized.
+    ///
+    /// The method returns an error if any of the conditions of RFC 7477
+    /// for processing the record are not met. In this case, the delegation
+    /// must be left unchanged.
+    pub fn process<'a, Octs, SoaName, N>(
+        &self,
+        csync: &Csync<Octs>,
+        soa: &Soa<SoaName>,
+        ns: &'a Rrset<N, Ns<N>>,
+        a: impl IntoIterator<Item = &'a Rrset<N, A>>,
+        aaaa: impl IntoIterator<Item = &'a Rrset<N, Aaaa>>,
+    ) -> Result<DelegationUpdate<'a, N>, CsyncError>
+    where
+        Octs: AsRef<[u8]>,
+        N: ToDname,
+    {
+        // Section 2.1.1.2: Unknown flags mean we must not process the
+        // record.
+        if csync.flags() & !0b0000_0000_0000_0011 != 0 {
+            return Err(CsyncError::UnknownFlags);
+        }
+
+        // Likewise for types we don’t know how to synchronize.
+        if let Some(rtype) = csync.types().iter().find(|rtype| {
+            !matches!(*rtype, Rtype::Ns | Rtype::A | Rtype::Aaaa)
+        }) {
+            return Err(CsyncError::UnsupportedType(rtype));
+        }
+
+        if !csync.is_immediate() && !self.approved {
+            return Err(CsyncError::NotApproved);
+        }
+
+        // Serial numbers may be incomparable in which case we err on the
+        // side of caution.
+        if csync.is_soa_minimum()
+            && !matches!(
+                soa.serial().partial_cmp(&csync.serial()),
+                Some(Ordering::Equal | Ordering::Greater)
+            )
+        {
+            return Err(CsyncError::SoaMinimum);
+        }
+        if let Some(last) = self.last_serial {
+            if !matches!(
+                soa.serial().partial_cmp(&last),
+                Some(Ordering::Equal | Ordering::Greater)
+            ) {
+                return Err(CsyncError::Stale);
+            }
+        }
+
+        if !ns.owner().name_eq(&self.apex) {
+            return Err(CsyncError::OwnerMismatch);
+        }
+        let types = csync.types();
+        if types.contains(Rtype::Ns) && ns.is_empty() {
+            return Err(CsyncError::EmptyNs);
+        }
+
+        // Section 3.2.2: Only glue for in-bailiwick name servers is copied.
+        let servers: Vec<_> = ns
+            .iter()
+            .map(Ns::nsdname)
+            .filter(|name| name.ends_with(&self.apex))
+            .collect();
+        let a = a
+            .into_iter()
+            .filter(|set| {
+                !set.is_empty()
+                    && servers.iter().any(|name| set.owner().name_eq(*name))
+            })
+            .collect::<Vec<_>>();
+        let aaaa = aaaa
+            .into_iter()
+            .filter(|set| {
+                !set.is_empty()
+                    && servers.iter().any(|name| set.owner().name_eq(*name))
+            })
+            .collect::<Vec<_>>();
+
+        // An in-bailiwick name server without any addresses would render
+        // the delegation lame. Whether a server has addresses doesn’t
+        // depend on which address types are synchronized, so we consider
+        // all of them.
+        if types.contains(Rtype::A) || types.contains(Rtype::Aaaa) {
+            for name in &servers {
+                let found = a.iter().any(|set| set.owner().name_eq(*name))
+                    || aaaa.iter().any(|set| set.owner().name_eq(*name));
+                if !found {
+                    return Err(CsyncError::MissingGlue);
+                }
+            }
+        }
+
+        Ok(DelegationUpdate {
+            ns: types.contains(Rtype::Ns).then_some(ns),
+            a: types.contains(Rtype::A).then_some(a),
+            aaaa: types.contains(Rtype::Aaaa).then_some(aaaa),
+        })
+    }
+}
+
+//------------ DelegationUpdate ----------------------------------------------
+
+/// The delegation records to be updated in the parent zone.
+///
+/// This type is returned by [`CsyncProcessor::process`]. For each kind of
+/// record, the update either contains the complete new data that replaces
+/// the data currently in the parent zone or nothing if the data should be
+/// left alone.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct DelegationUpdate<'a, Name> {
+    /// The new NS record set if it should be replaced.
+    ns: Option<&'a Rrset<Name, Ns<Name>>>,
+
+    /// The new A glue record sets if they should be replaced.
+    a: Option<Vec<&'a Rrset<Name, A>>>,
+
+    /// The new AAAA glue record sets if they should be replaced.
+    aaaa: Option<Vec<&'a Rrset<Name, Aaaa>>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, Name> DelegationUpdate<'a, Name> {
+    /// Returns the NS record set replacing the delegation’s NS records.
+    pub fn ns(&self) -> Option<&'a Rrset<Name, Ns<Name>>> {
+        self.ns
+    }
+
+    /// Returns the record sets replacing all A glue of the delegation.
+    ///
+    /// An empty slice means that all A glue should be removed.
+    pub fn a(&self) -> Option<&[&'a Rrset<Name, A>]> {
+        self.a.as_deref()
+    }
+
+    /// Returns the record sets replacing all AAAA glue of the delegation.
+    ///
+    /// An empty slice means that all AAAA glue should be removed.
+    pub fn aaaa(&self) -> Option<&[&'a Rrset<Name, Aaaa>]> {
+        self.aaaa.as_deref()
+    }
+
+    /// Returns whether the delegation is to be left unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.ns.is_none() && self.a.is_none() && self.aaaa.is_none()
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ CsyncError ----------------------------------------------------
+
+/// A CSYNC record could not be processed.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CsyncError {
+    /// The record has flags set that we don’t know about.
+    UnknownFlags,
+
+    /// The record asks for a type that we don’t know how to synchronize.
+    UnsupportedType(Rtype),
+
+    /// The immediate flag is not set and the update wasn’t approved.
+    NotApproved,
+
+    /// The child’s SOA serial is smaller than the serial of the record.
+    SoaMinimum,
+
+    /// The child’s SOA serial is older than the one last processed.
+    Stale,
+
+    /// The NS record set isn’t at the apex of the child zone.
+    OwnerMismatch,
+
+    /// The child’s NS record set is empty.
+    EmptyNs,
+
+    /// An in-bailiwick name server has no address records.
+    MissingGlue,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CsyncError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsyncError::UnknownFlags => f.write_str("unknown CSYNC flags"),
+            CsyncError::UnsupportedType(rtype) => {
+                write!(f, "unsupported CSYNC type {}", rtype)
+            }
+            CsyncError::NotApproved => f.write_str("update not approved"),
+            CsyncError::SoaMinimum => {
+                f.write_str("SOA serial below CSYNC serial")
+            }
+            CsyncError::Stale => f.write_str("SOA serial older than last"),
+            CsyncError::OwnerMismatch => {
+                f.write_str("NS record set not at child apex")
+            }
+            CsyncError::EmptyNs => f.write_str("empty NS record set"),
+            CsyncError::MissingGlue => {
+                f.write_str("missing glue for in-bailiwick name server")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CsyncError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::super::rfc4034::RtypeBitmapBuilder;
+    use super::*;
+    use crate::base::iana::Class;
+    use crate::base::name::Dname;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    type Name = Dname<Vec<u8>>;
+
+    fn name(s: &str) -> Name {
+        Name::from_str(s).unwrap()
+    }
+
+    fn csync(flags: u16, types: &[Rtype]) -> Csync<Vec<u8>> {
+        let mut builder = RtypeBitmapBuilder::new_vec();
+        for rtype in types {
+            builder.add(*rtype).unwrap();
+        }
+        Csync::new(Serial(66), flags, builder.finalize())
+    }
+
+    fn soa(serial: u32) -> Soa<Name> {
+        Soa::new(
+            name("ns1.example.com"),
+            name("hostmaster.example.com"),
+            Serial(serial),
+            3600,
+            600,
+            86400,
+            300,
+        )
+    }
+
+    fn ns(servers: &[&str]) -> Rrset<Name, Ns<Name>> {
+        let mut res =
+            Rrset::new(name("example.com"), Class::In, Rtype::Ns, 3600);
+        for server in servers {
+            res.push_data(Ns::new(name(server))).unwrap();
+        }
+        res
+    }
+
+    fn a(owner: &str) -> Rrset<Name, A> {
+        let mut res = Rrset::new(name(owner), Class::In, Rtype::A, 3600);
+        res.push_data(A::from_octets(192, 0, 2, 1)).unwrap();
+        res
+    }
+
+    #[test]
+    fn csync_compose_parse_scan() {
+        let rdata = csync(3, &[Rtype::A, Rtype::Ns, Rtype::Aaaa]);
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Csync::parse(parser));
+        test_scan(&["66", "3", "A", "NS", "AAAA"], Csync::scan, &rdata);
+        assert!(rdata.is_immediate());
+        assert!(rdata.is_soa_minimum());
+        assert_eq!(rdata.to_string(), "66 3 A NS AAAA");
+    }
+
+    #[test]
+    fn process() {
+        let nsset = ns(&["ns1.example.com", "ns.example.net"]);
+        let glue = [a("ns1.example.com"), a("ns.example.net")];
+        let none: &[Rrset<Name, Aaaa>] = &[];
+        let processor = CsyncProcessor::new(name("example.com"));
+
+        // NS and A glue, only for the in-bailiwick server.
+        let update = processor
+            .process(
+                &csync(3, &[Rtype::Ns, Rtype::A]),
+                &soa(66),
+                &nsset,
+                &glue,
+                none,
+            )
+            .unwrap();
+        assert_eq!(update.ns().unwrap().len(), 2);
+        let a = update.a().unwrap();
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[0].owner(), &name("ns1.example.com"));
+        assert!(update.aaaa().is_none());
+
+        // Only AAAA: the server has A glue, so removing all AAAA glue
+        // doesn’t make the delegation lame.
+        let update = processor
+            .process(&csync(1, &[Rtype::Aaaa]), &soa(66), &nsset, &glue, none)
+            .unwrap();
+        assert!(update.ns().is_none());
+        assert!(update.a().is_none());
+        assert!(update.aaaa().unwrap().is_empty());
+
+        // Only NS.
+        let update = processor
+            .process(&csync(1, &[Rtype::Ns]), &soa(66), &nsset, &glue, none)
+            .unwrap();
+        assert!(update.ns().is_some());
+        assert!(update.a().is_none());
+
+        // Errors.
+        let process = |processor: &CsyncProcessor<Name>,
+                       csync: Csync<Vec<u8>>,
+                       serial| {
+            processor
+                .process(&csync, &soa(serial), &nsset, &glue, none)
+                .unwrap_err()
+        };
+        assert_eq!(
+            process(&processor, csync(7, &[Rtype::Ns]), 66),
+            CsyncError::UnknownFlags
+        );
+        assert_eq!(
+            process(&processor, csync(1, &[Rtype::Ns, Rtype::Mx]), 66),
+            CsyncError::UnsupportedType(Rtype::Mx)
+        );
+        assert_eq!(
+            process(&processor, csync(2, &[Rtype::Ns]), 66),
+            CsyncError::NotApproved
+        );
+        assert_eq!(
+            process(&processor, csync(3, &[Rtype::Ns]), 65),
+            CsyncError::SoaMinimum
+        );
+        assert_eq!(
+            process(
+                &processor.clone().last_serial(Serial(67)),
+                csync(1, &[Rtype::Ns]),
+                66
+            ),
+            CsyncError::Stale
+        );
+        assert_eq!(
+            processor
+                .process(
+                    &csync(1, &[Rtype::Aaaa]),
+                    &soa(66),
+                    &nsset,
+                    &[a("ns.example.net")],
+                    none
+                )
+                .unwrap_err(),
+            CsyncError::MissingGlue
+        );
+        assert!(processor
+            .clone()
+            .approve(true)
+            .process(&csync(2, &[Rtype::Ns]), &soa(66), &nsset, &glue, none)
+            .is_ok());
+        assert_eq!(
+            processor
+                .process(
+                    &csync(1, &[Rtype::Ns]),
+                    &soa(66),
+                    &ns(&[]),
+                    &glue,
+                    none
+                )
+                .unwrap_err(),
+            CsyncError::EmptyNs
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Record data from [RFC 7553]: URI records.
+//!
+//! This RFC defines the URI record type used to publish mappings from
+//! hostnames to URIs.
+//!
+//! [RFC 7553]: https://tools.ietf.org/html/rfc7553
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Uri -----------------------------------------------------------
+
+/// URI record data.
+///
+/// URI records map the owner name, typically of the form
+/// `_service._proto.name`, to a URI. Similarly to SRV records, the
+/// priority and weight fields allow selecting one of several records.
+///
+/// In the presentation format, the target URI is given as a quoted string.
+///
+/// The URI record type is defined in [RFC 7553].
+///
+/// [RFC 7553]: https://tools.ietf.org/html/rfc7553
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: octseq::serde::DeserializeOctets<'de>
+        ",
+    ))
+)]
+pub struct Uri<Octs> {
+    priority: u16,
+    weight: u16,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+        )
+    )]
+    target: Octs,
+}
+
+impl<Octs> Uri<Octs> {
+    /// Creates new URI record data from its components.
+    ///
+    /// The function will fail if the target is longer than 65,531 octets.
+    pub fn new(
+        priority: u16,
+        weight: u16,
+        target: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            usize::from(u16::COMPOSE_LEN + u16::COMPOSE_LEN)
+                .checked_add(target.as_ref().len())
+                .expect("long target"),
+        )?;
+        Ok(unsafe { Uri::new_unchecked(priority, weight, target) })
+    }
+
+    /// Creates new URI record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        priority: u16,
+        weight: u16,
+        target: Octs,
+    ) -> Self {
+        Uri {
+            priority,
+            weight,
+            target,
+        }
+    }
+
+    /// The priority of the target.
+    ///
+    /// Clients should use the target with the lowest priority value
+    /// they can reach.
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// The weight of the target.
+    ///
+    /// Clients should select among targets with the same priority with
+    /// a probability proportional to their weight.
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// The target URI.
+    pub fn target(&self) -> &Octs {
+        &self.target
+    }
+
+    /// Converts the record data into the target URI.
+    pub fn into_target(self) -> Octs {
+        self.target
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Uri<Target>, Target::Error> {
+        Ok(unsafe {
+            Uri::new_unchecked(
+                self.priority,
+                self.weight,
+                self.target.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(4) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                u16::parse(parser)?,
+                u16::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            u16::scan(scanner)?,
+            u16::scan(scanner)?,
+            scanner.scan_octets()?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Uri<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Uri<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            priority,
+            weight,
+            target,
+        } = self;
+        Ok(unsafe {
+            Uri::new_unchecked(
+                priority,
+                weight,
+                target.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Uri<SrcOcts>> for Uri<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Uri<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Uri::new_unchecked(
+                source.priority,
+                source.weight,
+                Octs::try_octets_from(source.target)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Uri<Other>> for Uri<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Uri<Other>) -> bool {
+        self.priority == other.priority
+            && self.weight == other.weight
+            && self.target.as_ref() == other.target.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Uri<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Uri<Other>> for Uri<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Uri<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Uri<Other>> for Uri<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Uri<Other>) -> Ordering {
+        match self.priority.cmp(&other.priority) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.weight.cmp(&other.weight) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.target.as_ref().cmp(other.target.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Uri<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Uri<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.priority.hash(state);
+        self.weight.hash(state);
+        self.target.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Uri<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Uri
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Uri<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Uri {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Uri<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.target.as_ref().len())
+                .expect("long target")
+                .checked_add(u16::COMPOSE_LEN + u16::COMPOSE_LEN)
+                .expect("long target"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.priority.compose(target)?;
+        self.weight.compose(target)?;
+        target.append_slice(self.target.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Uri<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} \"", self.priority, self.weight)?;
+        for &ch in self.target.as_ref() {
+            match ch {
+                b'"' | b'\\' => write!(f, "\\{}", ch as char)?,
+                0x20..=0x7E => write!(f, "{}", ch as char)?,
+                _ => write!(f, "\\{:03}", ch)?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Uri<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Uri")
+            .field("priority", &self.priority)
+            .field("weight", &self.weight)
+            .field("target", &self.target.as_ref())
+            .finish()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn uri_compose_parse_scan() {
+        let rdata = Uri::new(
+            10,
+            1,
+            Vec::from(b"ftp://ftp1.example.com/public".as_ref()),
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Uri::parse(parser));
+        test_scan(
+            &["10", "1", "ftp://ftp1.example.com/public"],
+            Uri::scan,
+            &rdata,
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "10 1 \"ftp://ftp1.example.com/public\""
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Record data from [RFC 7929]: OPENPGPKEY records.
+//!
+//! [RFC 7929]: https://tools.ietf.org/html/rfc7929
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scanner, ScannerError};
+use crate::base::wire::{Composer, ParseError};
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Openpgpkey ----------------------------------------------------
+
+/// OPENPGPKEY record data.
+///
+/// OPENPGPKEY records publish the OpenPGP transferable public key for the
+/// email address encoded in the domain name of the record. The record data
+/// consists of the key only.
+///
+/// The OPENPGPKEY record type is defined in [RFC 7929].
+///
+/// [RFC 7929]: https://tools.ietf.org/html/rfc7929
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Openpgpkey<Octs> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    key: Octs,
+}
+
+impl<Octs> Openpgpkey<Octs> {
+    /// Creates new OPENPGPKEY record data from the given key.
+    ///
+    /// The function will fail if the key is longer than 65,535 octets.
+    pub fn new(key: Octs) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(key.as_ref().len())?;
+        Ok(unsafe { Self::new_unchecked(key) })
+    }
+
+    /// Creates new OPENPGPKEY record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the key is at most 65,535 octets
+    /// long.
+    pub unsafe fn new_unchecked(key: Octs) -> Self {
+        Openpgpkey { key }
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> &Octs {
+        &self.key
+    }
+
+    /// Converts the record data into the key.
+    pub fn into_key(self) -> Octs {
+        self.key
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Openpgpkey<Target>, Target::Error> {
+        Ok(unsafe { Openpgpkey::new_unchecked(self.key.try_octets_into()?) })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = parser.remaining();
+        Ok(unsafe { Self::new_unchecked(parser.parse_octets(len)?) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(scanner.convert_entry(base64::SymbolConverter::new())?)
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Openpgpkey<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Openpgpkey<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        Ok(unsafe {
+            Openpgpkey::new_unchecked(
+                self.key.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Openpgpkey<SrcOcts>> for Openpgpkey<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(
+        source: Openpgpkey<SrcOcts>,
+    ) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Openpgpkey::new_unchecked(Octs::try_octets_from(source.key)?)
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Openpgpkey<Other>> for Openpgpkey<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Openpgpkey<Other>) -> bool {
+        self.key.as_ref() == other.key.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Openpgpkey<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Openpgpkey<Other>> for Openpgpkey<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Openpgpkey<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Openpgpkey<Other>> for Openpgpkey<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Openpgpkey<Other>) -> Ordering {
+        self.key.as_ref().cmp(other.key.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Openpgpkey<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Openpgpkey<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.key.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Openpgpkey<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Openpgpkey
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Openpgpkey<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Openpgpkey {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Openpgpkey<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(u16::try_from(self.key.as_ref().len()).expect("long key"))
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.key.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Openpgpkey<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        base64::display(&self.key, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Openpgpkey<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Openpgpkey")
+            .field("key", &self.key.as_ref())
+            .finish()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+
+    #[test]
+    fn openpgpkey_compose_parse_scan() {
+        let rdata = Openpgpkey::new(b"key").unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Openpgpkey::parse(parser));
+        test_scan(&["a2V5"], Openpgpkey::scan, &rdata);
+    }
+}
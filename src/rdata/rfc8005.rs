@@ -0,0 +1,571 @@
+//! Record data from [RFC 8005]: HIP records.
+//!
+//! This RFC defines the HIP record type used to publish the Host Identity
+//! and Host Identity Tag of a host for the Host Identity Protocol as well
+//! as its rendezvous servers.
+//!
+//! [RFC 8005]: https://tools.ietf.org/html/rfc8005
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::{Dname, PushError, ToDname};
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, FormError, Parse, ParseError};
+use crate::utils::{base16, base64};
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::builder::FreezeBuilder;
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Hip -----------------------------------------------------------
+
+/// HIP record data.
+///
+/// HIP records contain the Host Identity Tag (HIT) and the public key of
+/// the Host Identity of a host together with the algorithm of that key.
+/// They can also list the domain names of rendezvous servers through which
+/// the host can be reached. The names are kept in their uncompressed wire
+/// format and can be accessed via [`servers`][Self::servers].
+///
+/// In the presentation format, the HIT is given in Base 16 and the public
+/// key in Base 64.
+///
+/// The HIP record type is defined in [RFC 8005].
+///
+/// [RFC 8005]: https://tools.ietf.org/html/rfc8005
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Hip<Octs> {
+    pk_algorithm: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    hit: Octs,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base64::serde")
+    )]
+    public_key: Octs,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    servers: Octs,
+}
+
+impl<Octs> Hip<Octs> {
+    /// Creates new HIP record data from its components.
+    ///
+    /// The `servers` must contain a sequence of uncompressed domain names
+    /// in wire format. The function will fail if they don’t, if the HIT
+    /// is longer than 255 octets, or if the wire format of the record data
+    /// would be longer than 65,535 octets.
+    pub fn new(
+        pk_algorithm: u8,
+        hit: Octs,
+        public_key: Octs,
+        servers: Octs,
+    ) -> Result<Self, HipError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        if hit.as_ref().len() > usize::from(u8::MAX) {
+            return Err(HipError(HipErrorInner::LongHit));
+        }
+        LongRecordData::check_len(
+            4usize
+                .checked_add(hit.as_ref().len())
+                .and_then(|len| len.checked_add(public_key.as_ref().len()))
+                .and_then(|len| len.checked_add(servers.as_ref().len()))
+                .expect("long HIP rdata"),
+        )?;
+        check_servers(servers.as_ref())?;
+        Ok(unsafe {
+            Hip::new_unchecked(pk_algorithm, hit, public_key, servers)
+        })
+    }
+
+    /// Creates new HIP record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the HIT is at most 255 octets long,
+    /// that `servers` contains a sequence of uncompressed domain names,
+    /// and that the wire format representation of the record data is at
+    /// most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        pk_algorithm: u8,
+        hit: Octs,
+        public_key: Octs,
+        servers: Octs,
+    ) -> Self {
+        Hip {
+            pk_algorithm,
+            hit,
+            public_key,
+            servers,
+        }
+    }
+
+    /// Returns the algorithm of the public key.
+    ///
+    /// The values are those of the IPSECKEY algorithm registry.
+    pub fn pk_algorithm(&self) -> u8 {
+        self.pk_algorithm
+    }
+
+    /// Returns the Host Identity Tag.
+    pub fn hit(&self) -> &Octs {
+        &self.hit
+    }
+
+    /// Returns the public key of the Host Identity.
+    pub fn public_key(&self) -> &Octs {
+        &self.public_key
+    }
+
+    /// Returns the wire format of the rendezvous servers.
+    pub fn servers_octets(&self) -> &Octs {
+        &self.servers
+    }
+
+    /// Returns an iterator over the domain names of the rendezvous servers.
+    pub fn servers(&self) -> HipServers
+    where
+        Octs: AsRef<[u8]>,
+    {
+        HipServers(Parser::from_ref(self.servers.as_ref()))
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Hip<Target>, Target::Error> {
+        Ok(unsafe {
+            Hip::new_unchecked(
+                self.pk_algorithm,
+                self.hit.try_octets_into()?,
+                self.public_key.try_octets_into()?,
+                self.servers.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let hit_len = u8::parse(parser)?;
+        let pk_algorithm = u8::parse(parser)?;
+        let pk_len = u16::parse(parser)?;
+        let hit = parser.parse_octets(usize::from(hit_len))?;
+        let public_key = parser.parse_octets(usize::from(pk_len))?;
+        let len = parser.remaining();
+        let servers = parser.parse_octets(len)?;
+        check_servers(servers.as_ref()).map_err(FormError::from)?;
+        Ok(unsafe {
+            Self::new_unchecked(pk_algorithm, hit, public_key, servers)
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let pk_algorithm = u8::scan(scanner)?;
+        let hit = scanner.convert_token(base16::SymbolConverter::new())?;
+        let public_key =
+            scanner.convert_token(base64::SymbolConverter::new())?;
+        let mut servers = scanner.octets_builder()?;
+        while scanner.continues() {
+            scanner
+                .scan_dname()?
+                .compose(&mut servers)
+                .map_err(|_| S::Error::short_buf())?;
+        }
+        Self::new(pk_algorithm, hit, public_key, servers.freeze())
+            .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Hip<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Hip<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            pk_algorithm,
+            hit,
+            public_key,
+            servers,
+        } = self;
+        Ok(unsafe {
+            Hip::new_unchecked(
+                pk_algorithm,
+                hit.try_octets_into().map_err(Into::into)?,
+                public_key.try_octets_into().map_err(Into::into)?,
+                servers.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Hip<SrcOcts>> for Hip<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Hip<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Hip::new_unchecked(
+                source.pk_algorithm,
+                Octs::try_octets_from(source.hit)?,
+                Octs::try_octets_from(source.public_key)?,
+                Octs::try_octets_from(source.servers)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Hip<Other>> for Hip<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Hip<Other>) -> bool {
+        self.pk_algorithm == other.pk_algorithm
+            && self.hit.as_ref() == other.hit.as_ref()
+            && self.public_key.as_ref() == other.public_key.as_ref()
+            && self.servers.as_ref() == other.servers.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Hip<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Hip<Other>> for Hip<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Hip<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Hip<Other>> for Hip<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Hip<Other>) -> Ordering {
+        // The canonical order is that of the wire format which starts with
+        // the lengths of the HIT and public key.
+        match self.hit.as_ref().len().cmp(&other.hit.as_ref().len()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.pk_algorithm.cmp(&other.pk_algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self
+            .public_key
+            .as_ref()
+            .len()
+            .cmp(&other.public_key.as_ref().len())
+        {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.hit.as_ref().cmp(other.hit.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.public_key.as_ref().cmp(other.public_key.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.servers.as_ref().cmp(other.servers.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Hip<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Hip<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.pk_algorithm.hash(state);
+        self.hit.as_ref().hash(state);
+        self.public_key.as_ref().hash(state);
+        self.servers.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Hip<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Hip
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Hip<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Hip {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Hip<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(
+                self.hit.as_ref().len()
+                    + self.public_key.as_ref().len()
+                    + self.servers.as_ref().len(),
+            )
+            .expect("long HIP rdata")
+            .checked_add(u8::COMPOSE_LEN + u8::COMPOSE_LEN + u16::COMPOSE_LEN)
+            .expect("long HIP rdata"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // The lengths have been checked upon creation.
+        (self.hit.as_ref().len() as u8).compose(target)?;
+        self.pk_algorithm.compose(target)?;
+        (self.public_key.as_ref().len() as u16).compose(target)?;
+        target.append_slice(self.hit.as_ref())?;
+        target.append_slice(self.public_key.as_ref())?;
+        target.append_slice(self.servers.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        // The rendezvous servers are not lowercased in the canonical form.
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Hip<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.pk_algorithm)?;
+        base16::display(&self.hit, f)?;
+        f.write_str(" ")?;
+        base64::display(&self.public_key, f)?;
+        for server in self.servers() {
+            write!(f, " {}.", server)?;
+        }
+        Ok(())
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Hip<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Hip")
+            .field("pk_algorithm", &self.pk_algorithm)
+            .field("hit", &self.hit.as_ref())
+            .field("public_key", &self.public_key.as_ref())
+            .field("servers", &self.servers.as_ref())
+            .finish()
+    }
+}
+
+//------------ HipServers ----------------------------------------------------
+
+/// An iterator over the rendezvous servers of HIP record data.
+#[derive(Clone)]
+pub struct HipServers<'a>(Parser<'a, [u8]>);
+
+impl<'a> Iterator for HipServers<'a> {
+    type Item = Dname<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.remaining() == 0 {
+            return None;
+        }
+        // The names have been checked upon creation, so this can’t fail.
+        Dname::parse(&mut self.0).ok()
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Checks that a slice contains a sequence of uncompressed domain names.
+fn check_servers(slice: &[u8]) -> Result<(), HipError> {
+    let mut parser = Parser::from_ref(slice);
+    while parser.remaining() > 0 {
+        Dname::<&[u8]>::parse(&mut parser)
+            .map_err(|_| HipError(HipErrorInner::InvalidServers))?;
+    }
+    Ok(())
+}
+
+//============ Error Types ===================================================
+
+//------------ HipError ------------------------------------------------------
+
+/// HIP record data could not be created.
+#[derive(Clone, Copy, Debug)]
+pub struct HipError(HipErrorInner);
+
+#[derive(Clone, Copy, Debug)]
+enum HipErrorInner {
+    Long(LongRecordData),
+    LongHit,
+    InvalidServers,
+}
+
+impl HipError {
+    pub fn as_str(self) -> &'static str {
+        match self.0 {
+            HipErrorInner::Long(err) => err.as_str(),
+            HipErrorInner::LongHit => "HIT too long",
+            HipErrorInner::InvalidServers => "invalid rendezvous server",
+        }
+    }
+}
+
+impl From<LongRecordData> for HipError {
+    fn from(err: LongRecordData) -> HipError {
+        HipError(HipErrorInner::Long(err))
+    }
+}
+
+impl From<HipError> for FormError {
+    fn from(err: HipError) -> FormError {
+        FormError::new(err.as_str())
+    }
+}
+
+impl fmt::Display for HipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HipError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use core::str::FromStr;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn hip_compose_parse_scan() {
+        let mut servers = Vec::new();
+        Dname::<Vec<u8>>::from_str("rvs1.example.com")
+            .unwrap()
+            .compose(&mut servers)
+            .unwrap();
+        Dname::<Vec<u8>>::from_str("rvs2.example.com")
+            .unwrap()
+            .compose(&mut servers)
+            .unwrap();
+        let rdata = Hip::new(
+            2,
+            base16::decode::<Vec<u8>>("200100107B1A74DF365639CC39F1D578")
+                .unwrap(),
+            base64::decode::<Vec<u8>>("AwEAAbdxyhNuSutc5EMzxTs9LBPC")
+                .unwrap(),
+            servers,
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Hip::parse(parser));
+        test_scan(
+            &[
+                "2",
+                "200100107B1A74DF365639CC39F1D578",
+                "AwEAAbdxyhNuSutc5EMzxTs9LBPC",
+                "rvs1.example.com.",
+                "rvs2.example.com.",
+            ],
+            Hip::scan,
+            &rdata,
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "2 200100107B1A74DF365639CC39F1D578 \
+             AwEAAbdxyhNuSutc5EMzxTs9LBPC \
+             rvs1.example.com. rvs2.example.com."
+        );
+        assert_eq!(
+            rdata
+                .servers()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+            ["rvs1.example.com", "rvs2.example.com"]
+        );
+    }
+
+    #[test]
+    fn hip_invalid() {
+        assert!(Hip::new(2, vec![0; 256], vec![], vec![]).is_err());
+        assert!(Hip::new(2, vec![], vec![], b"\x04rvs1".to_vec()).is_err());
+        assert!(Hip::new(2, vec![], vec![], b"\xc0\x0c".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,431 @@
+//! Record data from [RFC 8162]: SMIMEA records.
+//!
+//! [RFC 8162]: https://tools.ietf.org/html/rfc8162
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{
+    Rtype, TlsaCertUsage, TlsaMatchingType, TlsaSelector,
+};
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+#[cfg(feature = "ring")]
+use super::rfc6698::{match_certificate, match_spki, DaneError};
+
+//------------ Smimea --------------------------------------------------------
+
+/// SMIMEA record data.
+///
+/// SMIMEA records associate a certificate or public key with the email
+/// address encoded in the domain name of the record for use with S/MIME.
+/// The record data has the same format and semantics as that of the
+/// [TLSA record][super::rfc6698::Tlsa].
+///
+/// The SMIMEA record type is defined in [RFC 8162].
+///
+/// [RFC 8162]: https://tools.ietf.org/html/rfc8162
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Smimea<Octs> {
+    cert_usage: TlsaCertUsage,
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    data: Octs,
+}
+
+impl<Octs> Smimea<Octs> {
+    /// Creates new SMIMEA record data from its components.
+    ///
+    /// The function will fail if the association data is longer than
+    /// 65,532 octets.
+    pub fn new(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            3usize.checked_add(data.as_ref().len()).expect("long data"),
+        )?;
+        Ok(unsafe {
+            Smimea::new_unchecked(cert_usage, selector, matching_type, data)
+        })
+    }
+
+    /// Creates new SMIMEA record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        cert_usage: TlsaCertUsage,
+        selector: TlsaSelector,
+        matching_type: TlsaMatchingType,
+        data: Octs,
+    ) -> Self {
+        Smimea {
+            cert_usage,
+            selector,
+            matching_type,
+            data,
+        }
+    }
+
+    /// Returns the certificate usage.
+    pub fn cert_usage(&self) -> TlsaCertUsage {
+        self.cert_usage
+    }
+
+    /// Returns the selector.
+    pub fn selector(&self) -> TlsaSelector {
+        self.selector
+    }
+
+    /// Returns the matching type.
+    pub fn matching_type(&self) -> TlsaMatchingType {
+        self.matching_type
+    }
+
+    /// Returns the certificate association data.
+    pub fn data(&self) -> &Octs {
+        &self.data
+    }
+
+    /// Converts the record data into the certificate association data.
+    pub fn into_data(self) -> Octs {
+        self.data
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Smimea<Target>, Target::Error> {
+        Ok(unsafe {
+            Smimea::new_unchecked(
+                self.cert_usage,
+                self.selector,
+                self.matching_type,
+                self.data.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(3) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                TlsaCertUsage::parse(parser)?,
+                TlsaSelector::parse(parser)?,
+                TlsaMatchingType::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            TlsaCertUsage::scan(scanner)?,
+            TlsaSelector::scan(scanner)?,
+            TlsaMatchingType::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+#[cfg(feature = "ring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+impl<Octs: AsRef<[u8]>> Smimea<Octs> {
+    /// Returns whether a certificate matches the record.
+    ///
+    /// The certificate must be given in its DER encoding. Depending on the
+    /// selector of the record, either the full certificate or its
+    /// SubjectPublicKeyInfo is matched against the association data.
+    ///
+    /// The method only considers the selector, matching type, and
+    /// association data. Checking the certificate usage is left to the
+    /// caller.
+    pub fn matches_certificate(
+        &self,
+        cert: &[u8],
+    ) -> Result<bool, DaneError> {
+        match_certificate(
+            self.selector,
+            self.matching_type,
+            self.data.as_ref(),
+            cert,
+        )
+    }
+
+    /// Returns whether a public key matches the record.
+    ///
+    /// The key must be given as a DER-encoded SubjectPublicKeyInfo. If the
+    /// selector of the record requires the full certificate, the key never
+    /// matches.
+    pub fn matches_spki(&self, spki: &[u8]) -> Result<bool, DaneError> {
+        match_spki(
+            self.selector,
+            self.matching_type,
+            self.data.as_ref(),
+            spki,
+        )
+    }
+}
+
+impl<SrcOcts> Smimea<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Smimea<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            cert_usage,
+            selector,
+            matching_type,
+            data,
+        } = self;
+        Ok(unsafe {
+            Smimea::new_unchecked(
+                cert_usage,
+                selector,
+                matching_type,
+                data.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Smimea<SrcOcts>> for Smimea<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Smimea<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Smimea::new_unchecked(
+                source.cert_usage,
+                source.selector,
+                source.matching_type,
+                Octs::try_octets_from(source.data)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Smimea<Other>> for Smimea<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Smimea<Other>) -> bool {
+        self.cert_usage == other.cert_usage
+            && self.selector == other.selector
+            && self.matching_type == other.matching_type
+            && self.data.as_ref() == other.data.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Smimea<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Smimea<Other>> for Smimea<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Smimea<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Smimea<Other>> for Smimea<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Smimea<Other>) -> Ordering {
+        match self.cert_usage.cmp(&other.cert_usage) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.selector.cmp(&other.selector) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.matching_type.cmp(&other.matching_type) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.data.as_ref().cmp(other.data.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Smimea<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Smimea<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.cert_usage.hash(state);
+        self.selector.hash(state);
+        self.matching_type.hash(state);
+        self.data.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Smimea<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Smimea
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Smimea<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Smimea {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Smimea<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.data.as_ref().len())
+                .expect("long data")
+                .checked_add(
+                    TlsaCertUsage::COMPOSE_LEN
+                        + TlsaSelector::COMPOSE_LEN
+                        + TlsaMatchingType::COMPOSE_LEN,
+                )
+                .expect("long data"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.cert_usage.compose(target)?;
+        self.selector.compose(target)?;
+        self.matching_type.compose(target)?;
+        target.append_slice(self.data.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Smimea<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.cert_usage, self.selector, self.matching_type
+        )?;
+        base16::display(&self.data, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Smimea<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Smimea")
+            .field("cert_usage", &self.cert_usage)
+            .field("selector", &self.selector)
+            .field("matching_type", &self.matching_type)
+            .field("data", &self.data.as_ref())
+            .finish()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+
+    #[test]
+    fn smimea_compose_parse_scan() {
+        let rdata = Smimea::new(
+            TlsaCertUsage::DaneEe,
+            TlsaSelector::Cert,
+            TlsaMatchingType::Full,
+            b"key",
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Smimea::parse(parser));
+        test_scan(&["3", "0", "0", "6b6579"], Smimea::scan, &rdata);
+    }
+}
@@ -0,0 +1,788 @@
+//! Record data from [RFC 8659]: CAA records.
+//!
+//! [RFC 8659]: https://tools.ietf.org/html/rfc8659
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::Rtype;
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use core::cmp::Ordering;
+use core::{fmt, hash, str};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Caa -----------------------------------------------------------
+
+/// CAA record data.
+///
+/// CAA records allow the holder of a domain name to specify which
+/// certification authorities are authorized to issue certificates for the
+/// name. Each record contains a single property consisting of a tag and a
+/// value plus a flags field. The meaning of the value depends on the tag.
+/// The value of the three tags defined by the RFC can be parsed into a
+/// typed representation via the [`property`][Self::property] method.
+///
+/// The CAA record type is defined in [RFC 8659].
+///
+/// [RFC 8659]: https://tools.ietf.org/html/rfc8659
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs: octseq::serde::DeserializeOctets<'de>
+        ",
+    ))
+)]
+pub struct Caa<Octs> {
+    flags: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+        )
+    )]
+    tag: Octs,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "octseq::serde::SerializeOctets::serialize_octets",
+            deserialize_with = "octseq::serde::DeserializeOctets::deserialize_octets",
+        )
+    )]
+    value: Octs,
+}
+
+impl<Octs> Caa<Octs> {
+    /// Creates new CAA record data from its components.
+    ///
+    /// The function will fail if the tag is empty, longer than 255 octets,
+    /// or contains anything but ASCII letters and digits, or if the wire
+    /// format of the record data would be longer than 65,535 octets.
+    pub fn new(flags: u8, tag: Octs, value: Octs) -> Result<Self, CaaError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        check_tag(tag.as_ref())?;
+        LongRecordData::check_len(
+            (2 + tag.as_ref().len())
+                .checked_add(value.as_ref().len())
+                .expect("long value"),
+        )?;
+        Ok(unsafe { Self::new_unchecked(flags, tag, value) })
+    }
+
+    /// Creates new CAA record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that the tag is between 1 and 255 ASCII
+    /// letters and digits and that the wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(flags: u8, tag: Octs, value: Octs) -> Self {
+        Caa { flags, tag, value }
+    }
+
+    /// Returns the flags field.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns whether the issuer critical flag is set.
+    ///
+    /// If it is, a certification authority must not issue a certificate
+    /// if it does not understand the property.
+    pub fn is_critical(&self) -> bool {
+        self.flags & 0x80 != 0
+    }
+
+    /// Returns the property tag.
+    pub fn tag(&self) -> &Octs {
+        &self.tag
+    }
+
+    /// Returns the property value.
+    pub fn value(&self) -> &Octs {
+        &self.value
+    }
+
+    /// Returns whether the tag is the given tag.
+    ///
+    /// Tags are compared ignoring ASCII case.
+    pub fn has_tag(&self, tag: &str) -> bool
+    where
+        Octs: AsRef<[u8]>,
+    {
+        self.tag.as_ref().eq_ignore_ascii_case(tag.as_bytes())
+    }
+
+    /// Parses the property value according to its tag.
+    ///
+    /// Values of the `issue`, `issuewild`, and `iodef` tags are parsed
+    /// into their typed representation. Values of all other tags are
+    /// returned as is.
+    pub fn property(&self) -> Result<CaaProperty, CaaValueError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let value = self.value.as_ref();
+        if self.has_tag("issue") {
+            CaaIssueValue::parse(value).map(CaaProperty::Issue)
+        } else if self.has_tag("issuewild") {
+            CaaIssueValue::parse(value).map(CaaProperty::IssueWild)
+        } else if self.has_tag("iodef") {
+            CaaIodefValue::parse(value).map(CaaProperty::Iodef)
+        } else {
+            Ok(CaaProperty::Other(value))
+        }
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Caa<Target>, Target::Error> {
+        Ok(unsafe {
+            Caa::new_unchecked(
+                self.flags,
+                self.tag.try_octets_into()?,
+                self.value.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        let flags = u8::parse(parser)?;
+        let tag_len = u8::parse(parser)?;
+        let tag = parser.parse_octets(usize::from(tag_len))?;
+        if check_tag(tag.as_ref()).is_err() {
+            return Err(ParseError::form_error("invalid CAA tag"));
+        }
+        let value = parser.parse_octets(parser.remaining())?;
+        Ok(unsafe { Self::new_unchecked(flags, tag, value) })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            u8::scan(scanner)?,
+            scanner.scan_octets()?,
+            scanner.scan_octets()?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Caa<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Caa<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self { flags, tag, value } = self;
+        Ok(unsafe {
+            Caa::new_unchecked(
+                flags,
+                tag.try_octets_into().map_err(Into::into)?,
+                value.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Caa<SrcOcts>> for Caa<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Caa<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Caa::new_unchecked(
+                source.flags,
+                Octs::try_octets_from(source.tag)?,
+                Octs::try_octets_from(source.value)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Caa<Other>> for Caa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Caa<Other>) -> bool {
+        self.flags == other.flags
+            && self.tag.as_ref() == other.tag.as_ref()
+            && self.value.as_ref() == other.value.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Caa<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Caa<Other>> for Caa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Caa<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Caa<Other>> for Caa<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Caa<Other>) -> Ordering {
+        match self.flags.cmp(&other.flags) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.tag.as_ref().len().cmp(&other.tag.as_ref().len()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.tag.as_ref().cmp(other.tag.as_ref()) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.value.as_ref().cmp(other.value.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Caa<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Caa<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.flags.hash(state);
+        self.tag.as_ref().hash(state);
+        self.value.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Caa<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Caa
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Caa<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Caa {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Caa<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(
+                self.tag.as_ref().len() + self.value.as_ref().len(),
+            )
+            .expect("long value")
+            .checked_add(2)
+            .expect("long value"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.flags.compose(target)?;
+        u8::try_from(self.tag.as_ref().len())
+            .expect("long tag")
+            .compose(target)?;
+        target.append_slice(self.tag.as_ref())?;
+        target.append_slice(self.value.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Caa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.flags)?;
+        for &ch in self.tag.as_ref() {
+            write!(f, "{}", ch as char)?;
+        }
+        f.write_str(" \"")?;
+        for &ch in self.value.as_ref() {
+            match ch {
+                b'"' | b'\\' => write!(f, "\\{}", ch as char)?,
+                0x20..=0x7E => write!(f, "{}", ch as char)?,
+                _ => write!(f, "\\{:03}", ch)?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Caa<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Caa")
+            .field("flags", &self.flags)
+            .field("tag", &self.tag.as_ref())
+            .field("value", &self.value.as_ref())
+            .finish()
+    }
+}
+
+/// Checks that a tag is between 1 and 255 ASCII letters and digits.
+fn check_tag(tag: &[u8]) -> Result<(), CaaError> {
+    if tag.is_empty()
+        || tag.len() > 255
+        || !tag.iter().all(u8::is_ascii_alphanumeric)
+    {
+        Err(CaaError(CaaErrorInner::Tag))
+    } else {
+        Ok(())
+    }
+}
+
+//------------ CaaProperty ---------------------------------------------------
+
+/// The parsed property of a CAA record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaaProperty<'a> {
+    /// An `issue` property authorizing an issuer.
+    Issue(CaaIssueValue<'a>),
+
+    /// An `issuewild` property authorizing an issuer for wildcards.
+    IssueWild(CaaIssueValue<'a>),
+
+    /// An `iodef` property with a URL for reporting violations.
+    Iodef(CaaIodefValue<'a>),
+
+    /// The raw value of a property with some other tag.
+    Other(&'a [u8]),
+}
+
+//------------ CaaIssueValue -------------------------------------------------
+
+/// The value of an `issue` or `issuewild` property.
+///
+/// The value consists of an optional domain name identifying the issuer
+/// followed by an optional list of parameters. Each parameter is a
+/// `tag=value` pair. Parameters are separated from the issuer name and
+/// each other by semicolons. A missing issuer name means that no issuer
+/// is authorized.
+///
+/// The value is checked against the grammar in section 4.2 of RFC 8659
+/// upon parsing, so all accessors are infallible.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CaaIssueValue<'a> {
+    /// The issuer domain name, if present.
+    issuer: Option<&'a str>,
+
+    /// The parameters part of the value, trimmed of white space.
+    parameters: &'a str,
+}
+
+impl<'a> CaaIssueValue<'a> {
+    /// Parses the value of an `issue` or `issuewild` property.
+    pub fn parse(value: &'a [u8]) -> Result<Self, CaaValueError> {
+        let value = check_ascii(value)?;
+        let (issuer, parameters) = match value.split_once(';') {
+            Some((issuer, parameters)) => (issuer, Some(parameters)),
+            None => (value, None),
+        };
+        let issuer = trim_wsp(issuer);
+        let issuer = if issuer.is_empty() {
+            None
+        } else {
+            if !issuer.split('.').all(is_ldh_label) {
+                return Err(CaaValueError::InvalidIssuer);
+            }
+            Some(issuer)
+        };
+        let parameters = trim_wsp(parameters.unwrap_or(""));
+        if !parameters.is_empty() {
+            for parameter in parameters.split(';') {
+                if parse_parameter(parameter).is_none() {
+                    return Err(CaaValueError::InvalidParameter);
+                }
+            }
+        }
+        Ok(CaaIssueValue { issuer, parameters })
+    }
+
+    /// Returns the issuer domain name.
+    ///
+    /// Returns `None` if no issuer is authorized.
+    pub fn issuer(&self) -> Option<&'a str> {
+        self.issuer
+    }
+
+    /// Returns an iterator over the parameters as tag and value pairs.
+    pub fn parameters(&self) -> CaaParameters<'a> {
+        CaaParameters {
+            parameters: if self.parameters.is_empty() {
+                None
+            } else {
+                Some(self.parameters.split(';'))
+            },
+        }
+    }
+
+    /// Returns the value of the parameter with the given tag.
+    ///
+    /// Tags are compared ignoring ASCII case.
+    pub fn parameter(&self, tag: &str) -> Option<&'a str> {
+        self.parameters()
+            .find(|(item, _)| item.eq_ignore_ascii_case(tag))
+            .map(|(_, value)| value)
+    }
+}
+
+/// Parses a single parameter into its tag and value.
+///
+/// Returns `None` if the parameter is malformed.
+fn parse_parameter(parameter: &str) -> Option<(&str, &str)> {
+    let (tag, value) = parameter.split_once('=')?;
+    let tag = trim_wsp(tag);
+    let value = trim_wsp(value);
+    if !is_ldh_label(tag) || value.bytes().any(|ch| !ch.is_ascii_graphic()) {
+        None
+    } else {
+        Some((tag, value))
+    }
+}
+
+//------------ CaaParameters -------------------------------------------------
+
+/// An iterator over the parameters of an issue property value.
+#[derive(Clone, Debug)]
+pub struct CaaParameters<'a> {
+    parameters: Option<str::Split<'a, char>>,
+}
+
+impl<'a> Iterator for CaaParameters<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_parameter(self.parameters.as_mut()?.next()?)
+    }
+}
+
+//------------ CaaIodefValue -------------------------------------------------
+
+/// The value of an `iodef` property.
+///
+/// The value is a URL where violations of the CAA policy can be reported.
+/// RFC 8659 only allows the `mailto`, `http`, and `https` schemes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaaIodefValue<'a> {
+    /// Reports are to be sent by email to the contained address.
+    Mailto(&'a str),
+
+    /// Reports are to be sent via HTTP to the contained URL.
+    Http(&'a str),
+}
+
+impl<'a> CaaIodefValue<'a> {
+    /// Parses the value of an `iodef` property.
+    pub fn parse(value: &'a [u8]) -> Result<Self, CaaValueError> {
+        if let Some(&ch) = value.iter().find(|ch| !ch.is_ascii_graphic()) {
+            return Err(CaaValueError::IllegalCharacter(ch));
+        }
+        let value = check_ascii(value)?;
+        let (scheme, rest) = match value.split_once(':') {
+            Some(some) => some,
+            None => return Err(CaaValueError::UnsupportedScheme),
+        };
+        if scheme.eq_ignore_ascii_case("mailto") {
+            Ok(CaaIodefValue::Mailto(rest))
+        } else if scheme.eq_ignore_ascii_case("http")
+            || scheme.eq_ignore_ascii_case("https")
+        {
+            Ok(CaaIodefValue::Http(value))
+        } else {
+            Err(CaaValueError::UnsupportedScheme)
+        }
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Checks that a value only contains printable ASCII and white space.
+fn check_ascii(value: &[u8]) -> Result<&str, CaaValueError> {
+    if let Some(&ch) = value
+        .iter()
+        .find(|&&ch| !ch.is_ascii_graphic() && ch != b' ' && ch != b'\t')
+    {
+        return Err(CaaValueError::IllegalCharacter(ch));
+    }
+    // All octets are ASCII, so this can’t fail.
+    Ok(str::from_utf8(value).expect("ASCII value"))
+}
+
+/// Removes leading and trailing spaces and tabs.
+fn trim_wsp(s: &str) -> &str {
+    s.trim_matches(|ch| ch == ' ' || ch == '\t')
+}
+
+/// Checks for letters, digits, and hyphens neither leading nor trailing.
+fn is_ldh_label(label: &str) -> bool {
+    !label.is_empty()
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label
+            .bytes()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == b'-')
+}
+
+//============ Error Types ===================================================
+
+//------------ CaaError ------------------------------------------------------
+
+/// The components do not form valid CAA record data.
+#[derive(Clone, Copy, Debug)]
+pub struct CaaError(CaaErrorInner);
+
+#[derive(Clone, Copy, Debug)]
+enum CaaErrorInner {
+    Long(LongRecordData),
+    Tag,
+}
+
+impl CaaError {
+    pub fn as_str(self) -> &'static str {
+        match self.0 {
+            CaaErrorInner::Long(err) => err.as_str(),
+            CaaErrorInner::Tag => "invalid CAA tag",
+        }
+    }
+}
+
+impl From<LongRecordData> for CaaError {
+    fn from(err: LongRecordData) -> CaaError {
+        CaaError(CaaErrorInner::Long(err))
+    }
+}
+
+impl fmt::Display for CaaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CaaError {}
+
+//------------ CaaValueError -------------------------------------------------
+
+/// A CAA property value does not follow the grammar for its tag.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaaValueError {
+    /// The value contains an octet not allowed here.
+    IllegalCharacter(u8),
+
+    /// The issuer domain name is malformed.
+    InvalidIssuer,
+
+    /// A parameter is malformed.
+    InvalidParameter,
+
+    /// The URL of an iodef property has an unsupported scheme.
+    UnsupportedScheme,
+}
+
+impl fmt::Display for CaaValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CaaValueError::IllegalCharacter(ch) => {
+                write!(f, "illegal character '\\{:03}'", ch)
+            }
+            CaaValueError::InvalidIssuer => {
+                f.write_str("invalid issuer domain name")
+            }
+            CaaValueError::InvalidParameter => {
+                f.write_str("invalid parameter")
+            }
+            CaaValueError::UnsupportedScheme => {
+                f.write_str("unsupported URL scheme")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CaaValueError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn caa_compose_parse_scan() {
+        let rdata =
+            Caa::new(128, b"issue".as_ref(), b"ca.example.net".as_ref())
+                .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Caa::parse(parser));
+        test_scan(&["128", "issue", "ca.example.net"], Caa::scan, &rdata);
+    }
+
+    #[test]
+    fn caa_new() {
+        assert!(Caa::new(0, b"".as_ref(), b"".as_ref()).is_err());
+        assert!(Caa::new(0, b"iss-ue".as_ref(), b"".as_ref()).is_err());
+        assert!(Caa::new(0, b"tbs".as_ref(), b"".as_ref()).is_ok());
+        assert!(
+            Caa::new(0, b"issue".as_ref(), [0u8; 65534].as_ref()).is_err()
+        );
+    }
+
+    #[test]
+    fn caa_parse_bad_tag() {
+        let data = b"\x00\x00";
+        assert!(Caa::parse(&mut Parser::from_ref(data.as_slice())).is_err());
+        let data = b"\x00\x02a_b";
+        assert!(Caa::parse(&mut Parser::from_ref(data.as_slice())).is_err());
+    }
+
+    #[test]
+    fn caa_display() {
+        let rdata = Caa::new(
+            128,
+            b"tbs".as_ref(),
+            b"Unknown \"value\"\\\x01".as_ref(),
+        )
+        .unwrap();
+        assert!(rdata.is_critical());
+        assert_eq!(
+            rdata.to_string(),
+            "128 tbs \"Unknown \\\"value\\\"\\\\\\001\""
+        );
+    }
+
+    #[test]
+    fn issue_value() {
+        let value = CaaIssueValue::parse(b"ca.example.net").unwrap();
+        assert_eq!(value.issuer(), Some("ca.example.net"));
+        assert_eq!(value.parameters().count(), 0);
+
+        let value = CaaIssueValue::parse(b";").unwrap();
+        assert_eq!(value.issuer(), None);
+
+        let value = CaaIssueValue::parse(
+            b" ca.example.net ; account=230123 ;validationmethods=dns-01",
+        )
+        .unwrap();
+        assert_eq!(value.issuer(), Some("ca.example.net"));
+        assert_eq!(
+            value.parameters().collect::<Vec<_>>(),
+            [("account", "230123"), ("validationmethods", "dns-01")]
+        );
+        assert_eq!(value.parameter("Account"), Some("230123"));
+        assert_eq!(value.parameter("policy"), None);
+
+        assert_eq!(
+            CaaIssueValue::parse(b"ca..example.net"),
+            Err(CaaValueError::InvalidIssuer)
+        );
+        assert_eq!(
+            CaaIssueValue::parse(b"ca.example.net; account"),
+            Err(CaaValueError::InvalidParameter)
+        );
+        assert_eq!(
+            CaaIssueValue::parse(b"ca.example.net; a=b;"),
+            Err(CaaValueError::InvalidParameter)
+        );
+        assert_eq!(
+            CaaIssueValue::parse(b"ca.example.net; a=b c"),
+            Err(CaaValueError::InvalidParameter)
+        );
+        assert_eq!(
+            CaaIssueValue::parse(b"ca.example.net\x00"),
+            Err(CaaValueError::IllegalCharacter(0))
+        );
+    }
+
+    #[test]
+    fn iodef_value() {
+        assert_eq!(
+            CaaIodefValue::parse(b"mailto:security@example.com"),
+            Ok(CaaIodefValue::Mailto("security@example.com"))
+        );
+        assert_eq!(
+            CaaIodefValue::parse(b"https://iodef.example.com/"),
+            Ok(CaaIodefValue::Http("https://iodef.example.com/"))
+        );
+        assert_eq!(
+            CaaIodefValue::parse(b"ftp://iodef.example.com/"),
+            Err(CaaValueError::UnsupportedScheme)
+        );
+    }
+
+    #[test]
+    fn property() {
+        let rdata =
+            Caa::new(0, b"IssueWild".as_ref(), b";".as_ref()).unwrap();
+        assert_eq!(
+            rdata.property(),
+            Ok(CaaProperty::IssueWild(CaaIssueValue::parse(b";").unwrap()))
+        );
+        let rdata =
+            Caa::new(0, b"tbs".as_ref(), b"Unknown".as_ref()).unwrap();
+        assert_eq!(rdata.property(), Ok(CaaProperty::Other(b"Unknown")));
+    }
+}
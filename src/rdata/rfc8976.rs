@@ -0,0 +1,398 @@
+//! Record data from [RFC 8976]: ZONEMD records.
+//!
+//! This RFC defines the ZONEMD record type which provides a cryptographic
+//! message digest over the content of a zone.
+//!
+//! Computing and verifying zone digests is provided by the `SortedRecords`
+//! type of the `sign` module if the `sign` and `ring` features are enabled.
+//!
+//! [RFC 8976]: https://tools.ietf.org/html/rfc8976
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Rtype, ZonemdAlg, ZonemdScheme};
+use crate::base::name::PushError;
+use crate::base::rdata::{
+    ComposeRecordData, LongRecordData, ParseRecordData, RecordData,
+};
+use crate::base::scan::{Scan, Scanner, ScannerError};
+use crate::base::serial::Serial;
+use crate::base::wire::{Compose, Composer, Parse, ParseError};
+use crate::utils::base16;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+use octseq::octets::{Octets, OctetsFrom, OctetsInto};
+use octseq::parse::Parser;
+
+//------------ Zonemd --------------------------------------------------------
+
+/// ZONEMD record data.
+///
+/// ZONEMD records are placed at the apex of a zone and contain a digest
+/// over the content of the zone. The serial field repeats the serial of
+/// the zone’s SOA record the digest was calculated for. The scheme
+/// determines how the zone’s records are collated and the hash algorithm
+/// determines the hash function used to create the digest.
+///
+/// The ZONEMD record type is defined in [RFC 8976].
+///
+/// [RFC 8976]: https://tools.ietf.org/html/rfc8976
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            Octs: octseq::serde::SerializeOctets + AsRef<[u8]>
+        ",
+        deserialize = "
+            Octs:
+                octseq::builder::FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <Octs as octseq::builder::FromBuilder>::Builder:
+                octseq::builder::OctetsBuilder
+                + octseq::builder::EmptyBuilder,
+        ",
+    ))
+)]
+pub struct Zonemd<Octs> {
+    serial: Serial,
+    scheme: ZonemdScheme,
+    algorithm: ZonemdAlg,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::base16::serde")
+    )]
+    digest: Octs,
+}
+
+impl<Octs> Zonemd<Octs> {
+    /// Creates new ZONEMD record data from its components.
+    ///
+    /// The function will fail if the digest is longer than 65,529 octets.
+    pub fn new(
+        serial: Serial,
+        scheme: ZonemdScheme,
+        algorithm: ZonemdAlg,
+        digest: Octs,
+    ) -> Result<Self, LongRecordData>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        LongRecordData::check_len(
+            6usize
+                .checked_add(digest.as_ref().len())
+                .expect("long digest"),
+        )?;
+        Ok(unsafe {
+            Zonemd::new_unchecked(serial, scheme, algorithm, digest)
+        })
+    }
+
+    /// Creates new ZONEMD record data without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller needs to ensure that wire format representation of the
+    /// record data is at most 65,535 octets long.
+    pub unsafe fn new_unchecked(
+        serial: Serial,
+        scheme: ZonemdScheme,
+        algorithm: ZonemdAlg,
+        digest: Octs,
+    ) -> Self {
+        Zonemd {
+            serial,
+            scheme,
+            algorithm,
+            digest,
+        }
+    }
+
+    /// Returns the serial of the zone the digest was calculated for.
+    pub fn serial(&self) -> Serial {
+        self.serial
+    }
+
+    /// Returns the scheme.
+    pub fn scheme(&self) -> ZonemdScheme {
+        self.scheme
+    }
+
+    /// Returns the hash algorithm.
+    pub fn algorithm(&self) -> ZonemdAlg {
+        self.algorithm
+    }
+
+    /// Returns the digest.
+    pub fn digest(&self) -> &Octs {
+        &self.digest
+    }
+
+    /// Converts the record data into the digest.
+    pub fn into_digest(self) -> Octs {
+        self.digest
+    }
+
+    pub(super) fn convert_octets<Target: OctetsFrom<Octs>>(
+        self,
+    ) -> Result<Zonemd<Target>, Target::Error> {
+        Ok(unsafe {
+            Zonemd::new_unchecked(
+                self.serial,
+                self.scheme,
+                self.algorithm,
+                self.digest.try_octets_into()?,
+            )
+        })
+    }
+
+    pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
+        parser: &mut Parser<'a, Src>,
+    ) -> Result<Self, ParseError> {
+        let len = match parser.remaining().checked_sub(6) {
+            Some(len) => len,
+            None => return Err(ParseError::ShortInput),
+        };
+        Ok(unsafe {
+            Self::new_unchecked(
+                Serial::parse(parser)?,
+                ZonemdScheme::parse(parser)?,
+                ZonemdAlg::parse(parser)?,
+                parser.parse_octets(len)?,
+            )
+        })
+    }
+
+    pub fn scan<S: Scanner<Octets = Octs>>(
+        scanner: &mut S,
+    ) -> Result<Self, S::Error>
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Self::new(
+            Serial::scan(scanner)?,
+            ZonemdScheme::scan(scanner)?,
+            ZonemdAlg::scan(scanner)?,
+            scanner.convert_entry(base16::SymbolConverter::new())?,
+        )
+        .map_err(|err| S::Error::custom(err.as_str()))
+    }
+}
+
+impl<SrcOcts> Zonemd<SrcOcts> {
+    pub fn flatten_into<Octs>(self) -> Result<Zonemd<Octs>, PushError>
+    where
+        Octs: OctetsFrom<SrcOcts>,
+    {
+        let Self {
+            serial,
+            scheme,
+            algorithm,
+            digest,
+        } = self;
+        Ok(unsafe {
+            Zonemd::new_unchecked(
+                serial,
+                scheme,
+                algorithm,
+                digest.try_octets_into().map_err(Into::into)?,
+            )
+        })
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<Zonemd<SrcOcts>> for Zonemd<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>,
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(source: Zonemd<SrcOcts>) -> Result<Self, Self::Error> {
+        Ok(unsafe {
+            Zonemd::new_unchecked(
+                source.serial,
+                source.scheme,
+                source.algorithm,
+                Octs::try_octets_from(source.digest)?,
+            )
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<Octs, Other> PartialEq<Zonemd<Other>> for Zonemd<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn eq(&self, other: &Zonemd<Other>) -> bool {
+        self.serial == other.serial
+            && self.scheme == other.scheme
+            && self.algorithm == other.algorithm
+            && self.digest.as_ref() == other.digest.as_ref()
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Eq for Zonemd<Octs> {}
+
+//--- PartialOrd, CanonicalOrd, and Ord
+
+impl<Octs, Other> PartialOrd<Zonemd<Other>> for Zonemd<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn partial_cmp(&self, other: &Zonemd<Other>) -> Option<Ordering> {
+        Some(self.canonical_cmp(other))
+    }
+}
+
+impl<Octs, Other> CanonicalOrd<Zonemd<Other>> for Zonemd<Octs>
+where
+    Octs: AsRef<[u8]>,
+    Other: AsRef<[u8]>,
+{
+    fn canonical_cmp(&self, other: &Zonemd<Other>) -> Ordering {
+        match self.serial.canonical_cmp(&other.serial) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.scheme.cmp(&other.scheme) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        match self.algorithm.cmp(&other.algorithm) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        self.digest.as_ref().cmp(other.digest.as_ref())
+    }
+}
+
+impl<Octs: AsRef<[u8]>> Ord for Zonemd<Octs> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical_cmp(other)
+    }
+}
+
+//--- Hash
+
+impl<Octs: AsRef<[u8]>> hash::Hash for Zonemd<Octs> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.serial.hash(state);
+        self.scheme.hash(state);
+        self.algorithm.hash(state);
+        self.digest.as_ref().hash(state);
+    }
+}
+
+//--- RecordData, ParseRecordData, ComposeRecordData
+
+impl<Octs> RecordData for Zonemd<Octs> {
+    fn rtype(&self) -> Rtype {
+        Rtype::Zonemd
+    }
+}
+
+impl<'a, Octs> ParseRecordData<'a, Octs> for Zonemd<Octs::Range<'a>>
+where
+    Octs: Octets + ?Sized,
+{
+    fn parse_rdata(
+        rtype: Rtype,
+        parser: &mut Parser<'a, Octs>,
+    ) -> Result<Option<Self>, ParseError> {
+        if rtype == Rtype::Zonemd {
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<Octs: AsRef<[u8]>> ComposeRecordData for Zonemd<Octs> {
+    fn rdlen(&self, _compress: bool) -> Option<u16> {
+        Some(
+            u16::try_from(self.digest.as_ref().len())
+                .expect("long digest")
+                .checked_add(
+                    u32::COMPOSE_LEN
+                        + ZonemdScheme::COMPOSE_LEN
+                        + ZonemdAlg::COMPOSE_LEN,
+                )
+                .expect("long digest"),
+        )
+    }
+
+    fn compose_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.serial.compose(target)?;
+        self.scheme.compose(target)?;
+        self.algorithm.compose(target)?;
+        target.append_slice(self.digest.as_ref())
+    }
+
+    fn compose_canonical_rdata<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        self.compose_rdata(target)
+    }
+}
+
+//--- Display
+
+impl<Octs: AsRef<[u8]>> fmt::Display for Zonemd<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} ", self.serial, self.scheme, self.algorithm)?;
+        base16::display(&self.digest, f)
+    }
+}
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]>> fmt::Debug for Zonemd<Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Zonemd")
+            .field("serial", &self.serial)
+            .field("scheme", &self.scheme)
+            .field("algorithm", &self.algorithm)
+            .field("digest", &self.digest.as_ref())
+            .finish()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod test {
+    use super::*;
+    use crate::base::rdata::test::{
+        test_compose_parse, test_rdlen, test_scan,
+    };
+    use std::string::ToString;
+
+    #[test]
+    fn zonemd_compose_parse_scan() {
+        let rdata = Zonemd::new(
+            Serial(2018031900),
+            ZonemdScheme::Simple,
+            ZonemdAlg::Sha384,
+            b"digest",
+        )
+        .unwrap();
+        test_rdlen(&rdata);
+        test_compose_parse(&rdata, |parser| Zonemd::parse(parser));
+        test_scan(
+            &["2018031900", "1", "1", "6469", "67657374"],
+            Zonemd::scan,
+            &rdata,
+        );
+        assert_eq!(rdata.to_string(), "2018031900 1 1 646967657374");
+    }
+}
@@ -21,10 +21,29 @@ macro_rules! svcb_types {
 /// Struct has priority and target decoded, but not parameters.
 /// Provides a [`iter`](Svcb::iter) method to iterate through each parameter.
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "
+            O: AsRef<[u8]> + octseq::serde::SerializeOctets,
+            N: serde::Serialize,
+        ",
+        deserialize = "
+            O: AsRef<[u8]> + FromBuilder
+                + octseq::serde::DeserializeOctets<'de>,
+            <O as FromBuilder>::Builder:
+                EmptyBuilder + AsRef<[u8]> + AsMut<[u8]>,
+            N: serde::Deserialize<'de>,
+        ",
+    ))
+)]
 pub struct $name<O, N> {
     priority: u16,
     target: N,
+    #[cfg_attr(feature = "serde", serde(with = "params_serde"))]
     params: O,
+    #[cfg_attr(feature = "serde", serde(skip))]
     sorter: Sorter,
 }
 
@@ -203,6 +222,11 @@ where Octs: AsRef<[u8]>, Name: ToDname {
         self.priority.compose(target)?;
         self.target.compose(target)?;
 
+        // Parameters that haven’t been pushed are used as they are.
+        if self.sorter.n == 0 {
+            return target.append_slice(self.params.as_ref());
+        }
+
         let view = self.sorter.buf.as_slice();
         let mut bytes = [0u8; 2];
         for chunk in view.chunks_exact(Sorter::CHUNK_SIZE) {
@@ -382,6 +406,26 @@ impl<'a, Octs: Octets> Iterator for ParamIter<'a, Octs> {
     }
 }
 
+/// Checks that the parameters are well-formed and ordered by key.
+///
+/// Keys have to be in strictly increasing order, i.e., each key may only
+/// appear once.
+#[cfg(feature = "serde")]
+fn check_params(params: &[u8]) -> Result<(), ParseError> {
+    let mut parser = Parser::from_ref(params);
+    let mut last = None;
+    while parser.remaining() > 0 {
+        let param = AllParams::parse(&mut parser)?;
+        let key = u16::from(param.key());
+        if matches!(last, Some(last) if key <= last) {
+            return Err(FormError::new("unordered SVCB parameters").into());
+        }
+        last = Some(key);
+        param.check()?;
+    }
+    Ok(())
+}
+
 pub mod param {
     use super::*;
     use crate::base::net::{Ipv4Addr, Ipv6Addr};
@@ -392,6 +436,20 @@ pub mod param {
         ($($name:ident($type:ident $( < $( $type_arg:ident ),* > )*),)+) => {
             /// A enum to hold all the parameters.
             #[derive(Debug, Clone, Eq, PartialEq)]
+            #[cfg_attr(
+                feature = "serde",
+                derive(serde::Serialize, serde::Deserialize),
+                serde(bound(
+                    serialize = "
+                        Octs: AsRef<[u8]> + octseq::serde::SerializeOctets
+                    ",
+                    deserialize = "
+                        Octs: FromBuilder
+                            + octseq::serde::DeserializeOctets<'de>,
+                        <Octs as FromBuilder>::Builder: EmptyBuilder,
+                    ",
+                ))
+            )]
             pub enum AllParams<Octs> {
                 $($name( $type $( < $( $type_arg ),* > )* )),+
             }
@@ -504,12 +562,66 @@ pub mod param {
         }
     }
 
+    impl<Octs: Octets> AllParams<Octs> {
+        /// Checks that the value of the parameter is well-formed.
+        ///
+        /// Parameters that contain a list of values need at least one.
+        #[cfg(feature = "serde")]
+        pub(super) fn check(&self) -> Result<(), ParseError> {
+            fn check_list<T>(
+                empty: bool,
+                mut iter: impl Iterator<Item = Result<T, ParseError>>,
+            ) -> Result<(), ParseError> {
+                if empty {
+                    return Err(FormError::new("empty SVCB parameter").into());
+                }
+                iter.try_for_each(|item| item.map(|_| ()))
+            }
+
+            match self {
+                Self::Mandatory(v) => {
+                    check_list(v.as_slice().is_empty(), v.iter())
+                }
+                Self::Alpn(v) => {
+                    check_list(v.as_slice().is_empty(), v.iter())
+                }
+                Self::Ipv4Hint(v) => {
+                    check_list(v.as_slice().is_empty(), v.iter())
+                }
+                Self::Ipv6Hint(v) => {
+                    check_list(v.as_slice().is_empty(), v.iter())
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
     // for types wraps an octets
     macro_rules! octets_wrapper {
         ($name:ident) => {
             /// A SVCB parameter.
             #[derive(Debug, Clone, Eq, PartialEq)]
-            pub struct $name<Octs>(Octs);
+            #[cfg_attr(
+                feature = "serde",
+                derive(serde::Serialize, serde::Deserialize),
+                serde(bound(
+                    serialize = "
+                        Octs: AsRef<[u8]> + octseq::serde::SerializeOctets
+                    ",
+                    deserialize = "
+                        Octs: FromBuilder
+                            + octseq::serde::DeserializeOctets<'de>,
+                        <Octs as FromBuilder>::Builder: EmptyBuilder,
+                    ",
+                ))
+            )]
+            pub struct $name<Octs>(
+                #[cfg_attr(
+                    feature = "serde",
+                    serde(with = "crate::utils::base16::serde")
+                )]
+                Octs,
+            );
 
             impl<Octs> $name<Octs> {
                 pub fn parse<'a, Src: Octets<Range<'a> = Octs> + ?Sized>(
@@ -701,6 +813,10 @@ pub mod param {
     }
 
     #[derive(Debug, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize)
+    )]
     pub struct NoDefaultAlpn;
 
     impl NoDefaultAlpn {
@@ -737,12 +853,21 @@ pub mod param {
     }
 
     #[derive(Debug, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize)
+    )]
     pub struct Port(u16);
     impl Port {
         pub fn new(port: u16) -> Self {
             Self(port)
         }
 
+        /// Returns the port number.
+        pub fn port(&self) -> u16 {
+            self.0
+        }
+
         pub fn parse<Octs: AsRef<[u8]> + ?Sized>(
             parser: &mut Parser<Octs>,
         ) -> Result<Self, ParseError> {
@@ -877,8 +1002,23 @@ pub mod param {
     }
 
     #[derive(Debug, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(bound(
+            serialize = "Octs: AsRef<[u8]> + octseq::serde::SerializeOctets",
+            deserialize = "
+                Octs: FromBuilder + octseq::serde::DeserializeOctets<'de>,
+                <Octs as FromBuilder>::Builder: EmptyBuilder,
+            ",
+        ))
+    )]
     pub struct Unknown<Octs> {
         key: SvcbParamKey,
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::utils::base16::serde")
+        )]
         val: Octs,
     }
 
@@ -918,6 +1058,478 @@ pub mod param {
     }
 }
 
+//------------ params_serde --------------------------------------------------
+
+/// Serialize and deserialize the parameters of SVCB record data.
+///
+/// This module is used with Serde’s `with` attribute. Human readable
+/// serializers get a map from the key in presentation format to a value
+/// that depends on the key: a sequence of keys for `mandatory`, a sequence
+/// of strings for `alpn`, a unit for `nodefaultalpn`, an integer for
+/// `port`, a Base 64 string for `ech`, sequences of addresses for the two
+/// hints, a string for `dohpath`, and a Base 16 string for any other key.
+/// Compact serializers get the parameters in wire format.
+///
+/// Deserialization checks the parameters via [`check_params`].
+#[cfg(feature = "serde")]
+mod params_serde {
+    use super::check_params;
+    use super::param::{AllParams, SvcbParam};
+    use crate::base::iana::SvcbParamKey;
+    use crate::base::net::{Ipv4Addr, Ipv6Addr};
+    use crate::utils::{base16, base64};
+    use core::fmt;
+    use core::marker::PhantomData;
+    use core::str::FromStr;
+    use octseq::builder::{
+        EmptyBuilder, FromBuilder, OctetsBuilder, ShortBuf,
+    };
+    use octseq::parse::Parser;
+    use octseq::serde::{DeserializeOctets, SerializeOctets};
+    use serde::de::{Error as _, Unexpected};
+    use serde::ser::{Error as _, SerializeMap, SerializeSeq};
+
+    pub fn serialize<Octs, S>(
+        params: &Octs,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        Octs: AsRef<[u8]> + SerializeOctets,
+        S: serde::Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return params.serialize_octets(serializer);
+        }
+        let mut parser = Parser::from_ref(params.as_ref());
+        let mut map = serializer.serialize_map(None)?;
+        while parser.remaining() > 0 {
+            let param =
+                AllParams::parse(&mut parser).map_err(S::Error::custom)?;
+            map.serialize_entry(&Key(param.key()), &Value(&param))?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, Octs, D>(
+        deserializer: D,
+    ) -> Result<Octs, D::Error>
+    where
+        Octs: AsRef<[u8]> + FromBuilder + DeserializeOctets<'de>,
+        <Octs as FromBuilder>::Builder:
+            EmptyBuilder + AsRef<[u8]> + AsMut<[u8]>,
+        D: serde::Deserializer<'de>,
+    {
+        let params = if deserializer.is_human_readable() {
+            deserializer.deserialize_map(ParamsVisitor(PhantomData))?
+        } else {
+            Octs::deserialize_octets(deserializer)?
+        };
+        check_params(params.as_ref()).map_err(D::Error::custom)?;
+        Ok(params)
+    }
+
+    //--- Key
+
+    /// A parameter key in presentation format.
+    struct Key(SvcbParamKey);
+
+    impl serde::Serialize for Key {
+        fn serialize<S: serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&self.0)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Key {
+        fn deserialize<D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = Key;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("an SVCB parameter key")
+                }
+
+                fn visit_str<E: serde::de::Error>(
+                    self,
+                    v: &str,
+                ) -> Result<Self::Value, E> {
+                    let key = match v {
+                        "mandatory" => SvcbParamKey::Mandatory,
+                        "alpn" => SvcbParamKey::Alpn,
+                        "nodefaultalpn" => SvcbParamKey::NoDefaultAlpn,
+                        "port" => SvcbParamKey::Port,
+                        "ipv4hint" => SvcbParamKey::Ipv4Hint,
+                        "ech" => SvcbParamKey::Ech,
+                        "ipv6hint" => SvcbParamKey::Ipv6Hint,
+                        "dohpath" => SvcbParamKey::DohPath,
+                        _ => v
+                            .strip_prefix("key")
+                            .and_then(|n| n.parse().ok())
+                            .map(SvcbParamKey::from_int)
+                            .ok_or_else(|| {
+                                E::invalid_value(Unexpected::Str(v), &self)
+                            })?,
+                    };
+                    Ok(Key(key))
+                }
+            }
+
+            deserializer.deserialize_str(Visitor)
+        }
+    }
+
+    //--- Value
+
+    /// The value of a parameter in its human readable form.
+    struct Value<'a, 'b>(&'b AllParams<&'a [u8]>);
+
+    impl<'a, 'b> serde::Serialize for Value<'a, 'b> {
+        fn serialize<S: serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            fn serialize_list<S, T>(
+                serializer: S,
+                iter: impl Iterator<Item = Result<T, S::Error>>,
+            ) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+                T: serde::Serialize,
+            {
+                let mut seq = serializer.serialize_seq(None)?;
+                for item in iter {
+                    seq.serialize_element(&item?)?;
+                }
+                seq.end()
+            }
+
+            match self.0 {
+                AllParams::Mandatory(v) => serialize_list(
+                    serializer,
+                    v.iter()
+                        .map(|key| key.map(Key).map_err(S::Error::custom)),
+                ),
+                AllParams::Alpn(v) => serialize_list(
+                    serializer,
+                    v.iter().map(|id| {
+                        core::str::from_utf8(id.map_err(S::Error::custom)?)
+                            .map_err(S::Error::custom)
+                    }),
+                ),
+                AllParams::NoDefaultAlpn(_) => serializer.serialize_unit(),
+                AllParams::Port(v) => serializer.serialize_u16(v.port()),
+                AllParams::Ech(v) => serializer
+                    .collect_str(&base64::encode_display(&v.as_slice())),
+                AllParams::Ipv4Hint(v) => serialize_list(
+                    serializer,
+                    v.iter().map(|addr| {
+                        addr.map(Address).map_err(S::Error::custom)
+                    }),
+                ),
+                AllParams::Ipv6Hint(v) => serialize_list(
+                    serializer,
+                    v.iter().map(|addr| {
+                        addr.map(Address).map_err(S::Error::custom)
+                    }),
+                ),
+                AllParams::DohPath(v) => serializer.serialize_str(
+                    core::str::from_utf8(v.as_slice())
+                        .map_err(S::Error::custom)?,
+                ),
+                AllParams::Unknown(v) => {
+                    serializer.collect_str(&base16::encode_display(v.value()))
+                }
+            }
+        }
+    }
+
+    //--- Address
+
+    /// An IP address in presentation format.
+    struct Address<T>(T);
+
+    impl<T: fmt::Display> serde::Serialize for Address<T> {
+        fn serialize<S: serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&self.0)
+        }
+    }
+
+    impl<'de, T: FromStr> serde::Deserialize<'de> for Address<T> {
+        fn deserialize<D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            struct Visitor<T>(PhantomData<T>);
+
+            impl<'de, T: FromStr> serde::de::Visitor<'de> for Visitor<T> {
+                type Value = Address<T>;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("an IP address")
+                }
+
+                fn visit_str<E: serde::de::Error>(
+                    self,
+                    v: &str,
+                ) -> Result<Self::Value, E> {
+                    T::from_str(v).map(Address).map_err(|_| {
+                        E::invalid_value(Unexpected::Str(v), &self)
+                    })
+                }
+            }
+
+            deserializer.deserialize_str(Visitor(PhantomData))
+        }
+    }
+
+    //--- ParamsVisitor
+
+    /// A visitor for the map of parameters.
+    ///
+    /// The parameters are composed in the order they appear in the map.
+    struct ParamsVisitor<Octs>(PhantomData<Octs>);
+
+    impl<'de, Octs> serde::de::Visitor<'de> for ParamsVisitor<Octs>
+    where
+        Octs: AsRef<[u8]> + FromBuilder,
+        <Octs as FromBuilder>::Builder:
+            EmptyBuilder + AsRef<[u8]> + AsMut<[u8]>,
+    {
+        type Value = Octs;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map of SVCB parameters")
+        }
+
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut target = <Octs as FromBuilder>::Builder::empty();
+            while let Some(Key(key)) = map.next_key()? {
+                let start = target.as_ref().len();
+                target
+                    .append_slice(&u16::from(key).to_be_bytes())
+                    .and_then(|_| target.append_slice(&[0; 2]))
+                    .map_err(|_| A::Error::custom(ShortBuf))?;
+                map.next_value_seed(ValueSeed::<Octs> {
+                    key,
+                    target: &mut target,
+                })?;
+                let len = u16::try_from(target.as_ref().len() - start - 4)
+                    .map_err(|_| A::Error::custom("long SVCB parameter"))?;
+                target.as_mut()[start + 2..start + 4]
+                    .copy_from_slice(&len.to_be_bytes());
+            }
+            Ok(Octs::from_builder(target))
+        }
+    }
+
+    //--- ValueSeed
+
+    /// Deserializes the value of a parameter and appends it to a builder.
+    struct ValueSeed<'a, Octs: FromBuilder> {
+        key: SvcbParamKey,
+        target: &'a mut Octs::Builder,
+    }
+
+    impl<'a, Octs: FromBuilder> ValueSeed<'a, Octs> {
+        fn append<E: serde::de::Error>(
+            &mut self,
+            data: &[u8],
+        ) -> Result<(), E> {
+            self.target
+                .append_slice(data)
+                .map_err(|_| E::custom(ShortBuf))
+        }
+    }
+
+    impl<'a, 'de, Octs> serde::de::DeserializeSeed<'de> for ValueSeed<'a, Octs>
+    where
+        Octs: AsRef<[u8]> + FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+    {
+        type Value = ();
+
+        fn deserialize<D: serde::Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            match self.key {
+                SvcbParamKey::Mandatory
+                | SvcbParamKey::Alpn
+                | SvcbParamKey::Ipv4Hint
+                | SvcbParamKey::Ipv6Hint => {
+                    deserializer.deserialize_seq(self)
+                }
+                SvcbParamKey::NoDefaultAlpn => {
+                    deserializer.deserialize_unit(self)
+                }
+                SvcbParamKey::Port => deserializer.deserialize_u16(self),
+                _ => deserializer.deserialize_str(self),
+            }
+        }
+    }
+
+    impl<'a, 'de, Octs> serde::de::Visitor<'de> for ValueSeed<'a, Octs>
+    where
+        Octs: AsRef<[u8]> + FromBuilder,
+        <Octs as FromBuilder>::Builder: EmptyBuilder,
+    {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.key {
+                SvcbParamKey::Mandatory => {
+                    f.write_str("a sequence of SVCB parameter keys")
+                }
+                SvcbParamKey::Alpn => f.write_str("a sequence of ALPN IDs"),
+                SvcbParamKey::NoDefaultAlpn => f.write_str("a unit"),
+                SvcbParamKey::Port => f.write_str("a port number"),
+                SvcbParamKey::Ech => f.write_str("a Base 64 encoded string"),
+                SvcbParamKey::Ipv4Hint | SvcbParamKey::Ipv6Hint => {
+                    f.write_str("a sequence of IP addresses")
+                }
+                SvcbParamKey::DohPath => f.write_str("a URI template"),
+                _ => f.write_str("a Base 16 encoded string"),
+            }
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<(), E> {
+            match self.key {
+                SvcbParamKey::NoDefaultAlpn => Ok(()),
+                _ => Err(E::invalid_type(Unexpected::Unit, &self)),
+            }
+        }
+
+        fn visit_u64<E: serde::de::Error>(mut self, v: u64) -> Result<(), E> {
+            match self.key {
+                SvcbParamKey::Port => {
+                    let port = u16::try_from(v).map_err(|_| {
+                        E::invalid_value(Unexpected::Unsigned(v), &self)
+                    })?;
+                    self.append(&port.to_be_bytes())
+                }
+                _ => Err(E::invalid_type(Unexpected::Unsigned(v), &self)),
+            }
+        }
+
+        fn visit_str<E: serde::de::Error>(
+            mut self,
+            v: &str,
+        ) -> Result<(), E> {
+            match self.key {
+                SvcbParamKey::Mandatory
+                | SvcbParamKey::Alpn
+                | SvcbParamKey::NoDefaultAlpn
+                | SvcbParamKey::Port
+                | SvcbParamKey::Ipv4Hint
+                | SvcbParamKey::Ipv6Hint => {
+                    Err(E::invalid_type(Unexpected::Str(v), &self))
+                }
+                SvcbParamKey::Ech => {
+                    let data: Octs = base64::decode(v).map_err(E::custom)?;
+                    self.append(data.as_ref())
+                }
+                SvcbParamKey::DohPath => self.append(v.as_bytes()),
+                _ => {
+                    let data: Octs = base16::decode(v).map_err(E::custom)?;
+                    self.append(data.as_ref())
+                }
+            }
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(
+            mut self,
+            mut seq: A,
+        ) -> Result<(), A::Error> {
+            match self.key {
+                SvcbParamKey::Mandatory => {
+                    while let Some(Key(key)) = seq.next_element()? {
+                        self.append::<A::Error>(
+                            &u16::from(key).to_be_bytes(),
+                        )?;
+                    }
+                }
+                SvcbParamKey::Alpn => {
+                    while seq
+                        .next_element_seed(AlpnIdSeed(&mut *self.target))?
+                        .is_some()
+                    {}
+                }
+                SvcbParamKey::Ipv4Hint => {
+                    while let Some(Address::<Ipv4Addr>(addr)) =
+                        seq.next_element()?
+                    {
+                        self.append::<A::Error>(&addr.octets())?;
+                    }
+                }
+                SvcbParamKey::Ipv6Hint => {
+                    while let Some(Address::<Ipv6Addr>(addr)) =
+                        seq.next_element()?
+                    {
+                        self.append::<A::Error>(&addr.octets())?;
+                    }
+                }
+                _ => {
+                    return Err(A::Error::invalid_type(
+                        Unexpected::Seq,
+                        &self,
+                    ))
+                }
+            }
+            Ok(())
+        }
+    }
+
+    //--- AlpnIdSeed
+
+    /// Deserializes an ALPN ID and appends it to a builder.
+    struct AlpnIdSeed<'a, Builder>(&'a mut Builder);
+
+    impl<'a, 'de, Builder: OctetsBuilder> serde::de::DeserializeSeed<'de>
+        for AlpnIdSeed<'a, Builder>
+    {
+        type Value = ();
+
+        fn deserialize<D: serde::Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    impl<'a, 'de, Builder: OctetsBuilder> serde::de::Visitor<'de>
+        for AlpnIdSeed<'a, Builder>
+    {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an ALPN ID")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<(), E> {
+            let len = u8::try_from(v.len())
+                .ok()
+                .filter(|len| *len > 0)
+                .ok_or_else(|| E::invalid_length(v.len(), &self))?;
+            self.0
+                .append_slice(&[len])
+                .and_then(|_| self.0.append_slice(v.as_bytes()))
+                .map_err(|_| E::custom(ShortBuf))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1442,4 +2054,208 @@ mod test {
             iter.next().unwrap().unwrap().key()
         );
     }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn serde_svcb() -> Svcb<std::vec::Vec<u8>, Dname<std::vec::Vec<u8>>> {
+        use std::vec::Vec;
+
+        let mut mandatory = param::Mandatory::new(Vec::new());
+        mandatory.push(SvcbParamKey::Alpn).unwrap();
+        mandatory.push(SvcbParamKey::Ipv4Hint).unwrap();
+
+        let mut alpn = param::Alpn::new(Vec::new());
+        alpn.push("h2").unwrap();
+        alpn.push("h3-19").unwrap();
+
+        let mut ipv4_hint = param::Ipv4Hint::new(Vec::new());
+        ipv4_hint.push([192, 0, 2, 1].into()).unwrap();
+
+        let mut svcb =
+            Svcb::new(1, "foo.example.org".parse().unwrap(), Vec::new());
+        svcb.push(mandatory.into()).unwrap();
+        svcb.push(alpn.into()).unwrap();
+        svcb.push::<&[u8]>(param::NoDefaultAlpn.into()).unwrap();
+        svcb.push::<&[u8]>(param::Port::new(8443).into()).unwrap();
+        svcb.push(ipv4_hint.into()).unwrap();
+        svcb.freeze()
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn ser_de() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let svcb = serde_svcb();
+        assert_tokens(
+            &svcb.clone().compact(),
+            &[
+                Token::Struct {
+                    name: "Svcb",
+                    len: 3,
+                },
+                Token::Str("priority"),
+                Token::U16(1),
+                Token::Str("target"),
+                Token::NewtypeStruct { name: "Dname" },
+                Token::ByteBuf(b"\x03foo\x07example\x03org\0"),
+                Token::Str("params"),
+                Token::ByteBuf(
+                    b"\x00\x00\x00\x04\x00\x01\x00\x04\
+                      \x00\x01\x00\x09\x02h2\x05h3-19\
+                      \x00\x02\x00\x00\
+                      \x00\x03\x00\x02\x20\xfb\
+                      \x00\x04\x00\x04\xc0\x00\x02\x01",
+                ),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &svcb.readable(),
+            &[
+                Token::Struct {
+                    name: "Svcb",
+                    len: 3,
+                },
+                Token::Str("priority"),
+                Token::U16(1),
+                Token::Str("target"),
+                Token::NewtypeStruct { name: "Dname" },
+                Token::Str("foo.example.org"),
+                Token::Str("params"),
+                Token::Map { len: None },
+                Token::Str("mandatory"),
+                Token::Seq { len: None },
+                Token::Str("alpn"),
+                Token::Str("ipv4hint"),
+                Token::SeqEnd,
+                Token::Str("alpn"),
+                Token::Seq { len: None },
+                Token::Str("h2"),
+                Token::Str("h3-19"),
+                Token::SeqEnd,
+                Token::Str("nodefaultalpn"),
+                Token::Unit,
+                Token::Str("port"),
+                Token::U16(8443),
+                Token::Str("ipv4hint"),
+                Token::Seq { len: None },
+                Token::Str("192.0.2.1"),
+                Token::SeqEnd,
+                Token::MapEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn ser_de_compose() {
+        use std::vec::Vec;
+
+        let svcb = serde_svcb();
+        let yaml = serde_yaml::to_string(&svcb).unwrap();
+        let parsed: Svcb<Vec<u8>, Dname<Vec<u8>>> =
+            serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(svcb, parsed);
+
+        let mut composed = Vec::new();
+        svcb.compose_rdata(&mut composed).unwrap();
+        let mut parsed_composed = Vec::new();
+        parsed.compose_rdata(&mut parsed_composed).unwrap();
+        assert_eq!(composed, parsed_composed);
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn de_invalid_params() {
+        use serde_test::{assert_de_tokens_error, Compact, Readable, Token};
+        use std::vec::Vec;
+
+        type Data = Svcb<Vec<u8>, Dname<Vec<u8>>>;
+
+        let head = [
+            Token::Struct {
+                name: "Svcb",
+                len: 3,
+            },
+            Token::Str("priority"),
+            Token::U16(1),
+            Token::Str("target"),
+            Token::NewtypeStruct { name: "Dname" },
+        ];
+        let readable = |params: &[Token]| {
+            let mut tokens = Vec::from(head.as_ref());
+            tokens.push(Token::Str("foo.example.org"));
+            tokens.push(Token::Str("params"));
+            tokens.extend_from_slice(params);
+            tokens
+        };
+
+        // Keys out of order.
+        assert_de_tokens_error::<Readable<Data>>(
+            &readable(&[
+                Token::Map { len: None },
+                Token::Str("port"),
+                Token::U16(8443),
+                Token::Str("alpn"),
+                Token::Seq { len: None },
+                Token::Str("h2"),
+                Token::SeqEnd,
+                Token::MapEnd,
+            ]),
+            "unordered SVCB parameters",
+        );
+
+        // Duplicate keys.
+        assert_de_tokens_error::<Readable<Data>>(
+            &readable(&[
+                Token::Map { len: None },
+                Token::Str("port"),
+                Token::U16(8443),
+                Token::Str("port"),
+                Token::U16(443),
+                Token::MapEnd,
+            ]),
+            "unordered SVCB parameters",
+        );
+
+        // Empty list.
+        assert_de_tokens_error::<Readable<Data>>(
+            &readable(&[
+                Token::Map { len: None },
+                Token::Str("alpn"),
+                Token::Seq { len: None },
+                Token::SeqEnd,
+                Token::MapEnd,
+            ]),
+            "empty SVCB parameter",
+        );
+
+        // Unknown key name.
+        assert_de_tokens_error::<Readable<Data>>(
+            &readable(&[Token::Map { len: None }, Token::Str("foo")]),
+            "invalid value: string \"foo\", \
+             expected an SVCB parameter key",
+        );
+
+        // Wrong value type.
+        assert_de_tokens_error::<Readable<Data>>(
+            &readable(&[
+                Token::Map { len: None },
+                Token::Str("port"),
+                Token::Str("8443"),
+            ]),
+            "invalid type: string \"8443\", expected a port number",
+        );
+
+        // Port with trailing data.
+        let mut tokens = Vec::from(head.as_ref());
+        tokens.push(Token::ByteBuf(b"\x03foo\x07example\x03org\0"));
+        tokens.push(Token::Str("params"));
+        tokens.push(Token::ByteBuf(b"\x00\x03\x00\x03\x20\xfb\x00"));
+        assert_de_tokens_error::<Compact<Data>>(
+            &tokens,
+            "trailing data in option",
+        );
+    }
 }
@@ -20,6 +20,10 @@ use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 /// IP addresses or even socket addresses. Since the lookup may determine that
 /// the host name is in fact an alias for another name, the value will also
 /// return the canonical name.
+///
+/// Checking that the names in the answers are valid host names is left to
+/// the resolver. The stub resolver does so unless its `no_check_name`
+/// option is set.
 pub async fn lookup_host<R: Resolver>(
     resolver: &R,
     qname: impl ToDname,
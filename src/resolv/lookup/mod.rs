@@ -5,8 +5,10 @@
 
 pub use self::addr::lookup_addr;
 pub use self::host::{lookup_host, search_host};
+pub use self::naptr::{lookup_enum, lookup_naptr};
 pub use self::srv::lookup_srv;
 
 pub mod addr;
 pub mod host;
+pub mod naptr;
 pub mod srv;
@@ -0,0 +1,251 @@
+//! Looking up NAPTR records and ENUM.
+
+use crate::base::iana::Rtype;
+use crate::base::message::Message;
+use crate::base::name::{Dname, DnameBuilder, ToDname};
+use crate::base::wire::ParseError;
+use crate::rdata::Naptr;
+use crate::resolv::resolver::Resolver;
+use octseq::builder::FromBuilder;
+use std::string::String;
+use std::vec::Vec;
+use std::{fmt, io, slice};
+
+//------------ OctetsVec and Octets128 ---------------------------------------
+
+#[cfg(feature = "smallvec")]
+type OctetsVec = octseq::octets::SmallOctets;
+
+#[cfg(not(feature = "smallvec"))]
+type OctetsVec = Vec<u8>;
+
+type Octets128 = octseq::array::Array<128>;
+
+//------------ lookup_naptr --------------------------------------------------
+
+/// Looks up the NAPTR records of a domain name.
+///
+/// The function will use the resolver given in `resolver` to query the
+/// DNS for the NAPTR records of `name`. The records found are returned
+/// ordered by their order and preference fields.
+///
+/// If the `regex` feature is enabled, the rewrite rules of the records can
+/// be applied to an input string via [`FoundNaptrs::evaluate`].
+pub async fn lookup_naptr(
+    resolver: &impl Resolver,
+    name: impl ToDname,
+) -> Result<FoundNaptrs, NaptrError> {
+    let answer = resolver.query((name, Rtype::Naptr)).await?;
+    FoundNaptrs::new(answer.as_ref().for_slice())
+}
+
+//------------ lookup_enum ---------------------------------------------------
+
+/// Looks up the ENUM NAPTR records for a telephone number.
+///
+/// The number needs to be given in E.164 format, i.e., starting with a
+/// plus sign followed by the digits of the number. Spaces, hyphens, dots,
+/// and parentheses may be used to visually separate the digits. The number
+/// is converted into a domain name under `e164.arpa` via
+/// [`e164_to_dname`] which is then looked up via [`lookup_naptr`].
+///
+/// The rewrite rules of the records returned need to be applied to the
+/// application unique string of the number which can be created via
+/// [`e164_aus`].
+pub async fn lookup_enum(
+    resolver: &impl Resolver,
+    number: &str,
+) -> Result<FoundNaptrs, NaptrError> {
+    lookup_naptr(resolver, e164_to_dname(number)?).await
+}
+
+//------------ e164_to_dname -------------------------------------------------
+
+/// Converts an E.164 number into its ENUM domain name.
+///
+/// As described in section 2.4 of [RFC 6116], all characters but the
+/// digits are removed and the digits are then used as labels of the domain
+/// name in reverse order, with `e164.arpa` appended.
+///
+/// The function fails if the number doesn’t start with a plus sign,
+/// contains anything but digits and visual separators, or doesn’t have
+/// between one and fifteen digits.
+///
+/// [RFC 6116]: https://tools.ietf.org/html/rfc6116
+pub fn e164_to_dname(number: &str) -> Result<Dname<Octets128>, NaptrError> {
+    let digits = e164_digits(number)?;
+    let mut builder =
+        DnameBuilder::<<Octets128 as FromBuilder>::Builder>::new();
+    for digit in digits.bytes().rev() {
+        builder
+            .append_label(&[digit])
+            .expect("ENUM name exceeds 128 octets");
+    }
+    builder
+        .append_label(b"e164")
+        .expect("ENUM name exceeds 128 octets");
+    builder
+        .append_label(b"arpa")
+        .expect("ENUM name exceeds 128 octets");
+    Ok(builder.into_dname().expect("ENUM name exceeds 128 octets"))
+}
+
+//------------ e164_aus ------------------------------------------------------
+
+/// Returns the application unique string for an E.164 number.
+///
+/// The application unique string used by ENUM is the number with all
+/// visual separators removed, i.e., a plus sign followed by the digits.
+///
+/// The function fails under the same conditions as [`e164_to_dname`].
+pub fn e164_aus(number: &str) -> Result<String, NaptrError> {
+    let digits = e164_digits(number)?;
+    let mut res = String::with_capacity(digits.len() + 1);
+    res.push('+');
+    res.push_str(&digits);
+    Ok(res)
+}
+
+/// Returns the digits of an E.164 number.
+fn e164_digits(number: &str) -> Result<String, NaptrError> {
+    let number = number
+        .trim()
+        .strip_prefix('+')
+        .ok_or(NaptrError::InvalidNumber)?;
+    let mut res = String::with_capacity(15);
+    for ch in number.chars() {
+        match ch {
+            '0'..='9' => res.push(ch),
+            ' ' | '-' | '.' | '(' | ')' => {}
+            _ => return Err(NaptrError::InvalidNumber),
+        }
+    }
+    if res.is_empty() || res.len() > 15 {
+        return Err(NaptrError::InvalidNumber);
+    }
+    Ok(res)
+}
+
+//------------ FoundNaptrs ---------------------------------------------------
+
+/// The NAPTR records found by [`lookup_naptr`] or [`lookup_enum`].
+#[derive(Clone, Debug)]
+pub struct FoundNaptrs {
+    /// The records ordered by order and preference.
+    records: Vec<Naptr<OctetsVec, Dname<OctetsVec>>>,
+}
+
+impl FoundNaptrs {
+    fn new(answer: &Message<[u8]>) -> Result<Self, NaptrError> {
+        let name =
+            answer.canonical_name().ok_or(NaptrError::MalformedAnswer)?;
+        let mut records = Vec::new();
+        for record in answer.answer()?.limit_to_in::<Naptr<_, _>>() {
+            let record = record?;
+            if record.owner() != &name {
+                continue;
+            }
+            records.push(
+                record
+                    .into_data()
+                    .flatten_into()
+                    .map_err(|_| NaptrError::MalformedAnswer)?,
+            );
+        }
+        records.sort_by_key(|record| (record.order(), record.preference()));
+        Ok(FoundNaptrs { records })
+    }
+
+    /// Returns whether no records were found.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the number of records found.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns an iterator over the records in processing order.
+    pub fn iter(&self) -> slice::Iter<Naptr<OctetsVec, Dname<OctetsVec>>> {
+        self.records.iter()
+    }
+
+    /// Applies the rewrite rules of the records to a string.
+    ///
+    /// See [`rfc3403::evaluate`][crate::rdata::rfc3403::evaluate] for
+    /// details.
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn evaluate(
+        &self,
+        input: &str,
+    ) -> Vec<crate::rdata::rfc3403::NaptrMatch<OctetsVec, Dname<OctetsVec>>>
+    {
+        crate::rdata::rfc3403::evaluate(&self.records, input)
+    }
+}
+
+impl<'a> IntoIterator for &'a FoundNaptrs {
+    type Item = &'a Naptr<OctetsVec, Dname<OctetsVec>>;
+    type IntoIter = slice::Iter<'a, Naptr<OctetsVec, Dname<OctetsVec>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//------------ NaptrError ----------------------------------------------------
+
+#[derive(Debug)]
+pub enum NaptrError {
+    InvalidNumber,
+    MalformedAnswer,
+    Query(io::Error),
+}
+
+impl From<io::Error> for NaptrError {
+    fn from(err: io::Error) -> NaptrError {
+        NaptrError::Query(err)
+    }
+}
+
+impl From<ParseError> for NaptrError {
+    fn from(_: ParseError) -> NaptrError {
+        NaptrError::MalformedAnswer
+    }
+}
+
+impl fmt::Display for NaptrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NaptrError::InvalidNumber => f.write_str("invalid E.164 number"),
+            NaptrError::MalformedAnswer => f.write_str("malformed answer"),
+            NaptrError::Query(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for NaptrError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn e164() {
+        assert_eq!(
+            e164_to_dname("+44 20 7946-0148").unwrap(),
+            Dname::<Octets128>::from_str("8.4.1.0.6.4.9.7.0.2.4.4.e164.arpa")
+                .unwrap()
+        );
+        assert_eq!(e164_aus("+44 (20) 7946.0148").unwrap(), "+442079460148");
+        assert!(e164_to_dname("442079460148").is_err());
+        assert!(e164_to_dname("+").is_err());
+        assert!(e164_to_dname("+44a").is_err());
+        assert!(e164_to_dname("+1234567890123456").is_err());
+    }
+}
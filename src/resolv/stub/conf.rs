@@ -113,8 +113,12 @@ pub struct ResolvOptions {
 
     /// Disable checking of incoming hostname and mail names.
     ///
-    /// This is not currently implemented. Or rather, this is currently
-    /// always on—there is no name checking as yet.
+    /// Unless this option is set, the stub resolver rejects answers with
+    /// an error if the answer section contains records whose owner or
+    /// target names are not valid host, service, or mailbox names as
+    /// appropriate for the record type.
+    ///
+    /// This option is implemented by the query.
     pub no_check_name: bool,
 
     /// Do not strip TSIG records.
@@ -164,6 +168,36 @@ pub struct ResolvOptions {
     /// `search` and `ndots` fields govern resolution of relative names of
     /// all kinds.
     pub no_tld_query: bool,
+
+    /// Use DNS cookies.
+    ///
+    /// If enabled, queries to servers that support EDNS include a client
+    /// cookie as defined in RFC 7873 and the server cookie learned from
+    /// earlier responses. Responses that echo a different client cookie
+    /// are discarded.
+    ///
+    /// This option is implemented by the query.
+    pub use_cookies: bool,
+
+    /// The secret used for creating client cookies.
+    ///
+    /// The client cookie for a server is derived from this secret and the
+    /// IP addresses of the client and the server. If the value is `None`,
+    /// a random secret is chosen when the resolver is created.
+    pub cookie_secret: Option<[u8; 16]>,
+
+    /// Randomize the case of query names.
+    ///
+    /// If enabled, the letters in query names are sent in random case and
+    /// responses received over UDP have to echo the query name exactly as
+    /// described in draft-vixie-dnsext-dns0x20. Answers with the name in
+    /// different case are dropped. If this happens for several queries to
+    /// a server in a row, the server is assumed not to preserve case. The
+    /// query is then sent again and the case is not checked for this server
+    /// anymore.
+    ///
+    /// This option is implemented by the query.
+    pub randomize_case: bool,
 }
 
 impl Default for ResolvOptions {
@@ -198,6 +232,9 @@ impl Default for ResolvOptions {
             single_request: false,
             single_request_reopen: false,
             no_tld_query: false,
+            use_cookies: false,
+            cookie_secret: None,
+            randomize_case: false,
         }
     }
 }
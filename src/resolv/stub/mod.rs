@@ -12,25 +12,29 @@
 use self::conf::{
     ResolvConf, ResolvOptions, SearchSuffix, ServerConf, Transport,
 };
-use crate::base::iana::Rcode;
+use crate::base::iana::{OptRcode, Rcode, Rtype};
 use crate::base::message::Message;
 use crate::base::message_builder::{
     AdditionalBuilder, MessageBuilder, StreamTarget,
 };
-use crate::base::name::{ToDname, ToRelativeDname};
+use crate::base::name::{ParsedDname, ToDname, ToLabelIter, ToRelativeDname};
+use crate::base::opt::rfc7873::{ClientCookie, Cookie};
 use crate::base::question::Question;
+use crate::rdata::AllRecordData;
 use crate::resolv::lookup::addr::{lookup_addr, FoundAddrs};
 use crate::resolv::lookup::host::{lookup_host, search_host, FoundHosts};
 use crate::resolv::lookup::srv::{lookup_srv, FoundSrvs, SrvError};
 use crate::resolv::resolver::{Resolver, SearchNames};
 use bytes::Bytes;
+use core::hash::Hasher;
 use octseq::array::Array;
+use siphasher::sip::SipHasher24;
 use std::boxed::Box;
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::vec::Vec;
 use std::{io, ops};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -48,6 +52,10 @@ pub mod conf;
 /// How many times do we try a new random port if we get ‘address in use.’
 const RETRY_RANDOM_PORT: usize = 10;
 
+/// How many queries in a row need to see answers with the query name in
+/// the wrong case before we stop checking the case for a server.
+const CASE_MISMATCH_LIMIT: usize = 3;
+
 //------------ StubResolver --------------------------------------------------
 
 /// A DNS stub resolver.
@@ -89,11 +97,18 @@ impl StubResolver {
 
     /// Creates a new resolver using the given configuraiton.
     pub fn from_conf(conf: ResolvConf) -> Self {
+        let cookie_secret = if conf.options.use_cookies {
+            Some(conf.options.cookie_secret.unwrap_or_else(rand::random))
+        } else {
+            None
+        };
         StubResolver {
-            preferred: ServerList::from_conf(&conf, |s| {
+            preferred: ServerList::from_conf(&conf, cookie_secret, |s| {
                 s.transport.is_preferred()
             }),
-            stream: ServerList::from_conf(&conf, |s| s.transport.is_stream()),
+            stream: ServerList::from_conf(&conf, cookie_secret, |s| {
+                s.transport.is_stream()
+            }),
             options: conf.options,
         }
     }
@@ -107,7 +122,10 @@ impl StubResolver {
         question: Q,
     ) -> Result<Answer, io::Error> {
         Query::new(self)?
-            .run(Query::create_message(question.into()))
+            .run(Query::create_message(
+                question.into(),
+                self.options.randomize_case,
+            ))
             .await
     }
 
@@ -211,7 +229,10 @@ impl<'a> Resolver for &'a StubResolver {
         N: ToDname,
         Q: Into<Question<N>>,
     {
-        let message = Query::create_message(question.into());
+        let message = Query::create_message(
+            question.into(),
+            self.options().randomize_case,
+        );
         Box::pin(self.query_message(message))
     }
 }
@@ -243,6 +264,9 @@ pub struct Query<'a> {
     /// The index in the server list we currently trying.
     counter: ServerListCounter,
 
+    /// Have we already retried the current server after a BADCOOKIE?
+    cookie_retry: bool,
+
     /// The preferred error to return.
     ///
     /// Every time we finish a single query, we see if we can update this with
@@ -272,6 +296,7 @@ impl<'a> Query<'a> {
             preferred,
             attempt: 0,
             counter,
+            cookie_retry: false,
             error: Err(io::Error::new(
                 io::ErrorKind::TimedOut,
                 "all timed out",
@@ -286,7 +311,19 @@ impl<'a> Query<'a> {
         loop {
             match self.run_query(&mut message).await {
                 Ok(answer) => {
-                    if answer.header().rcode() == Rcode::FormErr
+                    if answer.opt_rcode() == OptRcode::BadCookie {
+                        // BADCOOKIE: We have learned the server cookie
+                        // from the response, so try again once. If that
+                        // fails, too, try a stream transport which doesn’t
+                        // need a valid server cookie.
+                        if !self.cookie_retry {
+                            self.cookie_retry = true;
+                            continue;
+                        } else if self.switch_to_stream() {
+                            continue;
+                        }
+                        self.update_error_servfail(answer);
+                    } else if answer.header().rcode() == Rcode::FormErr
                         && self.current_server().does_edns()
                     {
                         // FORMERR with EDNS: turn off EDNS and try again.
@@ -307,6 +344,16 @@ impl<'a> Query<'a> {
                         } else {
                             return Ok(answer);
                         }
+                    } else if !self.resolver.options().no_check_name
+                        && !answer.check_names()
+                    {
+                        // Names in the answer are not what they should be.
+                        // Other servers will hand out the same data, so we
+                        // give up right away.
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "invalid name in answer",
+                        ));
                     } else {
                         // I guess we have an answer ...
                         return Ok(answer);
@@ -320,14 +367,33 @@ impl<'a> Query<'a> {
         }
     }
 
-    fn create_message(question: Question<impl ToDname>) -> QueryMessage {
+    fn create_message(
+        question: Question<impl ToDname>,
+        randomize_case: bool,
+    ) -> QueryMessage {
         let mut message = MessageBuilder::from_target(
             StreamTarget::new(Default::default()).unwrap(),
         )
         .unwrap();
         message.header_mut().set_rd(true);
         let mut message = message.question();
-        message.push(question).unwrap();
+        if randomize_case {
+            let qname = question
+                .qname()
+                .to_dname::<Array<255>>()
+                .unwrap()
+                .to_random_case::<Array<255>>()
+                .unwrap();
+            message
+                .push(Question::new(
+                    qname,
+                    question.qtype(),
+                    question.qclass(),
+                ))
+                .unwrap();
+        } else {
+            message.push(question).unwrap();
+        }
         message.additional()
     }
 
@@ -335,9 +401,7 @@ impl<'a> Query<'a> {
         &mut self,
         message: &mut QueryMessage,
     ) -> Result<Answer, io::Error> {
-        let server = self.current_server();
-        server.prepare_message(message);
-        server.query(message).await
+        self.current_server().query(message).await
     }
 
     fn current_server(&self) -> &ServerInfo {
@@ -369,12 +433,14 @@ impl<'a> Query<'a> {
         }
         self.preferred = false;
         self.attempt = 0;
+        self.cookie_retry = false;
         self.counter =
             self.resolver.stream.counter(self.resolver.options().rotate);
         true
     }
 
     fn next_server(&mut self) -> bool {
+        self.cookie_retry = false;
         if self.counter.next() {
             return true;
         }
@@ -422,9 +488,104 @@ impl Answer {
         self.message.header().tc()
     }
 
+    /// Returns the extended rcode of the answer.
+    ///
+    /// If the answer doesn’t have an OPT record, this is the rcode of the
+    /// header.
+    pub fn opt_rcode(&self) -> OptRcode {
+        match self.message.opt() {
+            Some(opt) => opt.rcode(self.message.header()),
+            None => self.message.header().rcode().into(),
+        }
+    }
+
     pub fn into_message(self) -> Message<Bytes> {
         self.message
     }
+
+    /// Checks the syntax of the names in the answer section.
+    ///
+    /// Owner names of A and AAAA records as well as the names of hosts
+    /// referred to by CNAME records in answers to address queries and by
+    /// MX, NS, SOA, and SRV records need to be valid host names. So do the
+    /// names referred to by PTR records in answers to queries for names
+    /// below `in-addr.arpa` or `ip6.arpa` – other PTR records, such as
+    /// those used by DNS-SD, point to instance names that needn’t be host
+    /// names. The owners of SRV records need to be service names if their
+    /// first label starts with an underscore and the responsible person of
+    /// a SOA record needs to be a mailbox name.
+    ///
+    /// Returns `false` if any of these names is invalid. Records whose data
+    /// cannot be parsed are skipped.
+    fn check_names(&self) -> bool {
+        let address_query =
+            matches!(self.message.qtype(), Some(Rtype::A | Rtype::Aaaa));
+        let reverse_query = self
+            .message
+            .first_question()
+            .map_or(false, |question| is_reverse_name(question.qname()));
+        let answer = match self.message.answer() {
+            Ok(answer) => answer,
+            Err(_) => return true,
+        };
+        for record in answer.limit_to::<AllRecordData<_, ParsedDname<_>>>() {
+            let record = match record {
+                Ok(record) => record,
+                Err(_) => continue,
+            };
+            let res = match record.data() {
+                AllRecordData::A(_) | AllRecordData::Aaaa(_) => {
+                    record.owner().check_hostname()
+                }
+                AllRecordData::Cname(cname) if address_query => {
+                    cname.cname().check_hostname()
+                }
+                AllRecordData::Mx(mx) => mx.exchange().check_hostname(),
+                AllRecordData::Ns(ns) => ns.nsdname().check_hostname(),
+                AllRecordData::Ptr(ptr) if reverse_query => {
+                    ptr.ptrdname().check_hostname()
+                }
+                AllRecordData::Soa(soa) => soa
+                    .mname()
+                    .check_hostname()
+                    .and_then(|_| soa.rname().check_mailbox()),
+                AllRecordData::Srv(srv) => {
+                    let service = record
+                        .owner()
+                        .iter_labels()
+                        .next()
+                        .map_or(false, |label| {
+                            label.as_slice().starts_with(b"_")
+                        });
+                    if service {
+                        record
+                            .owner()
+                            .check_service_name()
+                            .and_then(|_| srv.target().check_hostname())
+                    } else {
+                        srv.target().check_hostname()
+                    }
+                }
+                _ => Ok(()),
+            };
+            if res.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns whether a name is below `in-addr.arpa` or `ip6.arpa`.
+fn is_reverse_name(name: &impl ToLabelIter) -> bool {
+    let mut labels = name.iter_labels().rev();
+
+    // The last label is always the root label.
+    let _ = labels.next();
+    labels.next().map_or(false, |label| label == b"arpa")
+        && labels
+            .next()
+            .map_or(false, |label| label == b"in-addr" || label == b"ip6")
 }
 
 impl From<Message<Bytes>> for Answer {
@@ -444,9 +605,47 @@ struct ServerInfo {
     ///
     /// We start out with assuming it does and unset it if we get a FORMERR.
     edns: Arc<AtomicBool>,
+
+    /// The secret for creating client cookies.
+    ///
+    /// If this is `None`, cookies are not used.
+    cookie_secret: Option<[u8; 16]>,
+
+    /// The last cookie received from the server.
+    ///
+    /// This cookie contains both the client cookie we sent and the server
+    /// cookie we need to send back. It is only valid as long as our client
+    /// cookie doesn’t change.
+    cookie: Arc<Mutex<Option<Cookie>>>,
+
+    /// Whether to check that answers preserve the case of the query name.
+    ///
+    /// We start out with the `randomize_case` option and unset it if the
+    /// server answers with the name in different case too often.
+    check_case: Arc<AtomicBool>,
+
+    /// The number of queries in a row that saw answers in the wrong case.
+    ///
+    /// This is reset whenever we receive an answer in the correct case.
+    case_mismatches: Arc<AtomicUsize>,
 }
 
 impl ServerInfo {
+    pub fn new(
+        conf: ServerConf,
+        cookie_secret: Option<[u8; 16]>,
+        check_case: bool,
+    ) -> Self {
+        ServerInfo {
+            conf,
+            edns: Arc::new(AtomicBool::new(true)),
+            cookie_secret,
+            cookie: Default::default(),
+            check_case: Arc::new(AtomicBool::new(check_case)),
+            case_mismatches: Default::default(),
+        }
+    }
+
     pub fn does_edns(&self) -> bool {
         self.edns.load(Ordering::Relaxed)
     }
@@ -455,40 +654,126 @@ impl ServerInfo {
         self.edns.store(false, Ordering::Relaxed);
     }
 
-    pub fn prepare_message(&self, query: &mut QueryMessage) {
+    pub fn does_check_case(&self) -> bool {
+        self.check_case.load(Ordering::Relaxed)
+    }
+
+    pub fn disable_check_case(&self) {
+        self.check_case.store(false, Ordering::Relaxed);
+    }
+
+    /// Records that a query received an answer in the wrong case.
+    ///
+    /// A single such answer may well be spoofed, so we only give up on
+    /// checking the case after it happened for a number of queries in a
+    /// row. Returns whether checking has been disabled.
+    fn note_case_mismatch(&self) -> bool {
+        let count = self.case_mismatches.fetch_add(1, Ordering::Relaxed) + 1;
+        if count >= CASE_MISMATCH_LIMIT {
+            self.disable_check_case();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Records that a query received an answer in the correct case.
+    fn note_case_match(&self) {
+        self.case_mismatches.store(0, Ordering::Relaxed);
+    }
+
+    /// Prepares the message for sending from the given client address.
+    ///
+    /// Returns the client cookie included in the message, if any.
+    pub fn prepare_message(
+        &self,
+        query: &mut QueryMessage,
+        client_ip: IpAddr,
+    ) -> Option<ClientCookie> {
         query.rewind();
-        if self.does_edns() {
-            query
-                .opt(|opt| {
-                    opt.set_udp_payload_size(self.conf.udp_payload_size);
-                    Ok(())
-                })
-                .unwrap();
+        if !self.does_edns() {
+            return None;
+        }
+        let cookie = self.cookie(client_ip);
+        query
+            .opt(|opt| {
+                opt.set_udp_payload_size(self.conf.udp_payload_size);
+                if let Some(cookie) = cookie {
+                    opt.cookie(cookie)?;
+                }
+                Ok(())
+            })
+            .unwrap();
+        cookie.map(|cookie| cookie.client())
+    }
+
+    /// Returns the cookie to send to the server, if cookies are enabled.
+    ///
+    /// The client cookie is derived from the secret and the client and
+    /// server addresses as suggested in RFC 7873, section 4.1. If we have
+    /// learned a server cookie for this client cookie, it is included.
+    fn cookie(&self, client_ip: IpAddr) -> Option<Cookie> {
+        let secret = self.cookie_secret.as_ref()?;
+        let mut hasher = SipHasher24::new_with_key(secret);
+        for ip in [client_ip, self.conf.addr.ip()] {
+            match ip {
+                IpAddr::V4(addr) => hasher.write(&addr.octets()),
+                IpAddr::V6(addr) => hasher.write(&addr.octets()),
+            }
+        }
+        let client = ClientCookie::new(hasher.finish().to_le_bytes());
+        match self.cookie.lock().ok().and_then(|cookie| *cookie) {
+            Some(cookie) if cookie.client() == client => Some(cookie),
+            _ => Some(client.into()),
         }
     }
 
+    /// Checks the cookie of an answer.
+    ///
+    /// Returns whether the answer should be accepted. An answer is rejected
+    /// if it contains a malformed cookie or a cookie with a client cookie
+    /// different from the one we sent. If the answer contains a server
+    /// cookie, it is remembered for later queries.
+    fn check_cookie(
+        &self,
+        answer: &Message<Bytes>,
+        client: Option<ClientCookie>,
+    ) -> bool {
+        let client = match client {
+            Some(client) => client,
+            None => return true,
+        };
+        let cookie = match answer.opt() {
+            Some(opt) => match opt.iter::<Cookie>().next() {
+                Some(Ok(cookie)) => cookie,
+                Some(Err(_)) => return false,
+                None => return true,
+            },
+            None => return true,
+        };
+        if cookie.client() != client {
+            return false;
+        }
+        if cookie.server().is_some() {
+            if let Ok(mut stored) = self.cookie.lock() {
+                *stored = Some(cookie)
+            }
+        }
+        true
+    }
+
     pub async fn query(
         &self,
-        query: &QueryMessage,
+        query: &mut QueryMessage,
     ) -> Result<Answer, io::Error> {
         let res = match self.conf.transport {
             Transport::Udp => {
-                timeout(
-                    self.conf.request_timeout,
-                    Self::udp_query(
-                        query,
-                        self.conf.addr,
-                        self.conf.recv_size,
-                    ),
-                )
-                .await
+                timeout(self.conf.request_timeout, self.udp_query(query))
+                    .await
             }
             Transport::Tcp => {
-                timeout(
-                    self.conf.request_timeout,
-                    Self::tcp_query(query, self.conf.addr),
-                )
-                .await
+                timeout(self.conf.request_timeout, self.tcp_query(query))
+                    .await
             }
         };
         match res {
@@ -502,10 +787,11 @@ impl ServerInfo {
     }
 
     pub async fn tcp_query(
-        query: &QueryMessage,
-        addr: SocketAddr,
+        &self,
+        query: &mut QueryMessage,
     ) -> Result<Answer, io::Error> {
-        let mut sock = TcpStream::connect(&addr).await?;
+        let mut sock = TcpStream::connect(&self.conf.addr).await?;
+        let client = self.prepare_message(query, sock.local_addr()?.ip());
         sock.write_all(query.as_target().as_stream_slice()).await?;
 
         // This loop can be infinite because we have a timeout on this whole
@@ -517,7 +803,9 @@ impl ServerInfo {
                 .read_to_end(&mut buf)
                 .await?;
             if let Ok(answer) = Message::from_octets(buf.into()) {
-                if answer.is_answer(&query.as_message()) {
+                if answer.is_answer(&query.as_message())
+                    && self.check_cookie(&answer, client)
+                {
                     return Ok(answer.into());
                 }
             // else try with the next message.
@@ -531,33 +819,59 @@ impl ServerInfo {
     }
 
     pub async fn udp_query(
-        query: &QueryMessage,
-        addr: SocketAddr,
-        recv_size: usize,
+        &self,
+        query: &mut QueryMessage,
     ) -> Result<Answer, io::Error> {
+        let addr = self.conf.addr;
         let sock = Self::udp_bind(addr.is_ipv4()).await?;
         sock.connect(addr).await?;
-        let sent = sock.send(query.as_target().as_dgram_slice()).await?;
-        if sent != query.as_target().as_dgram_slice().len() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "short UDP send",
-            ));
-        }
-        loop {
-            let mut buf = vec![0; recv_size]; // XXX use uninit'ed mem here.
-            let len = sock.recv(&mut buf).await?;
-            buf.truncate(len);
-
-            // We ignore garbage since there is a timer on this whole thing.
-            let answer = match Message::from_octets(buf.into()) {
-                Ok(answer) => answer,
-                Err(_) => continue,
-            };
-            if !answer.is_answer(&query.as_message()) {
-                continue;
+        let client = self.prepare_message(query, sock.local_addr()?.ip());
+        let mut case_mismatch = false;
+        'send: loop {
+            let check_case = self.does_check_case();
+            let sent = sock.send(query.as_target().as_dgram_slice()).await?;
+            if sent != query.as_target().as_dgram_slice().len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "short UDP send",
+                ));
+            }
+            loop {
+                // XXX use uninit'ed mem here.
+                let mut buf = vec![0; self.conf.recv_size];
+                let len = sock.recv(&mut buf).await?;
+                buf.truncate(len);
+
+                // We ignore garbage since there is a timer on this whole
+                // thing.
+                let answer = match Message::from_octets(buf.into()) {
+                    Ok(answer) => answer,
+                    Err(_) => continue,
+                };
+                if !answer.is_answer(&query.as_message()) {
+                    continue;
+                }
+                if !self.check_cookie(&answer, client) {
+                    continue;
+                }
+                if check_case {
+                    if !answer.is_answer_exact(&query.as_message()) {
+                        // Either the answer is spoofed or the server
+                        // doesn’t preserve the case of the query name. We
+                        // drop the answer and keep waiting. Only if this
+                        // keeps happening, we stop checking and ask again.
+                        if !case_mismatch {
+                            case_mismatch = true;
+                            if self.note_case_mismatch() {
+                                continue 'send;
+                            }
+                        }
+                        continue;
+                    }
+                    self.note_case_match();
+                }
+                return Ok(answer.into());
             }
-            return Ok(answer.into());
         }
     }
 
@@ -583,21 +897,6 @@ impl ServerInfo {
     }
 }
 
-impl From<ServerConf> for ServerInfo {
-    fn from(conf: ServerConf) -> Self {
-        ServerInfo {
-            conf,
-            edns: Arc::new(AtomicBool::new(true)),
-        }
-    }
-}
-
-impl<'a> From<&'a ServerConf> for ServerInfo {
-    fn from(conf: &'a ServerConf) -> Self {
-        conf.clone().into()
-    }
-}
-
 //------------ ServerList ----------------------------------------------------
 
 #[derive(Clone, Debug)]
@@ -617,7 +916,11 @@ struct ServerList {
 }
 
 impl ServerList {
-    pub fn from_conf<F>(conf: &ResolvConf, filter: F) -> Self
+    pub fn from_conf<F>(
+        conf: &ResolvConf,
+        cookie_secret: Option<[u8; 16]>,
+        filter: F,
+    ) -> Self
     where
         F: Fn(&ServerConf) -> bool,
     {
@@ -626,7 +929,13 @@ impl ServerList {
                 conf.servers
                     .iter()
                     .filter(|f| filter(f))
-                    .map(Into::into)
+                    .map(|f| {
+                        ServerInfo::new(
+                            f.clone(),
+                            cookie_secret,
+                            conf.options.randomize_case,
+                        )
+                    })
                     .collect()
             },
             start: Arc::new(AtomicUsize::new(0)),
@@ -773,3 +1082,165 @@ impl<'a> Iterator for SearchIter<'a> {
         }
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::name::Dname;
+    use crate::base::rdata::ComposeRecordData;
+    use crate::rdata::{Ptr, Srv};
+    use core::str::FromStr;
+    use std::time::Duration;
+
+    fn query_message() -> QueryMessage {
+        Query::create_message(
+            Question::new_in(
+                Dname::<Vec<u8>>::from_str("www.example.com").unwrap(),
+                Rtype::A,
+            ),
+            true,
+        )
+    }
+
+    /// Creates a reply to a query, optionally flipping the query name case.
+    fn reply(query: &[u8], flip_case: bool) -> Vec<u8> {
+        let mut reply = Vec::from(query);
+        reply[2] |= 0x80;
+        if flip_case {
+            let mut pos = 12;
+            while reply[pos] != 0 {
+                let end = pos + 1 + usize::from(reply[pos]);
+                for ch in &mut reply[pos + 1..end] {
+                    if ch.is_ascii_alphabetic() {
+                        *ch ^= 0x20;
+                    }
+                }
+                pos = end;
+            }
+        }
+        reply
+    }
+
+    async fn respond(server: &UdpSocket, flip_case: &[bool]) {
+        let mut buf = vec![0; 512];
+        let (len, addr) = server.recv_from(&mut buf).await.unwrap();
+        for &flip_case in flip_case {
+            server
+                .send_to(&reply(&buf[..len], flip_case), addr)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn spoofed_case_keeps_checking() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let info = ServerInfo::new(
+            ServerConf::new(server.local_addr().unwrap(), Transport::Udp),
+            None,
+            true,
+        );
+        let mut query = query_message();
+        let (answer, _) = tokio::join!(
+            info.query(&mut query),
+            respond(&server, &[true, false])
+        );
+        assert!(answer.unwrap().is_answer_exact(&query.as_message()));
+        assert!(info.does_check_case());
+    }
+
+    #[tokio::test]
+    async fn repeated_case_mismatch_disables_checking() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut conf =
+            ServerConf::new(server.local_addr().unwrap(), Transport::Udp);
+        conf.request_timeout = Duration::from_millis(200);
+        let info = ServerInfo::new(conf, None, true);
+        for _ in 1..CASE_MISMATCH_LIMIT {
+            let mut query = query_message();
+            let (answer, _) = tokio::join!(
+                info.query(&mut query),
+                respond(&server, &[true])
+            );
+            assert!(answer.is_err());
+            assert!(info.does_check_case());
+        }
+        let mut query = query_message();
+        let (answer, _, _) = tokio::join!(
+            info.query(&mut query),
+            respond(&server, &[true]),
+            respond(&server, &[true]),
+        );
+        assert!(answer.is_ok());
+        assert!(!info.does_check_case());
+    }
+
+    fn name(s: &str) -> Dname<Vec<u8>> {
+        Dname::from_str(s).unwrap()
+    }
+
+    /// Creates an answer with a single record.
+    fn answer_with(
+        qname: &str,
+        qtype: Rtype,
+        owner: &str,
+        data: impl ComposeRecordData,
+    ) -> Answer {
+        let mut msg = MessageBuilder::new_bytes().question();
+        msg.push((name(qname), qtype)).unwrap();
+        let mut msg = msg.answer();
+        msg.push((name(owner), 3600, data)).unwrap();
+        msg.into_message().into()
+    }
+
+    #[test]
+    fn check_answer_names() {
+        // PTR targets are host names only for reverse lookups.
+        assert!(!answer_with(
+            "1.2.0.192.in-addr.arpa",
+            Rtype::Ptr,
+            "1.2.0.192.in-addr.arpa",
+            Ptr::new(name("host_1.example.com")),
+        )
+        .check_names());
+        assert!(answer_with(
+            "1.2.0.192.in-addr.arpa",
+            Rtype::Ptr,
+            "1.2.0.192.in-addr.arpa",
+            Ptr::new(name("host-1.example.com")),
+        )
+        .check_names());
+        assert!(answer_with(
+            "_ipp._tcp.example.com",
+            Rtype::Ptr,
+            "_ipp._tcp.example.com",
+            Ptr::new(name("My\\032Printer._ipp._tcp.example.com")),
+        )
+        .check_names());
+
+        // SRV owners are service names only if they look like one.
+        assert!(answer_with(
+            "My\\032Printer._ipp._tcp.example.com",
+            Rtype::Srv,
+            "My\\032Printer._ipp._tcp.example.com",
+            Srv::new(0, 0, 631, name("printer.example.com")),
+        )
+        .check_names());
+        assert!(!answer_with(
+            "My\\032Printer._ipp._tcp.example.com",
+            Rtype::Srv,
+            "My\\032Printer._ipp._tcp.example.com",
+            Srv::new(0, 0, 631, name("my_printer.example.com")),
+        )
+        .check_names());
+        assert!(!answer_with(
+            "_ipp.www_1.example.com",
+            Rtype::Srv,
+            "_ipp.www_1.example.com",
+            Srv::new(0, 0, 631, name("printer.example.com")),
+        )
+        .check_names());
+    }
+}
@@ -2,14 +2,20 @@
 
 use super::key::SigningKey;
 use crate::base::cmp::CanonicalOrd;
-use crate::base::iana::{Class, Rtype};
+use crate::base::iana::{Class, Rtype, ZonemdAlg, ZonemdScheme};
 use crate::base::name::ToDname;
 use crate::base::rdata::{ComposeRecordData, RecordData};
 use crate::base::record::Record;
 use crate::base::serial::Serial;
 use crate::rdata::rfc4034::{ProtoRrsig, RtypeBitmap};
-use crate::rdata::{Dnskey, Ds, Nsec, Rrsig};
+#[cfg(feature = "ring")]
+use crate::rdata::Soa;
+use crate::rdata::{Dnskey, Ds, Nsec, Rrsig, Zonemd};
 use octseq::builder::{EmptyBuilder, FromBuilder, OctetsBuilder, Truncate};
+#[cfg(feature = "ring")]
+use octseq::parse::Parser;
+#[cfg(feature = "ring")]
+use ring::digest;
 use std::iter::FromIterator;
 use std::vec::Vec;
 use std::{fmt, io, slice};
@@ -132,37 +138,103 @@ impl<N, D> SortedRecords<N, D> {
                     }
                 }
 
-                // Create the signature.
-                buf.clear();
-                let rrsig = ProtoRrsig::new(
-                    rrset.rtype(),
-                    key.algorithm()?,
-                    name.owner().rrsig_label_count(),
-                    rrset.ttl(),
-                    expiration,
-                    inception,
-                    key.key_tag()?,
-                    apex.owner().clone(),
-                );
-                rrsig.compose_canonical(&mut buf).unwrap();
-                for record in rrset.iter() {
-                    record.compose_canonical(&mut buf).unwrap();
-                }
+                res.push(Self::sign_rrset(
+                    &rrset, &name, apex, expiration, inception, &key,
+                    &mut buf,
+                )?);
+            }
+        }
+        Ok(res)
+    }
 
-                // Create and push the RRSIG record.
-                res.push(Record::new(
-                    name.owner().clone(),
-                    name.class(),
-                    rrset.ttl(),
-                    rrsig
-                        .into_rrsig(key.sign(&buf)?.into())
-                        .expect("long signature"),
-                ));
+    /// Signs the RRset of the given record type at the apex.
+    ///
+    /// This is useful for signing an RRset that has been changed after the
+    /// rest of the zone was signed. In particular, the apex ZONEMD RRset
+    /// can only be completed once all other signatures are present. See
+    /// [`update_zonemd`][Self::update_zonemd] for details.
+    #[allow(clippy::type_complexity)]
+    pub fn sign_apex_rrset<Octets, Key, ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+        rtype: Rtype,
+        expiration: Serial,
+        inception: Serial,
+        key: Key,
+    ) -> Result<Vec<Record<N, Rrsig<Octets, ApexName>>>, Key::Error>
+    where
+        N: ToDname + Clone,
+        D: RecordData + ComposeRecordData,
+        Key: SigningKey,
+        Octets: From<Key::Signature> + AsRef<[u8]>,
+        ApexName: ToDname + Clone,
+    {
+        let mut res = Vec::new();
+        let mut buf = Vec::new();
+        let family = match self
+            .families()
+            .find(|family| family.family_name() == *apex)
+        {
+            Some(family) => family,
+            None => return Ok(res),
+        };
+        let name = family.family_name().cloned();
+        for rrset in family.rrsets() {
+            if rrset.rtype() == rtype {
+                res.push(Self::sign_rrset(
+                    &rrset, &name, apex, expiration, inception, &key,
+                    &mut buf,
+                )?);
             }
         }
         Ok(res)
     }
 
+    /// Creates the signature record for a single RRset.
+    fn sign_rrset<Octets, Key, ApexName>(
+        rrset: &Rrset<N, D>,
+        name: &FamilyName<N>,
+        apex: &FamilyName<ApexName>,
+        expiration: Serial,
+        inception: Serial,
+        key: &Key,
+        buf: &mut Vec<u8>,
+    ) -> Result<Record<N, Rrsig<Octets, ApexName>>, Key::Error>
+    where
+        N: ToDname + Clone,
+        D: RecordData + ComposeRecordData,
+        Key: SigningKey,
+        Octets: From<Key::Signature> + AsRef<[u8]>,
+        ApexName: ToDname + Clone,
+    {
+        // Create the signature.
+        buf.clear();
+        let rrsig = ProtoRrsig::new(
+            rrset.rtype(),
+            key.algorithm()?,
+            name.owner().rrsig_label_count(),
+            rrset.ttl(),
+            expiration,
+            inception,
+            key.key_tag()?,
+            apex.owner().clone(),
+        );
+        rrsig.compose_canonical(buf).unwrap();
+        for record in rrset.iter() {
+            record.compose_canonical(buf).unwrap();
+        }
+
+        // Create the RRSIG record.
+        Ok(Record::new(
+            name.owner().clone(),
+            name.class(),
+            rrset.ttl(),
+            rrsig
+                .into_rrsig(key.sign(buf.as_slice())?.into())
+                .expect("long signature"),
+        ))
+    }
+
     pub fn nsecs<Octets, ApexName>(
         &self,
         apex: &FamilyName<ApexName>,
@@ -244,6 +316,259 @@ impl<N, D> SortedRecords<N, D> {
         res
     }
 
+    /// Computes the digest of the zone for a ZONEMD record.
+    ///
+    /// The digest is calculated as described in section 3 of [RFC 8976]
+    /// over all records of the zone given by `apex` in canonical order,
+    /// excluding the ZONEMD records at the apex and the RRSIG records
+    /// covering them. Currently, only the SIMPLE scheme with the SHA-384
+    /// and SHA-512 hash algorithms is supported.
+    ///
+    /// [RFC 8976]: https://tools.ietf.org/html/rfc8976
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn zonemd_digest<ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+        scheme: ZonemdScheme,
+        algorithm: ZonemdAlg,
+    ) -> Result<digest::Digest, ZonemdError>
+    where
+        N: ToDname,
+        D: RecordData + ComposeRecordData + CanonicalOrd,
+        ApexName: ToDname,
+    {
+        if scheme != ZonemdScheme::Simple {
+            return Err(ZonemdError::UnsupportedScheme);
+        }
+        let mut ctx = digest::Context::new(match algorithm {
+            ZonemdAlg::Sha384 => &digest::SHA384,
+            ZonemdAlg::Sha512 => &digest::SHA512,
+            _ => return Err(ZonemdError::UnsupportedAlgorithm),
+        });
+        let mut buf = Vec::new();
+        let mut prev: Option<&Record<N, D>> = None;
+
+        let mut families = self.families();
+        families.skip_before(apex);
+        for family in families {
+            // Since the records are ordered, all records of the zone
+            // follow the apex.
+            if !family.is_in_zone(apex) {
+                break;
+            }
+            let at_apex = family.family_name() == *apex;
+            for record in family.records() {
+                if at_apex && is_zonemd(record, &mut buf) {
+                    continue;
+                }
+
+                // Duplicate records must only be included once.
+                if let Some(prev) = prev {
+                    if prev.canonical_cmp(record).is_eq() {
+                        continue;
+                    }
+                }
+                prev = Some(record);
+
+                buf.clear();
+                record.compose_canonical(&mut buf).unwrap();
+                ctx.update(&buf);
+            }
+        }
+        Ok(ctx.finish())
+    }
+
+    /// Fills in the digests of the ZONEMD records at the apex.
+    ///
+    /// All ZONEMD records at the apex given by `apex` are replaced by
+    /// records with the same scheme and hash algorithm but with the serial
+    /// of the apex SOA record and the digest calculated over the zone via
+    /// [`zonemd_digest`][Self::zonemd_digest]. Any RRSIG records covering
+    /// the ZONEMD RRset are removed.
+    ///
+    /// When signing a zone, a placeholder ZONEMD record should be added via
+    /// [`FamilyName::zonemd_placeholder`] before creating the NSEC records
+    /// so they include the record type. After the zone has been signed and
+    /// the signatures added, this method fills in the digest. Finally, the
+    /// ZONEMD RRset needs to be signed again via
+    /// [`sign_apex_rrset`][Self::sign_apex_rrset].
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn update_zonemd<Octets, ApexName>(
+        &mut self,
+        apex: &FamilyName<ApexName>,
+    ) -> Result<(), ZonemdError>
+    where
+        N: ToDname + Clone,
+        D: RecordData
+            + ComposeRecordData
+            + CanonicalOrd
+            + From<Zonemd<Octets>>,
+        Octets: From<Vec<u8>> + AsRef<[u8]>,
+        ApexName: ToDname,
+    {
+        let serial = self.apex_serial(apex)?;
+        let mut buf = Vec::new();
+        let mut zonemds = Vec::new();
+        for record in self.apex_records(apex) {
+            if record.rtype() == Rtype::Zonemd {
+                let zonemd = parse_zonemd(record, &mut buf)
+                    .ok_or(ZonemdError::MissingZonemd)?;
+                zonemds.push((
+                    record.owner().clone(),
+                    record.class(),
+                    record.ttl(),
+                    zonemd.scheme(),
+                    zonemd.algorithm(),
+                ));
+            }
+        }
+        if zonemds.is_empty() {
+            return Err(ZonemdError::MissingZonemd);
+        }
+
+        let mut records = Vec::new();
+        for (owner, class, ttl, scheme, algorithm) in zonemds {
+            let digest = self.zonemd_digest(apex, scheme, algorithm)?;
+            records.push(Record::new(
+                owner,
+                class,
+                ttl,
+                Zonemd::new(
+                    serial,
+                    scheme,
+                    algorithm,
+                    Octets::from(digest.as_ref().to_vec()),
+                )
+                .expect("long digest")
+                .into(),
+            ));
+        }
+        self.records.retain(|record| {
+            !(apex == record && is_zonemd(record, &mut buf))
+        });
+        self.extend(records);
+        Ok(())
+    }
+
+    /// Verifies the ZONEMD records at the apex.
+    ///
+    /// The verification follows section 4 of [RFC 8976]: The zone must
+    /// have at least one ZONEMD record at the apex given by `apex` with the
+    /// serial of the apex SOA record and a supported scheme and hash
+    /// algorithm. There must not be more than one such record with the
+    /// same scheme and hash algorithm. The digest of at least one of these
+    /// records must match the digest calculated over the zone.
+    ///
+    /// The method does not check any DNSSEC signatures.
+    ///
+    /// [RFC 8976]: https://tools.ietf.org/html/rfc8976
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn verify_zonemd<ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+    ) -> Result<(), ZonemdError>
+    where
+        N: ToDname,
+        D: RecordData + ComposeRecordData + CanonicalOrd,
+        ApexName: ToDname,
+    {
+        let serial = self.apex_serial(apex)?;
+        let mut buf = Vec::new();
+        let mut zonemds = Vec::new();
+        for record in self.apex_records(apex) {
+            if record.rtype() == Rtype::Zonemd {
+                if let Some(zonemd) = parse_zonemd(record, &mut buf) {
+                    zonemds.push(zonemd)
+                }
+            }
+        }
+
+        // Keep the most relevant error: anything beats an unsupported
+        // scheme or algorithm.
+        let mut err = ZonemdError::MissingZonemd;
+        let mut fail = |new: ZonemdError| {
+            if matches!(
+                err,
+                ZonemdError::MissingZonemd
+                    | ZonemdError::UnsupportedScheme
+                    | ZonemdError::UnsupportedAlgorithm
+            ) {
+                err = new
+            }
+        };
+        for (idx, zonemd) in zonemds.iter().enumerate() {
+            if zonemd.serial() != serial {
+                fail(ZonemdError::SerialMismatch);
+                continue;
+            }
+            let duplicate = zonemds.iter().enumerate().any(|(i, other)| {
+                i != idx
+                    && other.scheme() == zonemd.scheme()
+                    && other.algorithm() == zonemd.algorithm()
+            });
+            if duplicate {
+                fail(ZonemdError::Duplicate);
+                continue;
+            }
+            match self.zonemd_digest(
+                apex,
+                zonemd.scheme(),
+                zonemd.algorithm(),
+            ) {
+                Ok(digest) => {
+                    if digest.as_ref() == zonemd.digest().as_slice() {
+                        return Ok(());
+                    }
+                    fail(ZonemdError::DigestMismatch)
+                }
+                Err(new) => fail(new),
+            }
+        }
+        Err(err)
+    }
+
+    /// Returns the records at the apex.
+    #[cfg(feature = "ring")]
+    fn apex_records<ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+    ) -> slice::Iter<Record<N, D>>
+    where
+        N: ToDname,
+        D: RecordData,
+        ApexName: ToDname,
+    {
+        match self.families().find(|family| family.family_name() == *apex) {
+            Some(family) => family.records(),
+            None => [].iter(),
+        }
+    }
+
+    /// Returns the serial of the SOA record at the apex.
+    #[cfg(feature = "ring")]
+    fn apex_serial<ApexName>(
+        &self,
+        apex: &FamilyName<ApexName>,
+    ) -> Result<Serial, ZonemdError>
+    where
+        N: ToDname,
+        D: RecordData + ComposeRecordData,
+        ApexName: ToDname,
+    {
+        let soa = self
+            .apex_records(apex)
+            .find(|record| record.rtype() == Rtype::Soa)
+            .ok_or(ZonemdError::MissingSoa)?;
+        let mut buf = Vec::new();
+        soa.data().compose_canonical_rdata(&mut buf).unwrap();
+        Soa::parse(&mut Parser::from_ref(buf.as_slice()))
+            .map(|soa| soa.serial())
+            .map_err(|_| ZonemdError::MissingSoa)
+    }
+
     pub fn write<W>(&self, target: &mut W) -> Result<(), io::Error>
     where
         N: fmt::Display,
@@ -404,6 +729,37 @@ impl<N> FamilyName<N> {
     }
 }
 
+impl<N> FamilyName<N> {
+    /// Creates a placeholder ZONEMD record.
+    ///
+    /// The digest of the record is all zeros but has the correct length
+    /// for the hash algorithm. For unknown algorithms, the minimum digest
+    /// length of 12 octets is used. The digest can be filled in later via
+    /// [`SortedRecords::update_zonemd`].
+    pub fn zonemd_placeholder<Octets>(
+        &self,
+        ttl: u32,
+        serial: Serial,
+        scheme: ZonemdScheme,
+        algorithm: ZonemdAlg,
+    ) -> Record<N, Zonemd<Octets>>
+    where
+        N: Clone,
+        Octets: From<Vec<u8>> + AsRef<[u8]>,
+    {
+        let len = match algorithm {
+            ZonemdAlg::Sha384 => 48,
+            ZonemdAlg::Sha512 => 64,
+            _ => 12,
+        };
+        self.clone().into_record(
+            ttl,
+            Zonemd::new(serial, scheme, algorithm, vec![0; len].into())
+                .expect("long digest"),
+        )
+    }
+}
+
 impl<'a, N: Clone> FamilyName<&'a N> {
     pub fn cloned(&self) -> FamilyName<N> {
         FamilyName {
@@ -603,3 +959,323 @@ where
         Some(Rrset::new(res))
     }
 }
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns whether a record is a ZONEMD record or an RRSIG covering one.
+#[cfg(feature = "ring")]
+fn is_zonemd<N, D>(record: &Record<N, D>, buf: &mut Vec<u8>) -> bool
+where
+    D: RecordData + ComposeRecordData,
+{
+    match record.rtype() {
+        Rtype::Zonemd => true,
+        Rtype::Rrsig => {
+            // The type covered is the first field of the RRSIG record data.
+            buf.clear();
+            record.data().compose_canonical_rdata(buf).unwrap();
+            buf.get(..2) == Some(&Rtype::Zonemd.to_int().to_be_bytes()[..])
+        }
+        _ => false,
+    }
+}
+
+/// Parses the record data of a ZONEMD record.
+#[cfg(feature = "ring")]
+fn parse_zonemd<N, D>(
+    record: &Record<N, D>,
+    buf: &mut Vec<u8>,
+) -> Option<Zonemd<Vec<u8>>>
+where
+    D: ComposeRecordData,
+{
+    buf.clear();
+    record.data().compose_canonical_rdata(buf).unwrap();
+    Zonemd::parse(&mut Parser::from_ref(buf.as_slice()))
+        .ok()
+        .map(|zonemd| {
+            zonemd.flatten_into().expect("flattening into Vec failed")
+        })
+}
+
+//============ Error Types ===================================================
+
+//------------ ZonemdError ---------------------------------------------------
+
+/// Computing or verifying a zone digest failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZonemdError {
+    /// There is no SOA record at the apex.
+    MissingSoa,
+
+    /// There is no ZONEMD record at the apex.
+    MissingZonemd,
+
+    /// The scheme is not supported.
+    UnsupportedScheme,
+
+    /// The hash algorithm is not supported.
+    UnsupportedAlgorithm,
+
+    /// The serial of the ZONEMD record differs from that of the SOA record.
+    SerialMismatch,
+
+    /// There are multiple ZONEMD records with the same scheme and algorithm.
+    Duplicate,
+
+    /// The digest does not match the zone.
+    DigestMismatch,
+}
+
+impl fmt::Display for ZonemdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ZonemdError::MissingSoa => "missing apex SOA record",
+            ZonemdError::MissingZonemd => "missing apex ZONEMD record",
+            ZonemdError::UnsupportedScheme => "unsupported ZONEMD scheme",
+            ZonemdError::UnsupportedAlgorithm => {
+                "unsupported ZONEMD hash algorithm"
+            }
+            ZonemdError::SerialMismatch => "ZONEMD serial mismatch",
+            ZonemdError::Duplicate => "duplicate ZONEMD records",
+            ZonemdError::DigestMismatch => "ZONEMD digest mismatch",
+        })
+    }
+}
+
+impl std::error::Error for ZonemdError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "ring")]
+mod test {
+    use super::*;
+    use crate::base::name::Dname;
+    use crate::rdata::{Aaaa, Ns, Txt, ZoneRecordData, A};
+    use crate::utils::base16;
+    use core::str::FromStr;
+
+    type Name = Dname<Vec<u8>>;
+    type Data = ZoneRecordData<Vec<u8>, Name>;
+
+    fn name(s: &str) -> Name {
+        Name::from_str(s).unwrap()
+    }
+
+    fn zone(serial: u32) -> SortedRecords<Name, Data> {
+        let mut records = SortedRecords::new();
+        let apex = name("example.");
+        records.extend([
+            Record::new(
+                apex.clone(),
+                Class::In,
+                86400,
+                Soa::new(
+                    name("ns1.example."),
+                    name("admin.example."),
+                    Serial(serial),
+                    1800,
+                    900,
+                    604800,
+                    86400,
+                )
+                .into(),
+            ),
+            Record::new(
+                apex,
+                Class::In,
+                86400,
+                Ns::new(name("ns1.example.")).into(),
+            ),
+            Record::new(
+                name("ns1.example."),
+                Class::In,
+                3600,
+                A::from_octets(203, 0, 113, 63).into(),
+            ),
+        ]);
+        records
+    }
+
+    #[test]
+    fn zonemd() {
+        let apex = FamilyName::new(name("example."), Class::In);
+        let mut records = zone(2018031900);
+        assert_eq!(
+            records.verify_zonemd(&apex),
+            Err(ZonemdError::MissingZonemd)
+        );
+
+        let placeholder = apex.zonemd_placeholder::<Vec<u8>>(
+            86400,
+            Serial(0),
+            ZonemdScheme::Simple,
+            ZonemdAlg::Sha384,
+        );
+        assert_eq!(placeholder.data().digest().len(), 48);
+        records.extend([Record::new(
+            placeholder.owner().clone(),
+            placeholder.class(),
+            placeholder.ttl(),
+            placeholder.into_data().into(),
+        )]);
+        assert_eq!(
+            records.verify_zonemd(&apex),
+            Err(ZonemdError::SerialMismatch)
+        );
+
+        records.update_zonemd::<Vec<u8>, _>(&apex).unwrap();
+        assert_eq!(records.verify_zonemd(&apex), Ok(()));
+
+        // Records with unsupported algorithms are ignored.
+        records.extend([Record::new(
+            name("example."),
+            Class::In,
+            86400,
+            Zonemd::new(
+                Serial(2018031900),
+                ZonemdScheme::Simple,
+                ZonemdAlg::Int(240),
+                vec![0; 12],
+            )
+            .unwrap()
+            .into(),
+        )]);
+        assert_eq!(records.verify_zonemd(&apex), Ok(()));
+
+        // The digest doesn’t depend on the ZONEMD records themselves ...
+        let digest = records
+            .zonemd_digest(&apex, ZonemdScheme::Simple, ZonemdAlg::Sha384)
+            .unwrap();
+        let plain = zone(2018031900)
+            .zonemd_digest(&apex, ZonemdScheme::Simple, ZonemdAlg::Sha384)
+            .unwrap();
+        assert_eq!(digest.as_ref(), plain.as_ref());
+
+        // ... but on everything else.
+        records.extend([Record::new(
+            name("ns2.example."),
+            Class::In,
+            3600,
+            A::from_octets(203, 0, 113, 64).into(),
+        )]);
+        assert_eq!(
+            records.verify_zonemd(&apex),
+            Err(ZonemdError::DigestMismatch)
+        );
+    }
+
+    /// Returns the zone from appendix A.1 of RFC 8976 without the ZONEMD
+    /// record.
+    fn rfc8976_zone() -> SortedRecords<Name, Data> {
+        let mut records = zone(2018031900);
+        records.extend([
+            Record::new(
+                name("example."),
+                Class::In,
+                86400,
+                Ns::new(name("ns2.example.")).into(),
+            ),
+            Record::new(
+                name("ns2.example."),
+                Class::In,
+                3600,
+                Aaaa::new("2001:db8::63".parse().unwrap()).into(),
+            ),
+        ]);
+        records
+    }
+
+    fn rfc8976_zonemd(
+        scheme: u8,
+        algorithm: u8,
+        digest: &str,
+    ) -> Record<Name, Data> {
+        Record::new(
+            name("example."),
+            Class::In,
+            86400,
+            Zonemd::new(
+                Serial(2018031900),
+                ZonemdScheme::from_int(scheme),
+                ZonemdAlg::from_int(algorithm),
+                base16::decode::<Vec<u8>>(digest).unwrap(),
+            )
+            .unwrap()
+            .into(),
+        )
+    }
+
+    #[test]
+    fn zonemd_rfc8976_simple() {
+        // Appendix A.1 of RFC 8976.
+        const DIGEST: &str = "c68090d90a7aed716bc459f9340e3d7c\
+                              1370d4d24b7e2fc3a1ddc0b9a87153b9\
+                              a9713b3c9ae5cc27777f98b8e730044c";
+        let apex = FamilyName::new(name("example."), Class::In);
+        let mut records = rfc8976_zone();
+        assert_eq!(
+            records
+                .zonemd_digest(&apex, ZonemdScheme::Simple, ZonemdAlg::Sha384)
+                .unwrap()
+                .as_ref(),
+            base16::decode::<Vec<u8>>(DIGEST).unwrap().as_slice()
+        );
+        records.extend([rfc8976_zonemd(1, 1, DIGEST)]);
+        assert_eq!(records.verify_zonemd(&apex), Ok(()));
+    }
+
+    #[test]
+    fn zonemd_rfc8976_multiple_digests() {
+        // Appendix A.3 of RFC 8976.
+        const SHA384: &str = "62e6cf51b02e54b9b5f967d547ce4313\
+                              6792901f9f88e637493daaf401c92c27\
+                              9dd10f0edb1c56f8080211f8480ee306";
+        const SHA512: &str = "08cfa1115c7b948c4163a901270395ea\
+                              226a930cd2cbcf2fa9a5e6eb85f37c8a\
+                              4e114d884e66f176eab121cb02db7d65\
+                              2e0cc4827e7a3204f166b47e5613fd27";
+        let apex = FamilyName::new(name("example."), Class::In);
+        let mut records = rfc8976_zone();
+        records.extend([
+            Record::new(
+                name("ns2.example."),
+                Class::In,
+                86400,
+                Txt::build_from_slice(b"This example has multiple digests")
+                    .unwrap()
+                    .into(),
+            ),
+            // A duplicate, albeit in different case, is included once.
+            Record::new(
+                name("NS2.EXAMPLE."),
+                Class::In,
+                3600,
+                Aaaa::new("2001:db8::63".parse().unwrap()).into(),
+            ),
+        ]);
+        for (algorithm, digest) in
+            [(ZonemdAlg::Sha384, SHA384), (ZonemdAlg::Sha512, SHA512)]
+        {
+            assert_eq!(
+                records
+                    .zonemd_digest(&apex, ZonemdScheme::Simple, algorithm)
+                    .unwrap()
+                    .as_ref(),
+                base16::decode::<Vec<u8>>(digest).unwrap().as_slice()
+            );
+        }
+        records.extend([
+            rfc8976_zonemd(1, 1, SHA384),
+            rfc8976_zonemd(1, 2, SHA512),
+            rfc8976_zonemd(1, 240, "e2d523f654b9422a96c5a8f44607bbee"),
+            rfc8976_zonemd(
+                241,
+                1,
+                "e1846540e33a9e4189792d18d5d131f605fc283e",
+            ),
+        ]);
+        assert_eq!(records.verify_zonemd(&apex), Ok(()));
+    }
+}
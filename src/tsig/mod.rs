@@ -261,6 +261,32 @@ impl Key {
         self.signing_len
     }
 
+    /// Returns an upper bound for the length of a TSIG record.
+    ///
+    /// This is the length of a record generated by this key without any
+    /// other data, i.e., for every message except error responses for bad
+    /// time which carry six additional octets. The MAC is always
+    /// [`signing_len`][Self::signing_len] octets long. The key name is
+    /// assumed to be uncompressed, so the record can be shorter if the
+    /// message builder compresses names.
+    ///
+    /// The value can be used to reserve space for the record via
+    /// [`MessageBuilder::set_reserved_len`] or to take it into account
+    /// when padding a message.
+    ///
+    /// [`MessageBuilder::set_reserved_len`]: crate::base::MessageBuilder::set_reserved_len
+    pub fn compose_len(&self) -> usize {
+        // Owner name, type, class, TTL, and record data length, followed by
+        // the record data: algorithm name, time signed, fudge, MAC size,
+        // MAC, original ID, error, and other length.
+        usize::from(self.name.compose_len())
+            + 10
+            + usize::from(self.algorithm().to_dname().compose_len())
+            + 10
+            + self.signing_len
+            + 6
+    }
+
     /// Checks whether the key in the record is this key.
     fn check_tsig<Octs: Octets>(
         &self,
@@ -1402,7 +1428,7 @@ impl Variables {
             Some(ref time) => time.as_ref(),
             None => b"",
         };
-        builder.push((
+        builder.push_reserved((
             key.name.clone(),
             Class::Any,
             0,
@@ -1656,7 +1682,7 @@ impl<K: AsRef<Key>> ServerError<K> {
                     MessageTsig::from_message(msg)
                         .expect("missing or malformed TSIG record")
                 };
-                builder.push((
+                builder.push_reserved((
                     tsig.owner(),
                     tsig.class(),
                     tsig.ttl(),
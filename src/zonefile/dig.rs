@@ -0,0 +1,691 @@
+//! Reading DNS messages in the style of `dig`.
+//!
+//! The `dig` tool presents DNS messages in a text format consisting of
+//! comment lines describing the header and the EDNS OPT record, followed by
+//! the question section and the three record sections with records in zone
+//! file presentation format. This format is produced by
+//! [`Message::display_dig`].
+//!
+//! This module provides the function [`scan_message`] that reads text in
+//! this format and produces the wire-format message it describes. This is
+//! primarily useful for tests where it allows keeping the messages used in
+//! test cases in a readable form.
+//!
+//! The records of the answer, authority, and additional sections are read
+//! via the [zonefile scanner][super::inplace] and thus can use all record
+//! types supported by [`ZoneRecordData`][crate::rdata::ZoneRecordData].
+//! Relative domain names are taken as relative to the root. If the TTL of
+//! records is missing, as happens when TTLs are hidden in the output, zero
+//! is used instead.
+//!
+//! The record counts in the header are ignored. Instead, the counts are
+//! determined from the actual content of the sections.
+//!
+//! The following is an example of the format:
+//!
+//! ```text
+//! ;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 4711
+//! ;; flags: qr rd ra; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 1
+//!
+//! ;; OPT PSEUDOSECTION:
+//! ; EDNS: version: 0, flags: do; udp: 1232
+//!
+//! ;; QUESTION SECTION:
+//! ;example.com.            IN  A
+//!
+//! ;; ANSWER SECTION:
+//! example.com.    3600    IN  A   192.0.2.1
+//! ```
+
+use super::inplace::{self, Entry, Zonefile};
+use crate::base::header::Header;
+use crate::base::iana::{
+    Class, ExtendedErrorCode, Opcode, OptRcode, OptionCode, Rtype,
+};
+use crate::base::message::Message;
+use crate::base::message_builder::{MessageBuilder, PushError};
+use crate::base::name::Dname;
+use crate::base::opt::{ClientSubnet, ComposeOptData, UnknownOptData};
+use crate::base::question::Question;
+use crate::utils::base16;
+use bytes::Bytes;
+use core::fmt;
+use core::str::FromStr;
+use octseq::builder::infallible;
+use std::net::IpAddr;
+use std::string::String;
+use std::vec::Vec;
+
+//------------ scan_message --------------------------------------------------
+
+/// Scans a message in the style of `dig` from its text representation.
+///
+/// See the [module documentation][self] for details on the format.
+pub fn scan_message(text: &str) -> Result<Message<Vec<u8>>, Error> {
+    let mut scanner = DigScanner::default();
+    for (idx, line) in text.lines().enumerate() {
+        scanner
+            .scan_line(idx, line)
+            .map_err(|msg| Error::syntax(idx + 1, msg))?;
+    }
+    scanner.into_message()
+}
+
+//------------ DigScanner ----------------------------------------------------
+
+/// The state of scanning a message.
+#[derive(Default)]
+struct DigScanner {
+    /// The header of the message.
+    header: Header,
+
+    /// The full response code if given.
+    ///
+    /// The lower four bits will end up in the header. The upper eight bits
+    /// only if there is an OPT record.
+    rcode: Option<OptRcode>,
+
+    /// Which part of the text are we currently in?
+    state: State,
+
+    /// The questions.
+    questions: Vec<Question<Dname<Vec<u8>>>>,
+
+    /// The text of the three record sections.
+    sections: [SectionText; 3],
+
+    /// The OPT record if there was an OPT pseudo-section.
+    opt: Option<OptValues>,
+}
+
+impl DigScanner {
+    /// Scans a single line of text.
+    ///
+    /// The line index `idx` is zero-based.
+    fn scan_line(
+        &mut self,
+        idx: usize,
+        line: &str,
+    ) -> Result<(), &'static str> {
+        let trimmed = line.trim();
+
+        // Double semicolon lines contain the header and the section
+        // headings. All other double semicolon lines are just comments.
+        if let Some(comment) = trimmed.strip_prefix(";;") {
+            let comment = comment.trim();
+            if let Some(header) = comment.strip_prefix("->>HEADER<<-") {
+                return self.scan_header(header);
+            }
+            if let Some(flags) = comment.strip_prefix("flags:") {
+                return self.scan_flags(flags);
+            }
+            if comment == "OPT PSEUDOSECTION:" {
+                self.state = State::Opt;
+                return Ok(());
+            }
+            if let Some(name) = comment.strip_suffix(" SECTION:") {
+                self.state = match name {
+                    "QUESTION" | "ZONE" => State::Question,
+                    "ANSWER" | "PREREQUISITE" => State::Records(0),
+                    "AUTHORITY" | "UPDATE" => State::Records(1),
+                    "ADDITIONAL" => State::Records(2),
+                    _ => return Err("unknown section"),
+                };
+            }
+            return Ok(());
+        }
+
+        match self.state {
+            State::Header => {
+                if trimmed.is_empty() || trimmed.starts_with(';') {
+                    Ok(())
+                } else {
+                    Err("unexpected data before first section")
+                }
+            }
+            State::Opt => {
+                if let Some(option) = trimmed.strip_prefix(';') {
+                    self.scan_option(option.trim())
+                } else if trimmed.is_empty() {
+                    Ok(())
+                } else {
+                    Err("unexpected data in OPT pseudo-section")
+                }
+            }
+            State::Question => {
+                if let Some(question) = trimmed.strip_prefix(';') {
+                    self.scan_question(question)
+                } else if trimmed.is_empty() {
+                    Ok(())
+                } else {
+                    Err("unexpected data in question section")
+                }
+            }
+            State::Records(section) => {
+                if trimmed.starts_with("$INCLUDE") {
+                    return Err("$INCLUDE not allowed");
+                }
+                self.sections[section].push_line(idx, line);
+                Ok(())
+            }
+        }
+    }
+
+    /// Scans the header line.
+    ///
+    /// The line has the form of comma separated `key: value` pairs.
+    fn scan_header(&mut self, header: &str) -> Result<(), &'static str> {
+        for item in header.split(',') {
+            let (key, value) = match item.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err("invalid header line"),
+            };
+            match key {
+                "opcode" => self.header.set_opcode(
+                    Opcode::from_str(value).map_err(|_| "invalid opcode")?,
+                ),
+                "status" => {
+                    let rcode = OptRcode::from_str(value)
+                        .map_err(|_| "invalid status")?;
+                    self.header.set_rcode(rcode.rcode());
+                    self.rcode = Some(rcode);
+                }
+                "id" => self
+                    .header
+                    .set_id(u16::from_str(value).map_err(|_| "invalid id")?),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Scans the flags line.
+    ///
+    /// The flags end at the first semicolon. Everything after that is the
+    /// section counts which we ignore.
+    fn scan_flags(&mut self, flags: &str) -> Result<(), &'static str> {
+        let flags = flags.split(';').next().unwrap_or("");
+        for flag in flags.split_whitespace() {
+            match flag.to_ascii_lowercase().as_str() {
+                "qr" => self.header.set_qr(true),
+                "aa" => self.header.set_aa(true),
+                "tc" => self.header.set_tc(true),
+                "rd" => self.header.set_rd(true),
+                "ra" => self.header.set_ra(true),
+                "z" => self.header.set_z(true),
+                "ad" => self.header.set_ad(true),
+                "cd" => self.header.set_cd(true),
+                _ => return Err("unknown flag"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Scans a line of the OPT pseudo-section.
+    ///
+    /// The line has the form `name: value`. The name is either `EDNS` for
+    /// the line with the header values of the OPT record or the option code
+    /// of an option.
+    fn scan_option(&mut self, line: &str) -> Result<(), &'static str> {
+        let (name, value) = match line.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => return Err("invalid OPT pseudo-section line"),
+        };
+        if name == "EDNS" {
+            self.opt = Some(OptValues::scan(value)?);
+            return Ok(());
+        }
+        let opt = match self.opt.as_mut() {
+            Some(opt) => opt,
+            None => return Err("option before EDNS line"),
+        };
+        let code =
+            OptionCode::from_str(name).map_err(|_| "unknown option code")?;
+        opt.options.push(UnknownOptData::from_octets(
+            code,
+            scan_option_data(code, value)?,
+        ));
+        Ok(())
+    }
+
+    /// Scans a question.
+    ///
+    /// The question consists of the domain name, an optional class and the
+    /// record type.
+    fn scan_question(&mut self, question: &str) -> Result<(), &'static str> {
+        let mut tokens = question.split_whitespace();
+        let qname = tokens.next().ok_or("missing question name").and_then(
+            |name| Dname::from_str(name).map_err(|_| "invalid question name"),
+        )?;
+        let (qclass, qtype) = match (tokens.next(), tokens.next()) {
+            (Some(qclass), Some(qtype)) => {
+                (Class::from_str(qclass).map_err(|_| "invalid class")?, qtype)
+            }
+            (Some(qtype), None) => (Class::In, qtype),
+            _ => return Err("missing question type"),
+        };
+        let qtype = Rtype::from_str(qtype).map_err(|_| "invalid type")?;
+        if tokens.next().is_some() {
+            return Err("trailing data in question");
+        }
+        self.questions.push(Question::new(qname, qtype, qclass));
+        Ok(())
+    }
+
+    /// Assembles the message.
+    fn into_message(self) -> Result<Message<Vec<u8>>, Error> {
+        let [answer, authority, additional] = self.sections;
+
+        let mut msg = MessageBuilder::new_vec();
+        *msg.header_mut() = self.header;
+        let mut msg = msg.question();
+        for question in &self.questions {
+            msg.push(question)?;
+        }
+        let mut msg = msg.answer();
+        for record in answer.scan()? {
+            msg.push(record)?;
+        }
+        let mut msg = msg.authority();
+        for record in authority.scan()? {
+            msg.push(record)?;
+        }
+        let mut msg = msg.additional();
+        for record in additional.scan()? {
+            msg.push(record)?;
+        }
+        if let Some(opt) = self.opt {
+            let rcode = self.rcode.unwrap_or(OptRcode::NoError);
+            msg.opt(|builder| {
+                builder.set_udp_payload_size(opt.udp_payload_size);
+                builder.set_version(opt.version);
+                builder.set_dnssec_ok(opt.dnssec_ok);
+                builder.set_rcode(rcode);
+                for option in &opt.options {
+                    builder.push(option)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(msg.into_message())
+    }
+}
+
+//------------ State ---------------------------------------------------------
+
+/// Where in the text are we?
+#[derive(Clone, Copy, Debug, Default)]
+enum State {
+    /// Before the first section.
+    #[default]
+    Header,
+
+    /// In the OPT pseudo-section.
+    Opt,
+
+    /// In the question section.
+    Question,
+
+    /// In one of the three record sections.
+    Records(usize),
+}
+
+//------------ SectionText ---------------------------------------------------
+
+/// The text of one of the record sections.
+///
+/// The lines of the section are collected and, once all the text has been
+/// read, scanned via the zonefile scanner. In order for the line numbers in
+/// error messages of that scanner to be correct, all lines not part of the
+/// section are replaced with empty lines.
+#[derive(Default)]
+struct SectionText {
+    /// The collected text.
+    text: String,
+
+    /// The number of lines in `text`.
+    lines: usize,
+}
+
+impl SectionText {
+    /// Adds the line with the given zero-based index to the text.
+    fn push_line(&mut self, idx: usize, line: &str) {
+        while self.lines < idx {
+            self.text.push('\n');
+            self.lines += 1;
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+        self.lines += 1;
+    }
+
+    /// Scans the records of the section.
+    fn scan(self) -> Result<Vec<inplace::ScannedRecord>, Error> {
+        let mut zonefile = Zonefile::from(self.text.as_str());
+        zonefile.set_origin(Dname::<Bytes>::root_bytes());
+        zonefile.set_default_ttl(0);
+        let mut res = Vec::new();
+        while let Some(entry) = zonefile.next_entry()? {
+            match entry {
+                Entry::Record(record) => res.push(record),
+                Entry::Include { .. } => {
+                    // We reject these lines when collecting, so this
+                    // shouldn’t happen.
+                    return Err(Error::syntax(0, "$INCLUDE not allowed"));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+//------------ OptValues -----------------------------------------------------
+
+/// The values of the OPT record.
+#[derive(Default)]
+struct OptValues {
+    /// The UDP payload size.
+    udp_payload_size: u16,
+
+    /// The EDNS version.
+    version: u8,
+
+    /// The DNSSEC OK bit.
+    dnssec_ok: bool,
+
+    /// The options in wire format.
+    options: Vec<UnknownOptData<Vec<u8>>>,
+}
+
+impl OptValues {
+    /// Scans the value of the EDNS line.
+    ///
+    /// This looks like `version: 0, flags: do; udp: 1232`.
+    fn scan(value: &str) -> Result<Self, &'static str> {
+        let mut res = Self::default();
+        for item in value.split(|ch| ch == ';' || ch == ',') {
+            let (key, value) = match item.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            match key {
+                "version" => {
+                    res.version =
+                        u8::from_str(value).map_err(|_| "invalid version")?
+                }
+                "flags" => {
+                    for flag in value.split_whitespace() {
+                        if flag.eq_ignore_ascii_case("do") {
+                            res.dnssec_ok = true
+                        } else {
+                            return Err("unknown EDNS flag");
+                        }
+                    }
+                }
+                "udp" => {
+                    res.udp_payload_size = u16::from_str(value)
+                        .map_err(|_| "invalid UDP payload size")?
+                }
+                _ => {}
+            }
+        }
+        Ok(res)
+    }
+}
+
+//------------ scan_option_data ----------------------------------------------
+
+/// Scans the presentation format of an option into its wire format.
+///
+/// The formats are those produced by the `Display` implementations of the
+/// option types in [`opt`][crate::base::opt]. Options without a dedicated
+/// type are given as a hex string.
+fn scan_option_data(
+    code: OptionCode,
+    value: &str,
+) -> Result<Vec<u8>, &'static str> {
+    match code {
+        OptionCode::Nsid => {
+            // Hex octets separated by spaces followed by an optional
+            // string representation in parentheses.
+            value
+                .split_whitespace()
+                .take_while(|item| !item.starts_with('('))
+                .map(|item| {
+                    u8::from_str_radix(item, 16).map_err(|_| "invalid NSID")
+                })
+                .collect()
+        }
+        OptionCode::Dau | OptionCode::Dhu | OptionCode::N3u => value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| u8::from_str(item).map_err(|_| "invalid algorithm"))
+            .collect(),
+        OptionCode::Expire => {
+            if value.is_empty() {
+                Ok(Vec::new())
+            } else {
+                u32::from_str(value)
+                    .map(|value| value.to_be_bytes().into())
+                    .map_err(|_| "invalid expire value")
+            }
+        }
+        OptionCode::TcpKeepalive => u16::from_str(value)
+            .map(|value| value.to_be_bytes().into())
+            .map_err(|_| "invalid TCP keepalive value"),
+        OptionCode::Padding => {
+            // The length followed by a description of the content.
+            value
+                .split_whitespace()
+                .next()
+                .and_then(|len| usize::from_str(len).ok())
+                .map(|len| vec![0; len])
+                .ok_or("invalid padding")
+        }
+        OptionCode::ClientSubnet => {
+            let mut parts = value.split('/');
+            let addr = parts
+                .next()
+                .and_then(|addr| IpAddr::from_str(addr).ok())
+                .ok_or("invalid client subnet address")?;
+            let source = parts
+                .next()
+                .and_then(|len| u8::from_str(len).ok())
+                .ok_or("invalid client subnet source prefix")?;
+            let scope = match parts.next() {
+                Some(len) => u8::from_str(len)
+                    .map_err(|_| "invalid client subnet scope prefix")?,
+                None => 0,
+            };
+            let mut res = Vec::new();
+            infallible(
+                ClientSubnet::new(source, scope, addr)
+                    .compose_option(&mut res),
+            );
+            Ok(res)
+        }
+        OptionCode::Chain => Dname::<Vec<u8>>::from_str(value)
+            .map(Dname::into_octets)
+            .map_err(|_| "invalid CHAIN name"),
+        OptionCode::KeyTag => {
+            let mut res = Vec::new();
+            for item in value.split(',').map(str::trim) {
+                if !item.is_empty() {
+                    res.extend_from_slice(
+                        &u16::from_str(item)
+                            .map_err(|_| "invalid key tag")?
+                            .to_be_bytes(),
+                    );
+                }
+            }
+            Ok(res)
+        }
+        OptionCode::ExtendedError => {
+            // The error code followed by the optional text in parentheses.
+            let (info, text) = match value.split_once(" (") {
+                Some((info, text)) => (
+                    info,
+                    Some(
+                        text.strip_suffix(')')
+                            .ok_or("invalid extended error text")?,
+                    ),
+                ),
+                None => (value, None),
+            };
+            let info = match info.strip_prefix("EDE") {
+                Some(info) => u16::from_str(info).ok(),
+                None => ExtendedErrorCode::from_mnemonic(info.as_bytes())
+                    .map(ExtendedErrorCode::to_int),
+            }
+            .ok_or("invalid extended error code")?;
+            let mut res = Vec::from(info.to_be_bytes());
+            if let Some(text) = text {
+                res.extend_from_slice(text.as_bytes());
+            }
+            Ok(res)
+        }
+        _ => base16::decode(value).map_err(|_| "invalid option data"),
+    }
+}
+
+//------------ Error ---------------------------------------------------------
+
+/// An error happened while scanning a message.
+#[derive(Debug)]
+pub struct Error(ErrorKind);
+
+#[derive(Debug)]
+enum ErrorKind {
+    /// The text is not in the expected format.
+    Syntax { line: usize, msg: &'static str },
+
+    /// Scanning records failed.
+    Zonefile(inplace::Error),
+
+    /// The message became too large.
+    Push(PushError),
+}
+
+impl Error {
+    fn syntax(line: usize, msg: &'static str) -> Self {
+        Error(ErrorKind::Syntax { line, msg })
+    }
+}
+
+//--- From
+
+impl From<inplace::Error> for Error {
+    fn from(err: inplace::Error) -> Self {
+        Error(ErrorKind::Zonefile(err))
+    }
+}
+
+impl From<PushError> for Error {
+    fn from(err: PushError) -> Self {
+        Error(ErrorKind::Push(err))
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            ErrorKind::Syntax { line, msg } => {
+                write!(f, "{}: {}", line, msg)
+            }
+            ErrorKind::Zonefile(ref err) => err.fmt(f),
+            ErrorKind::Push(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+//============ Tests =========================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::dig::DigOptions;
+    use crate::base::iana::Rcode;
+    use crate::base::opt::rfc7873::ClientCookie;
+    use crate::base::opt::{AllOptData, Cookie};
+    use std::string::ToString;
+
+    /// Checks that scanning and displaying results in the original text.
+    fn round_trip(text: &str) -> Message<Vec<u8>> {
+        let msg = scan_message(text).unwrap();
+        assert_eq!(msg.display_dig().to_string(), text);
+        msg
+    }
+
+    #[test]
+    fn scan_response() {
+        let msg =
+            round_trip(include_str!("../../test-data/dig/response.txt"));
+        let header = msg.header();
+        assert_eq!(header.id(), 4711);
+        assert!(header.qr() && header.rd() && header.ra() && header.ad());
+        assert!(!header.aa());
+        assert_eq!(header.rcode(), Rcode::NoError);
+        let counts = msg.header_counts();
+        assert_eq!(counts.qdcount(), 1);
+        assert_eq!(counts.ancount(), 2);
+        assert_eq!(counts.nscount(), 1);
+        assert_eq!(counts.arcount(), 2);
+        let opt = msg.opt().unwrap();
+        assert_eq!(opt.udp_payload_size(), 1232);
+        assert!(opt.dnssec_ok());
+        assert_eq!(
+            opt.iter::<Cookie>().next().unwrap().unwrap(),
+            Cookie::from(ClientCookie::new([1, 2, 3, 4, 5, 6, 7, 8]))
+        );
+    }
+
+    #[test]
+    fn scan_options() {
+        let msg = round_trip(include_str!("../../test-data/dig/options.txt"));
+        let opt = msg.opt().unwrap();
+        assert_eq!(opt.rcode(msg.header()), OptRcode::BadCookie);
+        assert_eq!(opt.iter::<AllOptData<_, Dname<_>>>().count(), 6);
+    }
+
+    #[test]
+    fn scan_without_ttl() {
+        let msg = scan_message(
+            ";; ANSWER SECTION:\n\
+             example.com.\tIN\tA\t192.0.2.1\n",
+        )
+        .unwrap();
+        assert_eq!(
+            msg.display_dig_with(DigOptions::short()).to_string(),
+            "192.0.2.1\n"
+        );
+        let record = msg.answer().unwrap().next().unwrap().unwrap();
+        assert_eq!(record.ttl(), 0);
+    }
+
+    #[test]
+    fn scan_errors() {
+        assert_eq!(
+            scan_message(";; flags: qr xx; QUERY: 0\n")
+                .unwrap_err()
+                .to_string(),
+            "1: unknown flag"
+        );
+        assert_eq!(
+            scan_message(
+                ";; ANSWER SECTION:\n\
+                 example.com.\t3600\tIN\tA\t192.0.2.1\n\
+                 example.com.\t3600\tIN\tA\tbogus\n"
+            )
+            .unwrap_err()
+            .to_string()
+            .split(':')
+            .next(),
+            Some("3")
+        );
+    }
+}
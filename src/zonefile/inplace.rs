@@ -165,6 +165,15 @@ impl Zonefile {
         self.origin = Some(origin)
     }
 
+    /// Sets the default TTL of the zonefile.
+    ///
+    /// The TTL is used for records that don’t provide a TTL of their own
+    /// until a record does provide one. This has the same effect as a
+    /// `$TTL` directive at the very beginning of the data.
+    pub fn set_default_ttl(&mut self, ttl: u32) {
+        self.last_ttl = Some(ttl)
+    }
+
     /// Returns the next entry in the zonefile.
     ///
     /// Returns `Ok(None)` if the end of the file has been reached. Returns
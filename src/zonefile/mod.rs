@@ -2,4 +2,5 @@
 #![cfg(feature = "zonefile")]
 #![cfg_attr(docsrs, doc(cfg(feature = "zonefile")))]
 
+pub mod dig;
 pub mod inplace;